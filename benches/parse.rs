@@ -0,0 +1,160 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::hint::black_box;
+use trivial_argument_parser::{
+    argument::{
+        legacy_argument::*, parsable_argument::ParsableValueArgument, ArgumentIdentification,
+    },
+    options::{ParserLimits, ParserOptions},
+    ArgumentList,
+};
+
+const ARGUMENTS: usize = 500;
+const TOKENS: usize = 10_000;
+
+/// List with ARGUMENTS legacy arguments taking values and ARGUMENTS owned parsable arguments.
+fn large_list() -> ArgumentList<'static> {
+    let mut args_list = ArgumentList::new();
+    args_list.set_options(ParserOptions::new().limits(ParserLimits::unlimited()));
+    for i in 0..ARGUMENTS {
+        args_list.append_arg(
+            Argument::new(None, Some(&format!("value-{}", i)), ArgType::ValueList).unwrap(),
+        );
+        args_list.register_parsable_boxed(Box::new(ParsableValueArgument::new_integer(
+            ArgumentIdentification::Long(format!("number-{}", i)),
+        )));
+    }
+    args_list
+}
+
+/// Input of TOKENS values alternating between legacy and parsable arguments.
+fn large_input() -> Vec<String> {
+    (0..TOKENS / 2)
+        .flat_map(|i| {
+            let argument = i % ARGUMENTS;
+            if i % 2 == 0 {
+                vec![format!("--value-{}", argument), format!("text-{}", i)]
+            } else {
+                vec![format!("--number-{}", argument), i.to_string()]
+            }
+        })
+        .collect()
+}
+
+fn parse_long_names(c: &mut Criterion) {
+    let input = large_input();
+    c.bench_function("parse 10k tokens, 500 arguments", |b| {
+        b.iter_batched(
+            || (large_list(), input.clone()),
+            |(mut args_list, input)| {
+                args_list.parse_args(input).unwrap();
+                black_box(args_list)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// List with ARGUMENTS long flags. With `general` a parsable argument is added, so flag-only fast
+/// path is not used.
+fn flag_list(general: bool) -> ArgumentList<'static> {
+    let mut args_list = ArgumentList::new();
+    for i in 0..ARGUMENTS {
+        args_list
+            .append_arg(Argument::new(None, Some(&format!("flag-{}", i)), ArgType::Flag).unwrap());
+    }
+    if general {
+        args_list.register_parsable_boxed(Box::new(ParsableValueArgument::new_integer(
+            ArgumentIdentification::Long(String::from("number")),
+        )));
+    }
+    args_list
+}
+
+fn parse_flags(c: &mut Criterion) {
+    // Flags can be given only once, so every flag is given in reverse order of registration.
+    let input: Vec<String> = (0..ARGUMENTS)
+        .rev()
+        .map(|i| format!("--flag-{}", i))
+        .collect();
+    for (name, general) in [
+        ("parse 500 flags", false),
+        ("parse 500 flags, general path", true),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter_batched(
+                || (flag_list(general), input.clone()),
+                |(mut args_list, input)| {
+                    args_list.parse_args(input).unwrap();
+                    black_box(args_list)
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
+fn parse_positionals(c: &mut Criterion) {
+    let input: Vec<String> = (0..TOKENS).map(|i| format!("file-{}", i)).collect();
+    c.bench_function("parse 10k positionals, 500 arguments", |b| {
+        b.iter_batched(
+            || (large_list(), input.clone()),
+            |(mut args_list, input)| {
+                args_list.parse_args(input).unwrap();
+                black_box(args_list)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Input of TOKENS values given only to parsable arguments, so every legacy lookup misses.
+fn parse_parsable_names(c: &mut Criterion) {
+    let input: Vec<String> = (0..TOKENS / 2)
+        .flat_map(|i| vec![format!("--number-{}", i % ARGUMENTS), i.to_string()])
+        .collect();
+    c.bench_function("parse 10k tokens, parsable arguments only", |b| {
+        b.iter_batched(
+            || (large_list(), input.clone()),
+            |(mut args_list, input)| {
+                args_list.parse_args(input).unwrap();
+                black_box(args_list)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Registering TOKENS legacy arguments interns every long name, then each is looked up once.
+fn register_long_names(c: &mut Criterion) {
+    let names: Vec<String> = (0..TOKENS).map(|i| format!("option-{}", i)).collect();
+    let input: Vec<String> = names
+        .iter()
+        .rev()
+        .map(|name| format!("--{}", name))
+        .collect();
+    c.bench_function("register and parse 10k long flags", |b| {
+        b.iter_batched(
+            || input.clone(),
+            |input| {
+                let mut args_list = ArgumentList::new();
+                args_list.set_options(ParserOptions::new().limits(ParserLimits::unlimited()));
+                for name in &names {
+                    args_list.append_arg(Argument::new(None, Some(name), ArgType::Flag).unwrap());
+                }
+                args_list.parse_args(input).unwrap();
+                black_box(args_list)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    parse_long_names,
+    parse_flags,
+    parse_positionals,
+    parse_parsable_names,
+    register_long_names
+);
+criterion_main!(benches);
@@ -0,0 +1,188 @@
+use super::{
+    legacy_argument::ArgType,
+    parsable_argument::{BoxFuture, HandleableArgument},
+    ArgumentIdentification,
+};
+use crate::cursor::InputCursor;
+use std::future::Future;
+
+/// Signature of async value handlers after boxing their futures.
+type AsyncHandler<V> = dyn Fn(String) -> BoxFuture<'static, Result<V, String>>;
+
+///
+/// Parsable argument whose values are converted by an async handler (e.g. validating that URL
+/// resolves). During parsing every occurrence takes a single input value, handler is awaited for
+/// all of them by ArgumentList::parse_args_async. Values are not available after synchronous
+/// parse_args. Available with `async` feature.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::{async_argument::AsyncParsableValueArgument, ArgumentIdentification},
+///     error::ParseError,
+/// };
+///
+/// async fn resolve_host(host: String) -> Result<String, String> {
+///     Result::Ok(host.to_lowercase())
+/// }
+///
+/// async fn run() -> Result<(), ParseError> {
+///     let mut argument_host =
+///         AsyncParsableValueArgument::new(ArgumentIdentification::Short('h'), resolve_host);
+///     let mut args_list = ArgumentList::new();
+///     args_list.register_parsable(&mut argument_host);
+///     args_list
+///         .parse_args_async(vec![String::from("-h"), String::from("Example.com")])
+///         .await?;
+///     assert_eq!(argument_host.first_value().unwrap(), "example.com");
+///     Ok(())
+/// }
+/// ```
+pub struct AsyncParsableValueArgument<V> {
+    identification: ArgumentIdentification,
+    handler: Box<AsyncHandler<V>>,
+    pending: Vec<String>,
+    values: Vec<V>,
+    description: Option<String>,
+}
+
+impl<V: 'static> AsyncParsableValueArgument<V> {
+    pub fn new<F, Fut>(identification: ArgumentIdentification, handler: F) -> Self
+    where
+        F: Fn(String) -> Fut + 'static,
+        Fut: Future<Output = Result<V, String>> + 'static,
+    {
+        AsyncParsableValueArgument {
+            identification,
+            handler: Box::new(move |v| Box::pin(handler(v))),
+            pending: Vec::new(),
+            values: Vec::new(),
+            description: None,
+        }
+    }
+
+    /// Sets description displayed next to argument in help text.
+    pub fn set_description(mut self, description: &str) -> Self {
+        self.description = Some(String::from(description));
+        self
+    }
+
+    pub fn first_value(&self) -> Option<&V> {
+        self.values.first()
+    }
+
+    pub fn values(&self) -> &Vec<V> {
+        &self.values
+    }
+}
+
+impl<'a, V: 'static> HandleableArgument<'a> for AsyncParsableValueArgument<V> {
+    fn handle(&mut self, input_iter: &mut InputCursor<'_>) -> Result<(), String> {
+        if let Some(v) = input_iter.next() {
+            self.pending.push(v.clone());
+            Result::Ok(())
+        } else {
+            Result::Err(String::from("No remaining input values."))
+        }
+    }
+
+    fn is_by_short(&self, name: char) -> bool {
+        self.identification.is_by_short(name)
+    }
+
+    fn is_by_long(&self, name: &str) -> bool {
+        self.identification.is_by_long(name)
+    }
+
+    fn identification(&self) -> &ArgumentIdentification {
+        &self.identification
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn arg_type(&self) -> ArgType {
+        ArgType::ValueList
+    }
+
+    fn resolve_async(&mut self) -> Option<BoxFuture<'_, Result<(), String>>> {
+        Some(Box::pin(async move {
+            for v in std::mem::take(&mut self.pending) {
+                let value = (self.handler)(v).await?;
+                self.values.push(value);
+            }
+            Result::Ok(())
+        }))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+    };
+
+    use super::AsyncParsableValueArgument;
+    use crate::{argument::ArgumentIdentification, error::ParseError, ArgumentList};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Minimal executor polling future until it completes.
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = Pin::as_mut(&mut future).poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    async fn parse_port(v: String) -> Result<u16, String> {
+        v.parse().map_err(|_| format!("'{}' is not a port", v))
+    }
+
+    #[test]
+    fn parse_args_async_works() {
+        let mut argument_port =
+            AsyncParsableValueArgument::new(ArgumentIdentification::Short('p'), parse_port);
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_port);
+        let input = vec![
+            String::from("-p"),
+            String::from("80"),
+            String::from("-p"),
+            String::from("443"),
+        ];
+        block_on(args_list.parse_args_async(input)).unwrap();
+        assert_eq!(argument_port.values(), &vec![80, 443]);
+    }
+
+    #[test]
+    fn parse_args_async_fails() {
+        let mut argument_port =
+            AsyncParsableValueArgument::new(ArgumentIdentification::Short('p'), parse_port);
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_port);
+        assert_eq!(
+            block_on(args_list.parse_args_async(vec![String::from("-p"), String::from("x")])),
+            Err(ParseError::InvalidValue {
+                argument: String::from("-p"),
+                message: String::from("'x' is not a port"),
+                usage: String::from("-p <VALUE>"),
+                value: None,
+                suggestion: None,
+            })
+        );
+    }
+}
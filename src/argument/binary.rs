@@ -0,0 +1,173 @@
+use super::{parsable_argument::ParsableValueArgument, ArgumentIdentification};
+use crate::cursor::InputCursor;
+
+/// Decodes hexadecimal string. Both lower and upper case digits are accepted.
+fn decode_hex(input: &str) -> Result<Vec<u8>, String> {
+    if !input.len().is_multiple_of(2) {
+        return Result::Err(String::from("Hex value must have even number of digits"));
+    }
+    input
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let digits = std::str::from_utf8(pair).ok();
+            digits
+                .filter(|d| d.chars().all(|c| c.is_ascii_hexdigit()))
+                .and_then(|d| u8::from_str_radix(d, 16).ok())
+                .ok_or_else(|| String::from("Hex value contains invalid digit"))
+        })
+        .collect()
+}
+
+fn base64_digit(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes padded base64 string using standard alphabet. Non-canonical encodings (non-zero
+/// trailing bits) are rejected.
+fn decode_base64(input: &str) -> Result<Vec<u8>, String> {
+    let invalid = || String::from("Value is not valid base64");
+    let bytes = input.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return Result::Err(invalid());
+    }
+    let padding = bytes.iter().rev().take_while(|b| **b == b'=').count();
+    if padding > 2 {
+        return Result::Err(invalid());
+    }
+    let digits = bytes[..bytes.len() - padding]
+        .iter()
+        .map(|b| base64_digit(*b))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(invalid)?;
+    let mut decoded = Vec::with_capacity(digits.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for digit in digits {
+        buffer = (buffer << 6) | u32::from(digit);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    if buffer != 0 {
+        return Result::Err(invalid());
+    }
+    Result::Ok(decoded)
+}
+
+impl ParsableValueArgument<Vec<u8>> {
+    fn new_binary(
+        identification: ArgumentIdentification,
+        max_size: Option<usize>,
+        decode: fn(&str) -> Result<Vec<u8>, String>,
+    ) -> ParsableValueArgument<Vec<u8>> {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<Vec<u8>>| {
+            let decoded = input_iter.next_decoded(|v| {
+                let decoded = decode(v)?;
+                match max_size {
+                    Some(max_size) if decoded.len() > max_size => Result::Err(format!(
+                        "Value has {} bytes, but at most {} are allowed",
+                        decoded.len(),
+                        max_size
+                    )),
+                    _ => Result::Ok(decoded),
+                }
+            })?;
+            values.push(decoded);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+
+    /// Argument accepting binary data encoded with padded base64, e.g. keys or tokens. Values
+    /// decoding to more than `max_size` bytes are rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_key =
+    ///     ParsableValueArgument::new_base64(ArgumentIdentification::Long(String::from("key")), Some(16));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_key);
+    /// args_list
+    ///     .parse_args(vec![String::from("--key"), String::from("aGVsbG8=")])
+    ///     .unwrap();
+    /// assert_eq!(argument_key.first_value(), Some(&b"hello".to_vec()));
+    /// ```
+    pub fn new_base64(
+        identification: ArgumentIdentification,
+        max_size: Option<usize>,
+    ) -> ParsableValueArgument<Vec<u8>> {
+        ParsableValueArgument::new_binary(identification, max_size, decode_base64)
+    }
+
+    /// Argument accepting binary data encoded as hexadecimal digits (`deadbeef`). Values decoding
+    /// to more than `max_size` bytes are rejected.
+    pub fn new_hex(
+        identification: ArgumentIdentification,
+        max_size: Option<usize>,
+    ) -> ParsableValueArgument<Vec<u8>> {
+        ParsableValueArgument::new_binary(identification, max_size, decode_hex)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cursor::InputCursor;
+
+    use super::{decode_base64, decode_hex};
+    use crate::argument::{
+        parsable_argument::{HandleableArgument, ParsableValueArgument},
+        ArgumentIdentification,
+    };
+
+    #[test]
+    fn decode_base64_works() {
+        assert_eq!(decode_base64(""), Ok(Vec::new()));
+        assert_eq!(decode_base64("Zg=="), Ok(b"f".to_vec()));
+        assert_eq!(decode_base64("Zm8="), Ok(b"fo".to_vec()));
+        assert_eq!(
+            decode_base64("Zm9v+/8="),
+            Ok(vec![b'f', b'o', b'o', 0xfb, 0xff])
+        );
+        for invalid in ["Zg", "Zh==", "Z===", "Zm9v-_8=", "Z=g="].iter() {
+            assert!(decode_base64(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn decode_hex_works() {
+        assert_eq!(decode_hex("DEADbeef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert!(decode_hex("abc").is_err());
+        assert!(decode_hex("+1").is_err());
+        assert!(decode_hex("zz").is_err());
+    }
+
+    #[test]
+    fn max_size_works() {
+        let mut arg = ParsableValueArgument::new_hex(ArgumentIdentification::Short('k'), Some(2));
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("010203")])),
+            Err(String::from(
+                "error in -k: '010203': Value has 3 bytes, but at most 2 are allowed"
+            ))
+        );
+        arg.handle(&mut InputCursor::new(&[String::from("0102")]))
+            .unwrap();
+        assert_eq!(arg.first_value(), Some(&vec![1, 2]));
+    }
+}
@@ -9,34 +9,30 @@ pub struct ArgBuilder {
 impl ArgBuilder {
     /// Builder needs to be created with argument type specified.
     pub fn new(arg_type: ArgType) -> ArgBuilder {
-        return ArgBuilder {
+        ArgBuilder {
             arg_type,
             short_name: None,
             long_name: None,
-        };
+        }
     }
 
     pub fn set_short_name(mut self, short_name: char) -> ArgBuilder {
         self.short_name = Some(short_name);
-        return self;
+        self
     }
 
     pub fn set_long_name(mut self, long_name: &str) -> ArgBuilder {
         self.long_name = Some(String::from(long_name));
-        return self;
+        self
     }
 
     pub fn set_type(mut self, new_type: ArgType) -> ArgBuilder {
         self.arg_type = new_type;
-        return self;
+        self
     }
 
     pub fn build(&self) -> Result<Argument, String> {
-        let long = if let Some(ref l) = self.long_name {
-            Option::Some(l.as_str())
-        } else {
-            Option::None
-        };
+        let long = self.long_name.as_deref();
         Argument::new(self.short_name, long, self.arg_type)
     }
 }
@@ -0,0 +1,138 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// Counters describing how effective value cache was.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    /// Number of values returned from cache.
+    pub hits: usize,
+    /// Number of values which had to be parsed.
+    pub misses: usize,
+    /// Number of distinct input values stored in cache.
+    pub entries: usize,
+}
+
+/// Signature of functions parsing values which are not cached yet.
+type ValueParser<V> = dyn Fn(&str) -> Result<V, String>;
+
+struct CacheState<V> {
+    results: HashMap<String, Result<V, String>>,
+    stats: CacheStats,
+}
+
+///
+/// Memoizes results of expensive value parsing (e.g. path canonicalization) keyed by input value.
+/// Handles are cheap to clone and share the same storage, so one cache can be used by multiple
+/// arguments or across repeated parses.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::{
+///         cache::ValueCache, parsable_argument::ParsableValueArgument, ArgumentIdentification,
+///     },
+/// };
+///
+/// let cache = ValueCache::new(|v| Result::Ok(v.to_uppercase()));
+/// let mut argument_str = ParsableValueArgument::new_cached(
+///     ArgumentIdentification::Short('n'),
+///     cache.clone(),
+/// );
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_parsable(&mut argument_str);
+/// let input = vec!["-n", "a", "-n", "a"];
+/// args_list
+///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+///     .unwrap();
+/// assert_eq!(cache.stats().hits, 1);
+/// assert_eq!(cache.stats().misses, 1);
+/// ```
+pub struct ValueCache<V> {
+    state: Rc<RefCell<CacheState<V>>>,
+    parse: Rc<ValueParser<V>>,
+}
+
+impl<V: Clone> ValueCache<V> {
+    /// Creates empty cache using given function to parse values not yet cached.
+    pub fn new<F>(parse: F) -> ValueCache<V>
+    where
+        F: Fn(&str) -> Result<V, String> + 'static,
+    {
+        ValueCache {
+            state: Rc::new(RefCell::new(CacheState {
+                results: HashMap::new(),
+                stats: CacheStats::default(),
+            })),
+            parse: Rc::new(parse),
+        }
+    }
+
+    /// Returns parse result of input value. Parses value only if it was not seen before.
+    /// Errors are cached as well.
+    pub fn get(&self, value: &str) -> Result<V, String> {
+        let mut state = self.state.borrow_mut();
+        if let Some(result) = state.results.get(value) {
+            let result = result.clone();
+            state.stats.hits += 1;
+            return result;
+        }
+        let result = (self.parse)(value);
+        state.results.insert(String::from(value), result.clone());
+        state.stats.misses += 1;
+        state.stats.entries = state.results.len();
+        result
+    }
+
+    /// Returns current cache statistics.
+    pub fn stats(&self) -> CacheStats {
+        self.state.borrow().stats
+    }
+
+    /// Removes all cached values and resets statistics.
+    pub fn clear(&self) {
+        let mut state = self.state.borrow_mut();
+        state.results.clear();
+        state.stats = CacheStats::default();
+    }
+}
+
+impl<V> Clone for ValueCache<V> {
+    fn clone(&self) -> Self {
+        ValueCache {
+            state: Rc::clone(&self.state),
+            parse: Rc::clone(&self.parse),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::{CacheStats, ValueCache};
+
+    #[test]
+    fn get_works() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&calls);
+        let cache = ValueCache::new(move |v: &str| {
+            counter.set(counter.get() + 1);
+            v.parse::<i64>().map_err(|err| format!("{}", err))
+        });
+        assert_eq!(cache.get("1"), Ok(1));
+        assert_eq!(cache.get("1"), Ok(1));
+        assert!(cache.get("x").is_err());
+        assert!(cache.clone().get("x").is_err());
+        assert_eq!(calls.get(), 2);
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 2,
+                misses: 2,
+                entries: 2
+            }
+        );
+        cache.clear();
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+}
@@ -0,0 +1,144 @@
+use super::{parsable_argument::ParsableValueArgument, ArgumentIdentification};
+use crate::{cursor::InputCursor, suggest};
+use std::{fmt, str::FromStr};
+
+/// RGB color given by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    /// Named colors accepted in addition to `#RRGGBB` and `rgb(r, g, b)` notation.
+    pub const NAMED: [(&'static str, Color); 10] = [
+        ("black", Color::new(0, 0, 0)),
+        ("white", Color::new(255, 255, 255)),
+        ("red", Color::new(255, 0, 0)),
+        ("green", Color::new(0, 128, 0)),
+        ("blue", Color::new(0, 0, 255)),
+        ("yellow", Color::new(255, 255, 0)),
+        ("cyan", Color::new(0, 255, 255)),
+        ("magenta", Color::new(255, 0, 255)),
+        ("gray", Color::new(128, 128, 128)),
+        ("orange", Color::new(255, 165, 0)),
+    ];
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    fn from_hex(hex: &str) -> Option<Color> {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Color::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    fn from_rgb(channels: &str) -> Result<Color, String> {
+        let channels = channels
+            .split(',')
+            .map(|c| c.trim().parse::<u8>())
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| String::from("Channels of rgb() must be numbers from 0 to 255"))?;
+        match channels.as_slice() {
+            [r, g, b] => Result::Ok(Color::new(*r, *g, *b)),
+            _ => Result::Err(String::from("rgb() takes exactly 3 channels")),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex) = s.strip_prefix('#') {
+            return Color::from_hex(hex)
+                .ok_or_else(|| format!("'{}' is not a color in #RRGGBB format", s));
+        }
+        if let Some(channels) = s.strip_prefix("rgb(").and_then(|c| c.strip_suffix(')')) {
+            return Color::from_rgb(channels);
+        }
+        let name = s.to_lowercase();
+        for (color_name, color) in Color::NAMED.iter() {
+            if *color_name == name {
+                return Result::Ok(*color);
+            }
+        }
+        Result::Err(suggest::invalid_choice(
+            s,
+            Color::NAMED.iter().map(|(name, _)| *name),
+        ))
+    }
+}
+
+impl ParsableValueArgument<Color> {
+    /// Argument accepting color written as `#RRGGBB`, `rgb(r, g, b)` or one of Color::NAMED.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{color::Color, parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_color =
+    ///     ParsableValueArgument::new_color(ArgumentIdentification::Long(String::from("fg")));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_color);
+    /// args_list
+    ///     .parse_args(vec![String::from("--fg"), String::from("#ff8000")])
+    ///     .unwrap();
+    /// assert_eq!(argument_color.first_value(), Some(&Color::new(255, 128, 0)));
+    /// ```
+    pub fn new_color(identification: ArgumentIdentification) -> ParsableValueArgument<Color> {
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<Color>| {
+            values.push(input_iter.next_decoded(|v| v.parse())?);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Color;
+
+    #[test]
+    fn from_str_works() {
+        assert_eq!("#0a0B0c".parse(), Ok(Color::new(10, 11, 12)));
+        assert_eq!("rgb(1, 2,3)".parse(), Ok(Color::new(1, 2, 3)));
+        assert_eq!("Orange".parse(), Ok(Color::new(255, 165, 0)));
+        assert_eq!(
+            "#12345".parse::<Color>(),
+            Err(String::from("'#12345' is not a color in #RRGGBB format"))
+        );
+        assert_eq!(
+            "rgb(1, 2, 256)".parse::<Color>(),
+            Err(String::from(
+                "Channels of rgb() must be numbers from 0 to 255"
+            ))
+        );
+        assert_eq!(
+            "rgb(1, 2)".parse::<Color>(),
+            Err(String::from("rgb() takes exactly 3 channels"))
+        );
+        assert_eq!(
+            "gren".parse::<Color>(),
+            Err(String::from("'gren' is not valid; did you mean 'green'?"))
+        );
+    }
+
+    #[test]
+    fn display_works() {
+        assert_eq!(format!("{}", Color::new(255, 128, 0)), "#ff8000");
+    }
+}
@@ -0,0 +1,229 @@
+use super::{parsable_argument::ParsableValueArgument, ArgumentIdentification};
+use crate::cursor::InputCursor;
+use std::{fmt, str::FromStr};
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Range of values accepted in a single field of cron expression.
+struct FieldSpec {
+    name: &'static str,
+    min: u8,
+    max: u8,
+    names: &'static [&'static str],
+}
+
+const SECONDS: FieldSpec = FieldSpec {
+    name: "seconds",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const MINUTES: FieldSpec = FieldSpec {
+    name: "minutes",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOURS: FieldSpec = FieldSpec {
+    name: "hours",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAYS_OF_MONTH: FieldSpec = FieldSpec {
+    name: "day of month",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTHS: FieldSpec = FieldSpec {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &MONTH_NAMES,
+};
+// 7 is accepted as Sunday, like in most cron implementations.
+const DAYS_OF_WEEK: FieldSpec = FieldSpec {
+    name: "day of week",
+    min: 0,
+    max: 7,
+    names: &DAY_NAMES,
+};
+
+impl FieldSpec {
+    fn value(&self, value: &str) -> Option<u8> {
+        let upper = value.to_ascii_uppercase();
+        if let Some(i) = self.names.iter().position(|name| *name == upper) {
+            return Some(self.min + i as u8);
+        }
+        value
+            .parse()
+            .ok()
+            .filter(|v| (self.min..=self.max).contains(v))
+    }
+
+    /// Expands field (`*`, `1-5`, `*/15`, `MON,WED`) into sorted list of matching values.
+    fn parse(&self, field: &str) -> Result<Vec<u8>, String> {
+        let invalid = || format!("Invalid {} field '{}'", self.name, field);
+        let mut values = Vec::new();
+        for item in field.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (
+                    range,
+                    step.parse::<u8>()
+                        .ok()
+                        .filter(|s| *s > 0)
+                        .ok_or_else(invalid)?,
+                ),
+                None => (item, 1),
+            };
+            let (start, end) = if range == "*" {
+                (self.min, self.max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                let start = self.value(start).ok_or_else(invalid)?;
+                let end = self.value(end).ok_or_else(invalid)?;
+                if start > end {
+                    return Result::Err(invalid());
+                }
+                (start, end)
+            } else {
+                let value = self.value(range).ok_or_else(invalid)?;
+                // `5/10` means every 10 starting at 5.
+                if item.contains('/') {
+                    (value, self.max)
+                } else {
+                    (value, value)
+                }
+            };
+            values.extend((start..=end).step_by(step.into()));
+        }
+        if self.max == 7 {
+            // Sunday written as 7.
+            for value in values.iter_mut() {
+                if *value == 7 {
+                    *value = 0;
+                }
+            }
+        }
+        values.sort_unstable();
+        values.dedup();
+        Result::Ok(values)
+    }
+}
+
+/// Validated 5 field (`min hour day month weekday`) or 6 field (with leading seconds) cron
+/// expression. Every field is expanded to sorted list of matching values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    pub expression: String,
+    pub seconds: Option<Vec<u8>>,
+    pub minutes: Vec<u8>,
+    pub hours: Vec<u8>,
+    pub days_of_month: Vec<u8>,
+    pub months: Vec<u8>,
+    /// Days of week with Sunday as 0.
+    pub days_of_week: Vec<u8>,
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let (seconds, rest) = match fields.len() {
+            5 => (None, &fields[..]),
+            6 => (Some(SECONDS.parse(fields[0])?), &fields[1..]),
+            count => {
+                return Result::Err(format!(
+                    "Cron expression must have 5 or 6 fields, but has {}",
+                    count
+                ))
+            }
+        };
+        Result::Ok(CronSchedule {
+            expression: fields.join(" "),
+            seconds,
+            minutes: MINUTES.parse(rest[0])?,
+            hours: HOURS.parse(rest[1])?,
+            days_of_month: DAYS_OF_MONTH.parse(rest[2])?,
+            months: MONTHS.parse(rest[3])?,
+            days_of_week: DAYS_OF_WEEK.parse(rest[4])?,
+        })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl ParsableValueArgument<CronSchedule> {
+    /// Argument accepting cron expression, e.g. `--schedule "*/15 9-17 * * MON-FRI"`.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_schedule =
+    ///     ParsableValueArgument::new_cron(ArgumentIdentification::Long(String::from("schedule")));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_schedule);
+    /// args_list
+    ///     .parse_args(vec![String::from("--schedule"), String::from("0 */6 * * MON-FRI")])
+    ///     .unwrap();
+    /// let schedule = argument_schedule.first_value().unwrap();
+    /// assert_eq!(schedule.hours, vec![0, 6, 12, 18]);
+    /// assert_eq!(schedule.days_of_week, vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn new_cron(identification: ArgumentIdentification) -> ParsableValueArgument<CronSchedule> {
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<CronSchedule>| {
+            values.push(input_iter.next_decoded(|v| v.parse())?);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CronSchedule;
+
+    #[test]
+    fn from_str_works() {
+        let schedule: CronSchedule = "30 5/20 1,15 jan-mar sun,7".parse().unwrap();
+        assert_eq!(schedule.seconds, None);
+        assert_eq!(schedule.minutes, vec![30]);
+        assert_eq!(schedule.hours, vec![5]);
+        assert_eq!(schedule.days_of_month, vec![1, 15]);
+        assert_eq!(schedule.months, vec![1, 2, 3]);
+        assert_eq!(schedule.days_of_week, vec![0]);
+        let schedule: CronSchedule = "*/20  0 0 * * *".parse().unwrap();
+        assert_eq!(schedule.seconds, Some(vec![0, 20, 40]));
+        assert_eq!(format!("{}", schedule), "*/20 0 0 * * *");
+    }
+
+    #[test]
+    fn from_str_rejects_invalid() {
+        assert_eq!(
+            "* * *".parse::<CronSchedule>(),
+            Err(String::from(
+                "Cron expression must have 5 or 6 fields, but has 3"
+            ))
+        );
+        assert_eq!(
+            "60 * * * *".parse::<CronSchedule>(),
+            Err(String::from("Invalid minutes field '60'"))
+        );
+        assert!("* * 0 * *".parse::<CronSchedule>().is_err());
+        assert!("* 5-1 * * *".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+        assert!("* * * * FOO".parse::<CronSchedule>().is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use super::{parsable_argument::ParsableValueArgument, ArgumentIdentification};
+use crate::cursor::InputCursor;
+
+/// Single `key`, `operator`, `value` condition, e.g. `age>3`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub key: String,
+    pub operator: String,
+    pub value: String,
+}
+
+///
+/// Operators recognized in filter expressions. When several operators match at the same
+/// position, the longest one is used, so `>=` is not read as `>` followed by `=value`.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::argument::filter::{Filter, FilterOperators};
+///
+/// let operators = FilterOperators::new(&[":", "~"]);
+/// assert_eq!(
+///     operators.parse("name~ali,role:admin").unwrap()[1],
+///     Filter {
+///         key: String::from("role"),
+///         operator: String::from(":"),
+///         value: String::from("admin"),
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct FilterOperators {
+    operators: Vec<String>,
+}
+
+impl FilterOperators {
+    /// Creates table containing only given operators.
+    pub fn new(operators: &[&str]) -> FilterOperators {
+        FilterOperators {
+            operators: operators.iter().map(|o| String::from(*o)).collect(),
+        }
+    }
+
+    /// Adds operator to the table.
+    pub fn operator(mut self, operator: &str) -> FilterOperators {
+        self.operators.push(String::from(operator));
+        self
+    }
+
+    /// Finds position and length of operator used in the term.
+    fn find(&self, term: &str) -> Option<(usize, usize)> {
+        self.operators
+            .iter()
+            .filter(|o| !o.is_empty())
+            .filter_map(|o| term.find(o.as_str()).map(|position| (position, o.len())))
+            .min_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+    }
+
+    /// Parses comma-separated list of conditions.
+    pub fn parse(&self, expression: &str) -> Result<Vec<Filter>, String> {
+        expression
+            .split(',')
+            .map(|term| match self.find(term) {
+                Some((position, _)) if term[..position].trim().is_empty() => {
+                    Result::Err(format!("Condition '{}' does not name a key", term))
+                }
+                Some((position, length)) => Result::Ok(Filter {
+                    key: String::from(term[..position].trim()),
+                    operator: String::from(&term[position..position + length]),
+                    value: String::from(term[position + length..].trim()),
+                }),
+                None => Result::Err(format!(
+                    "Condition '{}' does not use any of operators: {}",
+                    term,
+                    self.operators.join(" ")
+                )),
+            })
+            .collect()
+    }
+}
+
+impl Default for FilterOperators {
+    /// Comparison operators `=`, `!=`, `<`, `<=`, `>`, `>=` and `~` (matches).
+    fn default() -> Self {
+        FilterOperators::new(&["=", "!=", "<", "<=", ">", ">=", "~"])
+    }
+}
+
+impl ParsableValueArgument<Filter> {
+    /// Argument accepting comma-separated conditions (`--filter name=foo,age>3`). Conditions of
+    /// all occurrences are collected into values.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{
+    ///         filter::FilterOperators, parsable_argument::ParsableValueArgument,
+    ///         ArgumentIdentification,
+    ///     },
+    /// };
+    ///
+    /// let mut argument_filter = ParsableValueArgument::new_filter(
+    ///     ArgumentIdentification::Long(String::from("filter")),
+    ///     FilterOperators::default(),
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_filter);
+    /// args_list
+    ///     .parse_args(vec![String::from("--filter"), String::from("name=foo,age>=3")])
+    ///     .unwrap();
+    /// let filters = argument_filter.values();
+    /// assert_eq!(filters.len(), 2);
+    /// assert_eq!(filters[1].key, "age");
+    /// assert_eq!(filters[1].operator, ">=");
+    /// assert_eq!(filters[1].value, "3");
+    /// ```
+    pub fn new_filter(
+        identification: ArgumentIdentification,
+        operators: FilterOperators,
+    ) -> ParsableValueArgument<Filter> {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<Filter>| {
+            values.extend(input_iter.next_decoded(|v| operators.parse(v))?);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Filter, FilterOperators};
+
+    fn filter(key: &str, operator: &str, value: &str) -> Filter {
+        Filter {
+            key: String::from(key),
+            operator: String::from(operator),
+            value: String::from(value),
+        }
+    }
+
+    #[test]
+    fn parse_works() {
+        let operators = FilterOperators::default();
+        assert_eq!(
+            operators.parse("name=foo, age>3,size<=10,tag!=x=y"),
+            Ok(vec![
+                filter("name", "=", "foo"),
+                filter("age", ">", "3"),
+                filter("size", "<=", "10"),
+                filter("tag", "!=", "x=y"),
+            ])
+        );
+        assert_eq!(
+            operators.parse("=foo"),
+            Err(String::from("Condition '=foo' does not name a key"))
+        );
+        assert_eq!(
+            operators.parse("age3"),
+            Err(String::from(
+                "Condition 'age3' does not use any of operators: = != < <= > >= ~"
+            ))
+        );
+    }
+
+    #[test]
+    fn custom_operators_work() {
+        let operators = FilterOperators::new(&[]).operator(" in ");
+        assert_eq!(
+            operators.parse("color in red|blue"),
+            Ok(vec![filter("color", " in ", "red|blue")])
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use std::iter::Peekable;
+use std::{fmt, iter::Peekable};
+
+use super::ArgumentIdentification;
+use crate::{cursor::InputCursor, parsed::write_values, source::Source};
 
 /**
 Enum allowing to choose the type of argument.
@@ -55,11 +58,7 @@ impl Argument {
         }
 
         // Check if long name is defined, if so use it
-        let long_owned: Option<String> = if let Some(text) = long {
-            Option::Some(String::from(text))
-        } else {
-            None
-        };
+        let long_owned: Option<String> = long.map(String::from);
 
         Ok(Argument {
             short,
@@ -90,24 +89,24 @@ impl Argument {
     /// let value = args_list.search_by_short_name('v').unwrap().get_value().unwrap();
     /// println!("Value: {}", value);
     ///```
-
     pub fn get_value(&self) -> Result<&str, &'static str> {
         if let ArgType::Value = self.arg_type {
             if let Some(result) = &self.arg_result {
                 if let ArgResult::Value(ref value) = result {
-                    return Ok(value);
+                    Ok(value)
                 } else {
-                    return Err("Wrong type of result. Something really bad has happened");
+                    Err("Wrong type of result. Something really bad has happened")
                 }
             } else {
-                return Err("No value assigned to result");
+                Err("No value assigned to result")
             }
         } else {
-            return Err("This argument is not an value");
+            Err("This argument is not an value")
         }
     }
     ///
-    /// Method allowing to simplify reading values of a value list type argument.
+    /// Method allowing to simplify reading values of a value list type argument. Values are
+    /// returned in order they were given.
     ///
     ///# Examples
     ///```
@@ -121,20 +120,19 @@ impl Argument {
     ///     println!("Value: {}", e);
     /// }
     ///```
-
     pub fn get_values(&self) -> Result<&Vec<String>, &'static str> {
         if let ArgType::ValueList = self.arg_type {
             if let Some(result) = &self.arg_result {
                 if let ArgResult::ValueList(ref list) = result {
-                    return Ok(list);
+                    Ok(list)
                 } else {
-                    return Err("Wrong type of result. Something really bad happened");
+                    Err("Wrong type of result. Something really bad happened")
                 }
             } else {
-                return Err("No result specified");
+                Err("No result specified")
             }
         } else {
-            return Err("This argument is not an value list");
+            Err("This argument is not an value list")
         }
     }
 
@@ -152,37 +150,48 @@ impl Argument {
     ///     println!("Flag was set");
     /// }
     ///```
-
     pub fn get_flag(&self) -> Result<bool, &'static str> {
         if let ArgType::Flag = self.arg_type {
-            return Ok(if let Some(_) = self.arg_result {
-                true
-            } else {
-                false
-            });
+            Ok(self.arg_result.is_some())
         } else {
-            return Err("Argument is not an flag type");
+            Err("Argument is not an flag type")
+        }
+    }
+
+    /// Marks flag as given. Flags can be given only once.
+    pub(crate) fn set_flag(&mut self) -> Result<(), String> {
+        if self.arg_result.is_some() {
+            return Err(String::from("Flag already set"));
         }
+        self.arg_result = Some(ArgResult::Flag);
+        Ok(())
     }
 
     pub fn add_value(
         &mut self,
         input_iter: &mut Peekable<&mut std::slice::Iter<'_, String>>,
     ) -> Result<(), String> {
+        self.add_next_value(|| input_iter.next().map(String::as_str))
+    }
+
+    /// Same as add_value, but reads value from InputCursor used by the parser, so values read
+    /// by a failed call can be rolled back.
+    pub fn add_value_from(&mut self, input: &mut InputCursor<'_>) -> Result<(), String> {
+        self.add_next_value(|| input.next().map(String::as_str))
+    }
+
+    /// Stores value returned by `next`, which is called only by arguments taking a value.
+    fn add_next_value<'v, F>(&mut self, next: F) -> Result<(), String>
+    where
+        F: FnOnce() -> Option<&'v str>,
+    {
         match self.arg_type {
-            ArgType::Flag => {
-                match self.arg_result {
-                    Some(_) => return Err(String::from("Flag already set")),
-                    _ => (),
-                }
-                self.arg_result = Some(ArgResult::Flag);
-            }
+            ArgType::Flag => self.set_flag()?,
             ArgType::Value => {
-                match self.arg_result {
-                    Some(_) => return Err(String::from("Value already assigned")),
-                    _ => (),
+                if self.arg_result.is_some() {
+                    return Err(String::from("Value already assigned"));
                 }
-                match input_iter.next() {
+                match next() {
                     Some(word) => self.arg_result = Some(ArgResult::Value(String::from(word))),
                     None => return Err(String::from("Expected value")),
                 }
@@ -198,7 +207,7 @@ impl Argument {
                     self.arg_result = Some(ArgResult::ValueList(Vec::new()));
                 }
 
-                match input_iter.next() {
+                match next() {
                     Some(word) => match self.arg_result.as_mut().expect("as mut") {
                         ArgResult::ValueList(ref mut values) => values.push(String::from(word)),
                         _ => return Err(String::from("WTF")),
@@ -222,13 +231,43 @@ impl Argument {
     pub fn arg_type(&self) -> &ArgType {
         &self.arg_type
     }
+
+    /// Returns names of this argument as identification used by parsable arguments.
+    pub fn identification(&self) -> ArgumentIdentification {
+        match (self.short, &self.long) {
+            (Some(c), Some(s)) => ArgumentIdentification::Both(c, s.clone()),
+            (None, Some(s)) => ArgumentIdentification::Long(s.clone()),
+            (Some(c), None) => ArgumentIdentification::Short(c),
+            (None, None) => unreachable!("argument always has at least one name"),
+        }
+    }
+}
+
+/// Formats names of argument and its result, e.g. `-p, --path = "/file" (from command line)` or
+/// `-v (not given)`.
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.identification())?;
+        match &self.arg_result {
+            None => return write!(f, " (not given)"),
+            Some(ArgResult::Flag) => (),
+            Some(ArgResult::Value(value)) => write_values(f, std::slice::from_ref(value))?,
+            Some(ArgResult::ValueList(values)) => write_values(f, values)?,
+        }
+        write!(f, " (from {})", Source::Cli)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::borrow::BorrowMut;
 
-    use crate::argument::legacy_argument::{ArgType, Argument};
+    use crate::cursor::InputCursor;
+
+    use crate::argument::{
+        legacy_argument::{ArgType, Argument},
+        ArgumentIdentification,
+    };
 
     #[test]
     fn new_works() {
@@ -243,6 +282,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn value_works() {
         let mut arg =
             Argument::new(Option::None, Option::Some("parameter"), ArgType::Value).unwrap();
@@ -259,6 +299,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn value_fails_too_many_calls() {
         let mut arg =
             Argument::new(Option::None, Option::Some("parameter"), ArgType::Value).unwrap();
@@ -270,6 +311,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::get_first, clippy::useless_vec)]
     fn value_list_works() {
         let mut arg =
             Argument::new(Option::None, Option::Some("parameter"), ArgType::ValueList).unwrap();
@@ -286,6 +328,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::useless_vec)]
     fn flag_works() {
         let mut arg =
             Argument::new(Option::None, Option::Some("parameter"), ArgType::Flag).unwrap();
@@ -300,4 +343,35 @@ mod test {
         assert!(val.is_ok());
         assert_eq!(val.unwrap(), true);
     }
+
+    #[test]
+    fn identification_works() {
+        assert_eq!(
+            Argument::new_short('x', ArgType::Flag).identification(),
+            ArgumentIdentification::Short('x')
+        );
+        assert_eq!(
+            Argument::new(Some('x'), Some("ex"), ArgType::Flag)
+                .unwrap()
+                .identification(),
+            ArgumentIdentification::Both('x', String::from("ex"))
+        );
+    }
+
+    #[test]
+    fn display_works() {
+        let mut arg = Argument::new(Some('I'), Some("include"), ArgType::ValueList).unwrap();
+        assert_eq!(format!("{}", arg), "-I, --include (not given)");
+        let inputs = [String::from("a"), String::from("b c")];
+        let mut inputs = InputCursor::new(&inputs);
+        arg.add_value_from(&mut inputs).unwrap();
+        arg.add_value_from(&mut inputs).unwrap();
+        assert_eq!(
+            format!("{}", arg),
+            "-I, --include = \"a\", \"b c\" (from command line)"
+        );
+        let mut flag = Argument::new_short('v', ArgType::Flag);
+        flag.add_value_from(&mut InputCursor::new(&[])).unwrap();
+        assert_eq!(format!("{}", flag), "-v (from command line)");
+    }
 }
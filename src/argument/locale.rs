@@ -0,0 +1,93 @@
+use super::{parsable_argument::ParsableValueArgument, ArgumentIdentification};
+use crate::cursor::InputCursor;
+
+/// Validates language tag (`language[-Script][-REGION][-variant...]`) and returns it with
+/// canonical case of every subtag. `_` is accepted as separator, like in POSIX locale names.
+fn normalize_locale(tag: &str) -> Result<String, String> {
+    let invalid = || format!("'{}' is not a valid language tag", tag);
+    let mut subtags = tag.split(['-', '_']);
+    let language = subtags.next().unwrap_or("");
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Result::Err(invalid());
+    }
+    let mut normalized = vec![language.to_ascii_lowercase()];
+    // Subtags must appear in order: script, region, variants.
+    let mut stage = 0;
+    for subtag in subtags {
+        let alphabetic = subtag.chars().all(|c| c.is_ascii_alphabetic());
+        let numeric = subtag.chars().all(|c| c.is_ascii_digit());
+        if stage < 1 && subtag.len() == 4 && alphabetic {
+            let (first, rest) = subtag.split_at(1);
+            normalized.push(first.to_ascii_uppercase() + &rest.to_ascii_lowercase());
+            stage = 1;
+        } else if stage < 2 && ((subtag.len() == 2 && alphabetic) || (subtag.len() == 3 && numeric))
+        {
+            normalized.push(subtag.to_ascii_uppercase());
+            stage = 2;
+        } else if subtag.chars().all(|c| c.is_ascii_alphanumeric())
+            && ((5..=8).contains(&subtag.len())
+                || (subtag.len() == 4 && subtag.starts_with(|c: char| c.is_ascii_digit())))
+        {
+            normalized.push(subtag.to_ascii_lowercase());
+            stage = 3;
+        } else {
+            return Result::Err(invalid());
+        }
+    }
+    Result::Ok(normalized.join("-"))
+}
+
+impl ParsableValueArgument<String> {
+    /// Argument accepting language tag like `pl` or `en-US`. Values are normalized to canonical
+    /// case (`EN_us` becomes `en-US`).
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_lang =
+    ///     ParsableValueArgument::new_locale(ArgumentIdentification::Long(String::from("lang")));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_lang);
+    /// args_list
+    ///     .parse_args(vec![String::from("--lang"), String::from("zh_hant_tw")])
+    ///     .unwrap();
+    /// assert_eq!(argument_lang.first_value().unwrap(), "zh-Hant-TW");
+    /// ```
+    pub fn new_locale(identification: ArgumentIdentification) -> ParsableValueArgument<String> {
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<String>| {
+            values.push(input_iter.next_decoded(normalize_locale)?);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::normalize_locale;
+
+    #[test]
+    fn normalize_locale_works() {
+        assert_eq!(normalize_locale("PL"), Ok(String::from("pl")));
+        assert_eq!(normalize_locale("en-us"), Ok(String::from("en-US")));
+        assert_eq!(normalize_locale("es-419"), Ok(String::from("es-419")));
+        assert_eq!(
+            normalize_locale("sl-ROZAJ-biske"),
+            Ok(String::from("sl-rozaj-biske"))
+        );
+        assert_eq!(
+            normalize_locale("de-CH-1996"),
+            Ok(String::from("de-CH-1996"))
+        );
+        for invalid in ["", "e", "english", "en-", "en-US-Latn", "en-U$"].iter() {
+            assert_eq!(
+                normalize_locale(invalid),
+                Err(format!("'{}' is not a valid language tag", invalid))
+            );
+        }
+    }
+}
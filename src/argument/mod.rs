@@ -1,11 +1,27 @@
+use std::fmt;
+
+#[cfg(feature = "async")]
+pub mod async_argument;
+pub mod binary;
 pub mod builder;
+pub mod cache;
+pub mod color;
+pub mod cron;
+pub mod filter;
 /// Legacy API argument module. Should not be use since parsable_argument is now the preffered way of defining arguments.
 /// Most likely will be removed in future.
 pub mod legacy_argument;
+pub mod locale;
+pub mod output_format;
 pub mod parsable_argument;
+pub mod spec;
+pub mod table;
+pub mod value;
+#[cfg(feature = "semver")]
+pub mod version;
 
 /// Defines how arguments can be identified.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ArgumentIdentification {
     Short(char),
     Long(String),
@@ -36,6 +52,77 @@ impl ArgumentIdentification {
     }
 }
 
+impl ArgumentIdentification {
+    /// Short name of this identification if it has one.
+    pub fn short(&self) -> Option<char> {
+        match self {
+            ArgumentIdentification::Short(c) | ArgumentIdentification::Both(c, _) => Some(*c),
+            ArgumentIdentification::Long(_) => None,
+        }
+    }
+
+    /// Long name of this identification if it has one.
+    pub fn long(&self) -> Option<&str> {
+        match self {
+            ArgumentIdentification::Long(s) | ArgumentIdentification::Both(_, s) => Some(s),
+            ArgumentIdentification::Short(_) => None,
+        }
+    }
+
+    /// Check if this identification can be identified by name typed by the user (`-p`, `--path`)
+    /// or given without prefix (`p`, `path`). Name without prefix matches long name or, when
+    /// it is a single character, short name.
+    pub fn is_named(&self, name: &str) -> bool {
+        if let Some(long) = name.strip_prefix("--") {
+            return self.is_by_long(long);
+        }
+        let short_only = name.starts_with('-');
+        let name = strip_name_prefix(name);
+        let mut chars = name.chars();
+        let by_short = match (chars.next(), chars.next()) {
+            (Some(c), None) => self.is_by_short(c),
+            _ => false,
+        };
+        by_short || (!short_only && self.is_by_long(name))
+    }
+
+    /// Check if both identifications share at least one name.
+    pub fn overlaps(&self, other: &ArgumentIdentification) -> bool {
+        other.short().is_some_and(|c| self.is_by_short(c))
+            || other.long().is_some_and(|s| self.is_by_long(s))
+    }
+}
+
+/// Removes `--` or `-` prefix from name typed by the user, so lookups accept both forms.
+pub(crate) fn strip_name_prefix(name: &str) -> &str {
+    name.strip_prefix("--")
+        .or_else(|| name.strip_prefix('-'))
+        .unwrap_or(name)
+}
+
+impl From<char> for ArgumentIdentification {
+    fn from(name: char) -> Self {
+        ArgumentIdentification::Short(name)
+    }
+}
+
+impl From<&str> for ArgumentIdentification {
+    fn from(name: &str) -> Self {
+        ArgumentIdentification::Long(String::from(name))
+    }
+}
+
+impl fmt::Display for ArgumentIdentification {
+    /// Formats identification the way it would be typed by the user (e.g. `-p, --path`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgumentIdentification::Short(c) => write!(f, "-{}", c),
+            ArgumentIdentification::Long(s) => write!(f, "--{}", s),
+            ArgumentIdentification::Both(c, s) => write!(f, "-{}, --{}", c, s),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::ArgumentIdentification;
@@ -59,4 +146,43 @@ mod test {
         assert!(both_id.is_by_long("file"));
         assert!(!both_id.is_by_long("bar"));
     }
+
+    #[test]
+    fn is_named_works() {
+        let both_id = ArgumentIdentification::Both('p', String::from("path"));
+        for name in ["p", "-p", "path", "--path"] {
+            assert!(both_id.is_named(name), "{}", name);
+        }
+        for name in ["--p", "-path", "pat", ""] {
+            assert!(!both_id.is_named(name), "{}", name);
+        }
+        assert!(ArgumentIdentification::from("p").is_named("--p"));
+        assert!(!ArgumentIdentification::from("p").is_named("-p"));
+    }
+
+    #[test]
+    fn overlaps_works() {
+        let both_id = ArgumentIdentification::Both('p', String::from("path"));
+        assert!(both_id.overlaps(&ArgumentIdentification::from('p')));
+        assert!(both_id.overlaps(&ArgumentIdentification::from("path")));
+        assert!(!both_id.overlaps(&ArgumentIdentification::from("p")));
+        assert!(ArgumentIdentification::from('p').overlaps(&both_id));
+        assert!(!ArgumentIdentification::from('x').overlaps(&both_id));
+    }
+
+    #[test]
+    fn display_works() {
+        assert_eq!(format!("{}", ArgumentIdentification::Short('x')), "-x");
+        assert_eq!(
+            format!("{}", ArgumentIdentification::Long(String::from("path"))),
+            "--path"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                ArgumentIdentification::Both('p', String::from("path"))
+            ),
+            "-p, --path"
+        );
+    }
 }
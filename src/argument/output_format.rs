@@ -0,0 +1,239 @@
+use super::{
+    legacy_argument::ArgType,
+    parsable_argument::{HandleableArgument, ParsableValueArgument},
+    ArgumentIdentification,
+};
+use crate::{cursor::InputCursor, suggest};
+use std::{fmt, str::FromStr};
+
+/// Structured output formats which can be selected by the user.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// All supported formats in order they are advertised in help text.
+    pub const ALL: [OutputFormat; 3] = [OutputFormat::Text, OutputFormat::Json, OutputFormat::Yaml];
+
+    /// Name of the format as typed by the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Yaml => "yaml",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for format in OutputFormat::ALL.iter() {
+            if format.name() == s {
+                return Result::Ok(*format);
+            }
+        }
+        let names = OutputFormat::ALL.iter().map(|f| f.name());
+        match suggest::closest(s, names) {
+            Some(suggestion) => Result::Err(format!(
+                "Unsupported output format '{}'. Did you mean '{}'?",
+                s, suggestion
+            )),
+            None => Result::Err(format!(
+                "Unsupported output format '{}'. Supported formats: {}.",
+                s,
+                supported_formats_list()
+            )),
+        }
+    }
+}
+
+fn supported_formats_list() -> String {
+    OutputFormat::ALL
+        .iter()
+        .map(|f| f.name())
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+///
+/// Reusable argument selecting structured output format (`--output-format {text,json,yaml}`).
+/// Should be registered in ArgumentList like any other parsable argument. Description listing
+/// all supported formats is generated automatically.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::output_format::{OutputFormat, OutputFormatArg},
+/// };
+///
+/// let mut output_format = OutputFormatArg::new();
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_parsable(&mut output_format);
+/// args_list
+///     .parse_args(vec![String::from("--output-format"), String::from("json")])
+///     .unwrap();
+/// assert_eq!(output_format.format(), OutputFormat::Json);
+/// ```
+pub struct OutputFormatArg {
+    argument: ParsableValueArgument<OutputFormat>,
+    default: OutputFormat,
+    description: String,
+}
+
+impl OutputFormatArg {
+    /// Creates argument identified by `--output-format` with text as the default format.
+    pub fn new() -> OutputFormatArg {
+        OutputFormatArg::with_identification(ArgumentIdentification::Long(String::from(
+            "output-format",
+        )))
+    }
+
+    /// Creates argument with custom identification.
+    pub fn with_identification(identification: ArgumentIdentification) -> OutputFormatArg {
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<OutputFormat>| {
+            values.push(input_iter.next_decoded(|v| v.parse())?);
+            Result::Ok(())
+        };
+        let default = OutputFormat::Text;
+        OutputFormatArg {
+            argument: ParsableValueArgument::new(identification, handler),
+            default,
+            description: OutputFormatArg::generate_description(default),
+        }
+    }
+
+    fn generate_description(default: OutputFormat) -> String {
+        format!(
+            "Output format, one of: {} (default: {})",
+            supported_formats_list(),
+            default
+        )
+    }
+
+    /// Changes format returned when user did not specify one.
+    pub fn set_default(mut self, default: OutputFormat) -> OutputFormatArg {
+        self.default = default;
+        self.description = OutputFormatArg::generate_description(default);
+        self
+    }
+
+    /// Returns format chosen by the user. When specified multiple times, the last one is used.
+    pub fn format(&self) -> OutputFormat {
+        *self.argument.values().last().unwrap_or(&self.default)
+    }
+
+    /// Check if user explicitly chose output format.
+    pub fn is_specified(&self) -> bool {
+        !self.argument.values().is_empty()
+    }
+
+    /// Returns all formats accepted by this argument.
+    pub fn supported_formats(&self) -> &'static [OutputFormat] {
+        &OutputFormat::ALL
+    }
+}
+
+impl Default for OutputFormatArg {
+    fn default() -> Self {
+        OutputFormatArg::new()
+    }
+}
+
+impl<'a> HandleableArgument<'a> for OutputFormatArg {
+    fn handle(&mut self, input_iter: &mut InputCursor<'_>) -> Result<(), String> {
+        self.argument.handle(input_iter)
+    }
+
+    fn is_by_short(&self, name: char) -> bool {
+        self.argument.is_by_short(name)
+    }
+
+    fn is_by_long(&self, name: &str) -> bool {
+        self.argument.is_by_long(name)
+    }
+
+    fn identification(&self) -> &ArgumentIdentification {
+        self.argument.identification()
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(&self.description)
+    }
+
+    fn arg_type(&self) -> ArgType {
+        ArgType::Value
+    }
+
+    fn default_value(&self) -> Option<&str> {
+        Some(self.default.name())
+    }
+
+    fn choices(&self) -> Vec<String> {
+        OutputFormat::ALL
+            .iter()
+            .map(|f| String::from(f.name()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cursor::InputCursor;
+
+    use super::{OutputFormat, OutputFormatArg};
+    use crate::argument::{parsable_argument::HandleableArgument, ArgumentIdentification};
+
+    #[test]
+    fn from_str_works() {
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!("yaml".parse::<OutputFormat>().unwrap(), OutputFormat::Yaml);
+        assert_eq!(
+            "xml".parse::<OutputFormat>().unwrap_err(),
+            "Unsupported output format 'xml'. Supported formats: text, json, yaml."
+        );
+        assert_eq!(
+            "jsn".parse::<OutputFormat>().unwrap_err(),
+            "Unsupported output format 'jsn'. Did you mean 'json'?"
+        );
+    }
+
+    #[test]
+    fn format_works() {
+        let mut arg = OutputFormatArg::new();
+        assert_eq!(arg.format(), OutputFormat::Text);
+        assert!(!arg.is_specified());
+        arg.handle(&mut InputCursor::new(&[String::from("yaml")]))
+            .unwrap();
+        assert_eq!(arg.format(), OutputFormat::Yaml);
+        arg.handle(&mut InputCursor::new(&[String::from("json")]))
+            .unwrap();
+        assert_eq!(arg.format(), OutputFormat::Json);
+        assert!(arg.is_specified());
+    }
+
+    #[test]
+    fn description_lists_formats() {
+        let arg = OutputFormatArg::with_identification(ArgumentIdentification::Short('o'))
+            .set_default(OutputFormat::Json);
+        assert_eq!(
+            arg.description().unwrap(),
+            "Output format, one of: text, json, yaml (default: json)"
+        );
+        assert_eq!(arg.supported_formats().len(), 3);
+        assert_eq!(arg.choices(), vec!["text", "json", "yaml"]);
+        assert_eq!(arg.default_value(), Some("json"));
+    }
+}
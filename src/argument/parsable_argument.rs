@@ -1,5 +1,42 @@
-use super::ArgumentIdentification;
-use std::iter::Peekable;
+use super::{
+    cache::ValueCache,
+    legacy_argument::ArgType,
+    value::{ArgValue, ValueError},
+    ArgumentIdentification,
+};
+use crate::{cursor::InputCursor, suggest};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    fmt::Display,
+    io::{self, Read},
+    path::PathBuf,
+    rc::Rc,
+    str::FromStr,
+};
+
+/// Signature of closures used to handle parsable argument values.
+type ArgumentHandler<V> = dyn Fn(&mut InputCursor<'_>, &mut Vec<V>) -> Result<(), String>;
+
+/// Boxed future returned by async parts of argument handling.
+#[cfg(feature = "async")]
+pub type BoxFuture<'f, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + 'f>>;
+
+/// Marker requesting list items from outside of the command line and function reading them.
+type ExternalItems = (String, fn() -> io::Result<String>);
+
+/// Signature of closures invoked once on all collected values after parsing is done.
+type ArgumentFinalizer<V> = dyn Fn(&mut Vec<V>) -> Result<(), String>;
+
+/// Transforms copies of values with function used by ParsableValueArgument::and_then.
+fn map_values<V, U, F>(f: &F, values: &[V]) -> Result<Vec<U>, String>
+where
+    V: Clone,
+    F: Fn(V) -> Result<U, String>,
+{
+    values.iter().cloned().map(f).collect()
+}
+
 /**
  * Structure which defines how given argument should be handled. Allows for automatic parsing and validation.
  * Mutable borrow to parsable argument definition has to be registered in ArgumentList. Because of that
@@ -9,114 +46,1598 @@ use std::iter::Peekable;
  */
 pub struct ParsableValueArgument<V> {
     identification: ArgumentIdentification,
-    handler: Box<
-        dyn Fn(&mut Peekable<&mut std::slice::Iter<'_, String>>, &mut Vec<V>) -> Result<(), String>,
-    >,
+    handler: Box<ArgumentHandler<V>>,
     values: Vec<V>,
+    /// Range of values appended by every successful occurrence which appended any.
+    occurrence_ranges: Vec<(usize, usize)>,
+    description: Option<String>,
+    localized_descriptions: Vec<(String, String)>,
+    finalizer: Option<Box<ArgumentFinalizer<V>>>,
+    /// Finalizers set before map or and_then, run on values of the original type before
+    /// finalizer. Values are mapped again afterwards.
+    source_finalizer: Option<Box<ArgumentFinalizer<V>>>,
+    required: bool,
+    secret: bool,
+    default: Option<(V, String)>,
+    choices: Vec<String>,
+    arg_type: ArgType,
+    examples: Vec<String>,
+    /// Names of values displayed in usage, one for every value of an occurrence.
+    value_names: Vec<String>,
+    optional_value: bool,
+    tags: Vec<String>,
+    /// Warnings recorded by the handler, shared with it.
+    warnings: Rc<RefCell<Vec<String>>>,
+    /// Whether handler of unsigned integer argument saturates values too large for the type,
+    /// shared with it. None for other handlers.
+    saturate: Option<Rc<Cell<bool>>>,
 }
 
 /// Unifies how parsable arguments are parsed.
 pub trait HandleableArgument<'a> {
-    /// Handles argument. Gets all needed values from input iterator.
-    fn handle(
-        &mut self,
-        input_iter: &mut Peekable<&mut std::slice::Iter<'_, String>>,
-    ) -> Result<(), String>;
+    /// Handles argument. Gets all needed values from input cursor. When Err is returned, values
+    /// consumed from the cursor are given back to the parser, which handles them as if this
+    /// argument was not given (e.g. as dangling values), so handlers do not need to roll it back.
+    fn handle(&mut self, input_iter: &mut InputCursor<'_>) -> Result<(), String>;
     /// Check if this argument is identified by specified short name.
     fn is_by_short(&self, name: char) -> bool;
     /// Check if this argument is identified by specified long name.
     fn is_by_long(&self, name: &str) -> bool;
     /// Get this arguments identification.
     fn identification(&self) -> &ArgumentIdentification;
+    /// Description of this argument displayed in help text.
+    fn description(&self) -> Option<&str> {
+        None
+    }
+    /// Descriptions in other languages as pairs of language tag and description, used instead of
+    /// description when language is selected with ArgumentList::set_language.
+    fn localized_descriptions(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+    /// Called once after all input was consumed. Allows validating all occurrences together.
+    fn finalize(&mut self) -> Result<(), String> {
+        Result::Ok(())
+    }
+    /// Describes how many values argument takes. Parsable arguments collect values of all
+    /// occurrences by default.
+    fn arg_type(&self) -> ArgType {
+        ArgType::ValueList
+    }
+    /// Check if argument has to be present in user input.
+    fn is_required(&self) -> bool {
+        false
+    }
+    /// Check if value of argument can be resolved by ArgumentList::add_value_provider when it is
+    /// not given in command line.
+    fn is_secret(&self) -> bool {
+        false
+    }
+    /// Default value formatted for display.
+    fn default_value(&self) -> Option<&str> {
+        None
+    }
+    /// All values accepted by this argument, empty when any value is accepted.
+    fn choices(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Example command lines using this argument, listed in help and manual page.
+    fn examples(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Name of value displayed in help and usage (e.g. `PATH` in `--config <PATH>`). `VALUE` is
+    /// displayed when None.
+    fn value_name(&self) -> Option<&str> {
+        None
+    }
+    /// Names of every value of an occurrence, for arguments reading more than one value (e.g.
+    /// `<X> <Y>`). Contains only value_name by default.
+    fn value_names(&self) -> Vec<&str> {
+        self.value_name().into_iter().collect()
+    }
+    /// Tags of this argument (e.g. `experimental`), used to hide it with
+    /// ArgumentList::set_hidden_tags.
+    fn tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Check if argument can be given without a value.
+    fn has_optional_value(&self) -> bool {
+        false
+    }
+    /// Returns warnings recorded while handling values since the last call, e.g. about
+    /// saturated values.
+    fn take_warnings(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Returns future completing handling of values collected while parsing. Awaited by
+    /// ArgumentList::parse_args_async after synchronous parsing is done.
+    #[cfg(feature = "async")]
+    fn resolve_async(&mut self) -> Option<BoxFuture<'_, Result<(), String>>> {
+        None
+    }
 }
 
+/// Parsable argument which can be owned by ArgumentList (see register_parsable_boxed) and
+/// downcast back to its concrete type. Implemented for every suitable argument type.
+pub trait DynArgument: Any + for<'x> HandleableArgument<'x> {}
+
+impl<T: Any + for<'x> HandleableArgument<'x>> DynArgument for T {}
+
 impl<V> ParsableValueArgument<V> {
     pub fn new<C>(identification: ArgumentIdentification, handler: C) -> ParsableValueArgument<V>
     where
-        C: Fn(&mut Peekable<&mut std::slice::Iter<'_, String>>, &mut Vec<V>) -> Result<(), String>
-            + 'static,
+        C: Fn(&mut InputCursor<'_>, &mut Vec<V>) -> Result<(), String> + 'static,
     {
         ParsableValueArgument::<V> {
             identification,
             handler: Box::new(handler),
             values: Vec::new(),
+            occurrence_ranges: Vec::new(),
+            description: None,
+            localized_descriptions: Vec::new(),
+            finalizer: None,
+            source_finalizer: None,
+            required: false,
+            secret: false,
+            default: None,
+            choices: Vec::new(),
+            arg_type: ArgType::ValueList,
+            examples: Vec::new(),
+            value_names: Vec::new(),
+            optional_value: false,
+            tags: Vec::new(),
+            warnings: Rc::default(),
+            saturate: None,
+        }
+    }
+
+    /// Sets description displayed next to argument in help text.
+    pub fn set_description(mut self, description: &str) -> ParsableValueArgument<V> {
+        self.description = Some(String::from(description));
+        self
+    }
+
+    /// Adds description in given language (e.g. `pl` or `pt-BR`), displayed in help and manual
+    /// page when the language is selected with ArgumentList::set_language.
+    pub fn add_localized_description(
+        mut self,
+        language: &str,
+        description: &str,
+    ) -> ParsableValueArgument<V> {
+        self.localized_descriptions
+            .push((String::from(language), String::from(description)));
+        self
+    }
+
+    /// Adds example of using the argument (e.g. `-p ./config.toml`) listed in Examples section of
+    /// help and manual page.
+    pub fn add_example(mut self, example: &str) -> ParsableValueArgument<V> {
+        self.examples.push(String::from(example));
+        self
+    }
+
+    /// Adds tag (e.g. `experimental`), so the argument can be hidden with
+    /// ArgumentList::set_hidden_tags.
+    pub fn add_tag(mut self, tag: &str) -> ParsableValueArgument<V> {
+        self.tags.push(String::from(tag));
+        self
+    }
+
+    /// Sets name of value displayed in help and usage, e.g. `FILE` in `--config <FILE>`.
+    /// Constructors of typed arguments set it to the expected format (`INT`, `PATH` or `CHAR`).
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable_boxed(Box::new(ParsableValueArgument::new_integer(
+    ///     ArgumentIdentification::Short('n'),
+    /// )));
+    /// args_list.register_parsable_boxed(Box::new(
+    ///     ParsableValueArgument::new_path(ArgumentIdentification::Short('c')).set_value_name("FILE"),
+    /// ));
+    /// let usages: Vec<String> = args_list.iter_specs().map(|spec| spec.usage()).collect();
+    /// assert_eq!(usages, vec!["-n <INT>", "-c <FILE>"]);
+    /// ```
+    pub fn set_value_name(mut self, value_name: &str) -> ParsableValueArgument<V> {
+        self.value_names = vec![String::from(value_name)];
+        self
+    }
+
+    /// Sets names of values given one after another, displayed as separate placeholders (e.g.
+    /// `--point <X> <Y>`).
+    pub fn set_value_names(mut self, value_names: &[&str]) -> ParsableValueArgument<V> {
+        self.value_names = value_names.iter().map(|name| String::from(*name)).collect();
+        self
+    }
+
+    /// Sets type of argument described in specs, e.g. for arguments defined in static tables.
+    pub(crate) fn with_arg_type(mut self, arg_type: ArgType) -> ParsableValueArgument<V> {
+        self.arg_type = arg_type;
+        self
+    }
+
+    /// Marks argument as required. Parsing fails when required argument is not present in input.
+    pub fn set_required(mut self, required: bool) -> ParsableValueArgument<V> {
+        self.required = required;
+        self
+    }
+
+    /// Marks argument as secret (e.g. token or password), so its value is resolved by value
+    /// providers added with ArgumentList::add_value_provider when not given in command line.
+    pub fn set_secret(mut self, secret: bool) -> ParsableValueArgument<V> {
+        self.secret = secret;
+        self
+    }
+
+    /// Sets value returned by first_value when argument was not present in input.
+    pub fn set_default(mut self, default: V) -> ParsableValueArgument<V>
+    where
+        V: Display,
+    {
+        let display = format!("{}", default);
+        self.default = Some((default, display));
+        self
+    }
+
+    /// Sets hook invoked once after parsing with all collected values. Can be used to validate
+    /// or adjust values across occurrences (e.g. check if they are unique).
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_int = ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+    ///     .set_finalizer(|values| {
+    ///         values.sort();
+    ///         Result::Ok(())
+    ///     });
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_int);
+    /// args_list
+    ///     .parse_args(vec![String::from("-n"), String::from("3"), String::from("-n"), String::from("1")])
+    ///     .unwrap();
+    /// assert_eq!(argument_int.values(), &vec![1, 3]);
+    /// ```
+    pub fn set_finalizer<F>(mut self, finalizer: F) -> ParsableValueArgument<V>
+    where
+        F: Fn(&mut Vec<V>) -> Result<(), String> + 'static,
+    {
+        self.finalizer = Some(Box::new(finalizer));
+        self
+    }
+
+    /// Allows argument to hold at most one value, like legacy arguments of type Value. Values of
+    /// repeated occurrences are handled as selected by `policy`. Should be used with handlers
+    /// which add a single value to values and do not depend on values added before.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{
+    ///         parsable_argument::{DuplicatePolicy, ParsableValueArgument},
+    ///         ArgumentIdentification,
+    ///     },
+    /// };
+    ///
+    /// let mut argument_output = ParsableValueArgument::new_string(ArgumentIdentification::Short('o'))
+    ///     .single(DuplicatePolicy::Last);
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_output);
+    /// let input = vec!["-o", "a.txt", "-o", "b.txt"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// drop(args_list);
+    /// assert_eq!(argument_output.values(), &vec![String::from("b.txt")]);
+    /// ```
+    pub fn single(mut self, policy: DuplicatePolicy) -> ParsableValueArgument<V>
+    where
+        V: 'static,
+    {
+        let handler = self.handler;
+        self.handler = Box::new(move |input_iter, values| {
+            if values.is_empty() {
+                return handler(input_iter, values);
+            }
+            let mut repeated = Vec::new();
+            match policy {
+                DuplicatePolicy::Error => Result::Err(String::from("Value already assigned")),
+                DuplicatePolicy::First => handler(input_iter, &mut repeated),
+                DuplicatePolicy::Last => {
+                    handler(input_iter, &mut repeated)?;
+                    *values = repeated;
+                    Result::Ok(())
+                }
+            }
+        });
+        self.arg_type = ArgType::Value;
+        self
+    }
+
+    /// Makes value of the argument optional. When input ends or the next value starts with `-`
+    /// (e.g. `--color --verbose`), nothing is consumed and `bare` is added instead. Lone `-` is
+    /// consumed as a value. Values starting with `-` (e.g. negative numbers) can be given only
+    /// after `=` (`--jobs=-1`), which is always consumed.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_color = ParsableValueArgument::new_choice(
+    ///     ArgumentIdentification::Long(String::from("color")),
+    ///     &["auto", "always", "never"],
+    /// )
+    /// .optional_value(String::from("always"));
+    /// let mut argument_verbose =
+    ///     ParsableValueArgument::new_flag(ArgumentIdentification::Long(String::from("verbose")));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_color);
+    /// args_list.register_parsable(&mut argument_verbose);
+    /// args_list
+    ///     .parse_args(vec![String::from("--color"), String::from("--verbose")])
+    ///     .unwrap();
+    /// assert_eq!(args_list.help_message(), "Options:\n  --color [<auto|always|never>]\n  --verbose\n");
+    /// drop(args_list);
+    /// assert_eq!(argument_color.first_value().map(String::as_str), Some("always"));
+    /// assert!(argument_verbose.is_set());
+    /// ```
+    pub fn optional_value(mut self, bare: V) -> ParsableValueArgument<V>
+    where
+        V: Clone + 'static,
+    {
+        let handler = self.handler;
+        self.handler = Box::new(move |input_iter, values| {
+            if input_iter.is_inline() {
+                return handler(input_iter, values);
+            }
+            match input_iter.peek() {
+                Some(v) if v == "-" || !v.starts_with('-') => handler(input_iter, values),
+                _ => {
+                    values.push(bare.clone());
+                    Result::Ok(())
+                }
+            }
+        });
+        self.optional_value = true;
+        self
+    }
+
+    /// Transforms every value produced by the handler, and the default value set before. Values
+    /// of the original type are kept, so handlers reading or replacing values collected before
+    /// (e.g. single, new_fold, new_integer_sum) and finalizers set before keep working, and all
+    /// values are transformed again after every occurrence. Should be used while defining the
+    /// argument, values collected before are discarded.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     argument::{
+    ///         parsable_argument::{HandleableArgument, ParsableValueArgument},
+    ///         ArgumentIdentification,
+    ///     },
+    ///     cursor::InputCursor,
+    /// };
+    ///
+    /// let mut arg = ParsableValueArgument::new_string(ArgumentIdentification::Short('n'))
+    ///     .map(|v| v.trim().to_lowercase());
+    /// arg.handle(&mut InputCursor::new(&[String::from(" Hello ")]))
+    ///     .unwrap();
+    /// assert_eq!(arg.first_value().unwrap(), "hello");
+    /// ```
+    pub fn map<U, F>(self, f: F) -> ParsableValueArgument<U>
+    where
+        V: Clone + 'static,
+        U: 'static,
+        F: Fn(V) -> U + 'static,
+    {
+        self.and_then(move |v| Result::Ok(f(v)))
+    }
+
+    /// Transforms every value produced by the handler with function which can reject the value.
+    /// Error is returned from handling the argument, or from finalizing it when a finalizer set
+    /// before changed values. The default value set before is transformed too; when it is
+    /// rejected, it is discarded and the error is reported by take_warnings. Works like map
+    /// otherwise.
+    pub fn and_then<U, F>(self, f: F) -> ParsableValueArgument<U>
+    where
+        V: Clone + 'static,
+        U: 'static,
+        F: Fn(V) -> Result<U, String> + 'static,
+    {
+        let f = Rc::new(f);
+        let default = match self.default {
+            Some((default, display)) => match f(default) {
+                Result::Ok(default) => Some((default, display)),
+                Result::Err(message) => {
+                    self.warnings.borrow_mut().push(format!(
+                        "Default value {} was rejected: {}",
+                        display, message
+                    ));
+                    None
+                }
+            },
+            None => None,
+        };
+        // Values of the original type, which the inner handler and finalizers work on.
+        let original: Rc<RefCell<Vec<V>>> = Rc::default();
+        let inner = self.handler;
+        let handler_original = Rc::clone(&original);
+        let handler_f = Rc::clone(&f);
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<U>| {
+            let start = input_iter.consumed();
+            let mut next = handler_original.borrow().clone();
+            inner(input_iter, &mut next)?;
+            let mapped = map_values(&*handler_f, &next).inspect_err(|_| {
+                // Rejected value is known only when the occurrence consumed a single value.
+                if input_iter.consumed() == start + 1 {
+                    if let Some(value) = input_iter.previous() {
+                        input_iter.reject(value);
+                    }
+                }
+            })?;
+            *handler_original.borrow_mut() = next;
+            *values = mapped;
+            Result::Ok(())
+        };
+        let finalizers: Vec<Box<ArgumentFinalizer<V>>> = self
+            .source_finalizer
+            .into_iter()
+            .chain(self.finalizer)
+            .collect();
+        let source_finalizer: Option<Box<ArgumentFinalizer<U>>> = if finalizers.is_empty() {
+            None
+        } else {
+            Some(Box::new(move |values: &mut Vec<U>| {
+                let mut original = original.borrow_mut();
+                for finalizer in &finalizers {
+                    finalizer(&mut original)?;
+                }
+                *values = map_values(&*f, &original)?;
+                Result::Ok(())
+            }))
+        };
+        ParsableValueArgument::<U> {
+            identification: self.identification,
+            handler: Box::new(handler),
+            values: Vec::new(),
+            occurrence_ranges: Vec::new(),
+            description: self.description,
+            localized_descriptions: self.localized_descriptions,
+            finalizer: None,
+            source_finalizer,
+            required: self.required,
+            secret: self.secret,
+            default,
+            choices: self.choices,
+            arg_type: self.arg_type,
+            examples: self.examples,
+            value_names: self.value_names,
+            optional_value: self.optional_value,
+            tags: self.tags,
+            warnings: self.warnings,
+            saturate: None,
         }
     }
 
+    /// Returns first value given by the user or default value if argument was not used.
     pub fn first_value(&self) -> Option<&V> {
-        self.values().get(0)
+        self.values()
+            .first()
+            .or_else(|| self.default.as_ref().map(|(v, _)| v))
     }
 
+    /// Values handled by the argument, in order they were given.
     pub fn values(&self) -> &Vec<V> {
         &self.values
     }
+
+    /// Values handled by the argument grouped by occurrence, e.g. `[[1, 2], [3]]` for
+    /// `--point 1 2 --point 3` given to a list argument. Occurrences which did not append any
+    /// value (e.g. of sums, which replace the value) have no group and values of single value
+    /// arguments always form one group. Values changed by finalizers are not split reliably.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{
+    ///         parsable_argument::{ListOptions, ParsableValueArgument},
+    ///         ArgumentIdentification,
+    ///     },
+    /// };
+    ///
+    /// let mut argument_point = ParsableValueArgument::new_integer_list(
+    ///     ArgumentIdentification::Long(String::from("point")),
+    ///     ListOptions::new(),
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_point);
+    /// let input = vec!["--point", "1", "2", "--point", "3"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// drop(args_list);
+    /// assert_eq!(argument_point.values(), &vec![1, 2, 3]);
+    /// assert_eq!(argument_point.grouped_values(), vec![&[1, 2][..], &[3][..]]);
+    /// ```
+    pub fn grouped_values(&self) -> Vec<&[V]> {
+        self.occurrence_ranges()
+            .into_iter()
+            .map(|(start, end)| &self.values[start..end])
+            .collect()
+    }
+
+    /// Moves out values grouped by occurrence, like grouped_values.
+    pub fn take_grouped_values(self) -> Vec<Vec<V>> {
+        let ranges = self.occurrence_ranges();
+        let mut values = self.values.into_iter();
+        let mut position = 0;
+        ranges
+            .into_iter()
+            .map(|(start, end)| {
+                let group = values
+                    .by_ref()
+                    .skip(start - position)
+                    .take(end - start)
+                    .collect();
+                position = end;
+                group
+            })
+            .collect()
+    }
+
+    /// Ranges of values added by every occurrence, limited to values still present. Ranges are
+    /// ordered and do not overlap, even when a handler removed values of earlier occurrences.
+    fn occurrence_ranges(&self) -> Vec<(usize, usize)> {
+        let len = self.values.len();
+        let mut previous_end = 0;
+        let mut ranges = Vec::new();
+        for (start, end) in &self.occurrence_ranges {
+            let end = (*end).min(len);
+            let start = (*start).max(previous_end);
+            if start < end {
+                ranges.push((start, end));
+                previous_end = end;
+            }
+        }
+        ranges
+    }
+
+    /// Moves out values handled by the argument, so values which are not Clone can be owned
+    /// after parsing. Default value is not included.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_file = ParsableValueArgument::new_path(ArgumentIdentification::Short('f'));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_file);
+    /// let input = vec!["-f", "a.txt", "-f", "b.txt"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// drop(args_list);
+    /// let files = argument_file.take_values();
+    /// assert_eq!(files.len(), 2);
+    /// ```
+    pub fn take_values(self) -> Vec<V> {
+        self.values
+    }
+
+    /// Moves out first value given by the user or default value if argument was not used.
+    pub fn into_first(self) -> Option<V> {
+        match self.values.into_iter().next() {
+            Some(value) => Some(value),
+            None => self.default.map(|(value, _)| value),
+        }
+    }
+}
+
+impl<V> ParsableValueArgument<V> {
+    /// Prefixes handler error with identification and value which was rejected (see
+    /// InputCursor::reject), e.g. `error in --level: 'high': Input is not a number`. Value is
+    /// omitted when message already mentions it.
+    fn error_context(&self, token: Option<&str>, message: &str) -> String {
+        let mentioned = |token: &str| {
+            message.contains(&format!("'{}'", token))
+                || message
+                    .split_whitespace()
+                    .any(|word| word.trim_matches(|c| "'\",.;:?!".contains(c)) == token)
+        };
+        match token {
+            Some(token) if !mentioned(token) => {
+                format!("error in {}: '{}': {}", self.identification, token, message)
+            }
+            _ => format!("error in {}: {}", self.identification, message),
+        }
+    }
+}
+
+impl<V: ArgValue + 'static> ParsableValueArgument<V> {
+    /// Creates argument decoding values with ArgValue implementation of V. Typed constructors
+    /// (new_string, new_integer, new_u8, ...) are based on it.
+    pub fn new_of(identification: ArgumentIdentification) -> ParsableValueArgument<V> {
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<V>| {
+            let value = V::decode(input_iter).map_err(|error: ValueError| {
+                if let Some(value) = error.value() {
+                    input_iter.reject(value);
+                }
+                error.to_string()
+            })?;
+            values.push(value);
+            Result::Ok(())
+        };
+        let argument = ParsableValueArgument::new(identification, handler);
+        match V::value_name() {
+            Some(value_name) => argument.set_value_name(value_name),
+            None if V::value_names().len() > 1 => argument.set_value_names(&V::value_names()),
+            None => argument,
+        }
+    }
 }
 
 impl ParsableValueArgument<i64> {
     fn validate_integer(v: &str) -> Option<String> {
         let mut chars_iter = v.chars().peekable();
         if let Some(c) = chars_iter.next() {
-            if (c != '-' || chars_iter.peek().is_none()) && !c.is_digit(10) {
-                return Option::Some(format!("Input is not a number"));
+            if (c != '-' || chars_iter.peek().is_none()) && !c.is_ascii_digit() {
+                return Option::Some("Input is not a number".to_string());
             }
         }
         for c in chars_iter {
-            if !c.is_digit(10) {
-                return Option::Some(format!("Input is not a number"));
+            if !c.is_ascii_digit() {
+                return Option::Some("Input is not a number".to_string());
             }
         }
         Option::None
     }
+
+    pub(crate) fn parse_integer(v: &str) -> Result<i64, String> {
+        if let Option::Some(err) = ParsableValueArgument::validate_integer(v) {
+            return Result::Err(err);
+        }
+        v.parse().map_err(|err| format!("{}", err))
+    }
+
     /**
      * Default integer type argument value handler. Checks whether value contains only digits or starts with minus sign.
      */
     pub fn new_integer(identification: ArgumentIdentification) -> ParsableValueArgument<i64> {
-        let handler = |input_iter: &mut Peekable<&mut std::slice::Iter<'_, String>>,
-                       values: &mut Vec<i64>| {
-            if let Option::Some(v) = input_iter.next() {
-                let validation = ParsableValueArgument::validate_integer(v);
-                if let Option::Some(err) = validation {
-                    return Result::Err(err);
+        ParsableValueArgument::new_of(identification)
+    }
+
+    /// Parses integer which can be written with `0x`, `0o` or `0b` prefix. Decimal values are
+    /// parsed like in new_integer.
+    fn parse_integer_literal(v: &str) -> Result<i64, String> {
+        let (negative, digits) = match v.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, v),
+        };
+        let radix = match digits.get(..2) {
+            Some("0x") | Some("0X") => 16,
+            Some("0o") | Some("0O") => 8,
+            Some("0b") | Some("0B") => 2,
+            _ => return ParsableValueArgument::parse_integer(v),
+        };
+        let digits = &digits[2..];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return Result::Err(format!("Input is not a valid base {} number", radix));
+        }
+        // Parsing with sign keeps i64::MIN in range.
+        let signed = if negative {
+            format!("-{}", digits)
+        } else {
+            String::from(digits)
+        };
+        i64::from_str_radix(&signed, radix).map_err(|err| format!("{}", err))
+    }
+
+    /// Integer argument which also accepts hexadecimal (`0x1F`), octal (`0o755`) and binary
+    /// (`0b1010`) literals. Useful for permissions, masks and addresses.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_mode =
+    ///     ParsableValueArgument::new_integer_literal(ArgumentIdentification::Short('m'));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_mode);
+    /// args_list
+    ///     .parse_args(vec![String::from("-m"), String::from("0o755")])
+    ///     .unwrap();
+    /// assert_eq!(argument_mode.first_value(), Some(&0o755));
+    /// ```
+    pub fn new_integer_literal(
+        identification: ArgumentIdentification,
+    ) -> ParsableValueArgument<i64> {
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<i64>| {
+            values.push(input_iter.next_decoded(ParsableValueArgument::parse_integer_literal)?);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler).set_value_name("INT")
+    }
+
+    /// Integer argument which adds up values of all occurrences (`--weight 3 --weight 4` gives 7).
+    pub fn new_integer_sum(identification: ArgumentIdentification) -> ParsableValueArgument<i64> {
+        ParsableValueArgument::new_fold(identification, 0, |acc: i64, v| {
+            acc.checked_add(ParsableValueArgument::parse_integer(v)?)
+                .ok_or_else(|| String::from("Sum of values is too large"))
+        })
+        .set_value_name("INT")
+    }
+
+    /// Integer argument which keeps the lowest value of all occurrences.
+    pub fn new_integer_min(identification: ArgumentIdentification) -> ParsableValueArgument<i64> {
+        ParsableValueArgument::new_fold(identification, i64::MAX, |acc, v| {
+            Result::Ok(acc.min(ParsableValueArgument::parse_integer(v)?))
+        })
+        .set_value_name("INT")
+    }
+
+    /// Integer argument which keeps the highest value of all occurrences.
+    pub fn new_integer_max(identification: ArgumentIdentification) -> ParsableValueArgument<i64> {
+        ParsableValueArgument::new_fold(identification, i64::MIN, |acc, v| {
+            Result::Ok(acc.max(ParsableValueArgument::parse_integer(v)?))
+        })
+        .set_value_name("INT")
+    }
+}
+
+impl<V: FromStr + 'static> ParsableValueArgument<V> {
+    /// Parses unsigned integer. Input is validated before conversion, so conversion can only fail
+    /// when the value does not fit into V.
+    pub(crate) fn parse_unsigned(v: &str, max: u64) -> Result<V, String> {
+        if v.is_empty() || !v.chars().all(|c| c.is_ascii_digit()) {
+            return Result::Err(String::from("Input is not a non-negative number"));
+        }
+        v.parse()
+            .map_err(|_| format!("Value {} is greater than {}", v, max))
+    }
+
+    /// Unsigned integer argument which rejects values greater than `max`, or replaces them with
+    /// `max` and records a warning after allow_saturating.
+    fn new_unsigned(
+        identification: ArgumentIdentification,
+        max: V,
+        max_display: u64,
+    ) -> ParsableValueArgument<V>
+    where
+        V: Copy,
+    {
+        let warnings: Rc<RefCell<Vec<String>>> = Rc::default();
+        let saturate = Rc::new(Cell::new(false));
+        let handler_warnings = Rc::clone(&warnings);
+        let handler_saturate = Rc::clone(&saturate);
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<V>| {
+            let value = input_iter.next_decoded(|v| {
+                match ParsableValueArgument::parse_unsigned(v, max_display) {
+                    // Only digits are left, so the value is too large.
+                    Result::Err(_)
+                        if handler_saturate.get()
+                            && !v.is_empty()
+                            && v.chars().all(|c| c.is_ascii_digit()) =>
+                    {
+                        handler_warnings
+                            .borrow_mut()
+                            .push(format!("Value was saturated to {}.", max_display));
+                        Result::Ok(max)
+                    }
+                    result => result,
+                }
+            })?;
+            values.push(value);
+            Result::Ok(())
+        };
+        let mut argument =
+            ParsableValueArgument::new(identification, handler).set_value_name("INT");
+        argument.warnings = warnings;
+        argument.saturate = Some(saturate);
+        argument
+    }
+
+    /// Makes handler created by new_unsigned saturate values. Handlers replaced or wrapped by
+    /// the user are not changed.
+    fn saturating(self) -> ParsableValueArgument<V> {
+        if let Some(saturate) = &self.saturate {
+            saturate.set(true);
+        }
+        self
+    }
+}
+
+impl ParsableValueArgument<u8> {
+    /// Unsigned integer argument rejecting values greater than 255.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_level =
+    ///     ParsableValueArgument::new_u8(ArgumentIdentification::Short('l')).allow_saturating();
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_level);
+    /// args_list
+    ///     .parse_args(vec![String::from("-l"), String::from("300")])
+    ///     .unwrap();
+    /// assert_eq!(args_list.value_warnings(), &vec![String::from("-l: Value was saturated to 255.")]);
+    /// drop(args_list);
+    /// assert_eq!(argument_level.first_value(), Some(&255));
+    /// ```
+    pub fn new_u8(identification: ArgumentIdentification) -> ParsableValueArgument<u8> {
+        ParsableValueArgument::new_unsigned(identification, u8::MAX, u8::MAX.into())
+    }
+
+    /// Accepts values greater than 255 as 255 instead of rejecting them. Saturation is reported
+    /// by ArgumentList::value_warnings and in ParseReport. Affects only values read by handler of
+    /// new_u8, wrappers like optional_value keep working and custom handlers are not changed.
+    pub fn allow_saturating(self) -> ParsableValueArgument<u8> {
+        self.saturating()
+    }
+}
+
+impl ParsableValueArgument<u16> {
+    /// Unsigned integer argument rejecting values greater than 65535, e.g. port numbers.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_port = ParsableValueArgument::new_u16(ArgumentIdentification::Short('p'));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_port);
+    /// assert!(args_list
+    ///     .parse_args(vec![String::from("-p"), String::from("65536")])
+    ///     .is_err());
+    /// args_list
+    ///     .parse_args(vec![String::from("-p"), String::from("8080")])
+    ///     .unwrap();
+    /// assert_eq!(argument_port.first_value(), Some(&8080));
+    /// ```
+    pub fn new_u16(identification: ArgumentIdentification) -> ParsableValueArgument<u16> {
+        ParsableValueArgument::new_unsigned(identification, u16::MAX, u16::MAX.into())
+    }
+
+    /// Accepts values greater than 65535 as 65535 instead of rejecting them.
+    pub fn allow_saturating(self) -> ParsableValueArgument<u16> {
+        self.saturating()
+    }
+}
+
+impl ParsableValueArgument<u32> {
+    /// Unsigned integer argument rejecting values greater than 4294967295.
+    pub fn new_u32(identification: ArgumentIdentification) -> ParsableValueArgument<u32> {
+        ParsableValueArgument::new_unsigned(identification, u32::MAX, u32::MAX.into())
+    }
+
+    /// Accepts values greater than 4294967295 as 4294967295 instead of rejecting them.
+    pub fn allow_saturating(self) -> ParsableValueArgument<u32> {
+        self.saturating()
+    }
+}
+
+impl ParsableValueArgument<u64> {
+    /// Unsigned integer argument accepting any 64 bit value.
+    pub fn new_u64(identification: ArgumentIdentification) -> ParsableValueArgument<u64> {
+        ParsableValueArgument::new_unsigned(identification, u64::MAX, u64::MAX)
+    }
+
+    /// Accepts values greater than 18446744073709551615 as the maximum instead of rejecting
+    /// them.
+    pub fn allow_saturating(self) -> ParsableValueArgument<u64> {
+        self.saturating()
+    }
+}
+
+impl ParsableValueArgument<char> {
+    /// Argument accepting a single character, e.g. a delimiter.
+    pub fn new_char(identification: ArgumentIdentification) -> ParsableValueArgument<char> {
+        ParsableValueArgument::new_of(identification)
+    }
+}
+
+/// Decides what happens when argument made single with ParsableValueArgument::single is given
+/// more than once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicatePolicy {
+    /// Repeated occurrence fails without consuming its value.
+    Error,
+    /// Value of the first occurrence is kept. Repeated values are still validated.
+    First,
+    /// Value of the last occurrence replaces earlier ones.
+    Last,
+}
+
+/// Decides how new_percentage interprets values written without `%` sign.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BareNumber {
+    /// `0.75` means 75%.
+    Ratio,
+    /// `75` means 75%.
+    Percent,
+}
+
+impl ParsableValueArgument<f64> {
+    fn parse_percentage(v: &str, bare: BareNumber) -> Result<f64, String> {
+        let (number, is_percent) = match v.strip_suffix('%') {
+            Some(number) => (number, true),
+            None => (v, bare == BareNumber::Percent),
+        };
+        let number: f64 = number
+            .trim()
+            .parse()
+            .map_err(|_| format!("'{}' is not a percentage", v))?;
+        let ratio = if is_percent { number / 100.0 } else { number };
+        // Comparison is false for NaN, so it is rejected too.
+        if (0.0..=1.0).contains(&ratio) {
+            Result::Ok(ratio)
+        } else {
+            Result::Err(format!("'{}' is not between 0% and 100%", v))
+        }
+    }
+
+    /// Argument accepting percentage (`75%`) or a value without `%` sign, interpreted as
+    /// selected by `bare`. Values are normalized to ratio in range [0, 1].
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{
+    ///         parsable_argument::{BareNumber, ParsableValueArgument},
+    ///         ArgumentIdentification,
+    ///     },
+    /// };
+    ///
+    /// let mut argument_rate = ParsableValueArgument::new_percentage(
+    ///     ArgumentIdentification::Long(String::from("sample-rate")),
+    ///     BareNumber::Ratio,
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_rate);
+    /// args_list
+    ///     .parse_args(vec![
+    ///         String::from("--sample-rate"),
+    ///         String::from("25%"),
+    ///         String::from("--sample-rate"),
+    ///         String::from("0.5"),
+    ///     ])
+    ///     .unwrap();
+    /// assert_eq!(argument_rate.values(), &vec![0.25, 0.5]);
+    /// ```
+    pub fn new_percentage(
+        identification: ArgumentIdentification,
+        bare: BareNumber,
+    ) -> ParsableValueArgument<f64> {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<f64>| {
+            values.push(
+                input_iter.next_decoded(|v| ParsableValueArgument::parse_percentage(v, bare))?,
+            );
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler).set_value_name("PERCENT")
+    }
+}
+
+/// Defines how list arguments read their items.
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    delimiter: Option<char>,
+    min_items: usize,
+    max_items: Option<usize>,
+    external: Option<ExternalItems>,
+}
+
+/// Reads whole standard input.
+fn read_stdin() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Result::Ok(input)
+}
+
+impl ListOptions {
+    /// Items are read from following input values until one looking like an argument is found.
+    /// Every occurrence needs at least one item.
+    pub fn new() -> ListOptions {
+        ListOptions {
+            delimiter: None,
+            min_items: 1,
+            max_items: None,
+            external: None,
+        }
+    }
+
+    /// Items are read from a single input value split by delimiter (e.g. `--ids 1,2,3`).
+    pub fn delimiter(mut self, delimiter: char) -> ListOptions {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Minimal number of items in every occurrence.
+    pub fn min_items(mut self, min_items: usize) -> ListOptions {
+        self.min_items = min_items;
+        self
+    }
+
+    /// Maximal number of items in every occurrence.
+    pub fn max_items(mut self, max_items: usize) -> ListOptions {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// When `marker` (e.g. `--stdin` or `-`) is given as an item, items are also read from
+    /// standard input, one per line, and merged with items given on the command line. Useful
+    /// when list of files is too long for a single command.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{
+    ///         parsable_argument::{ListOptions, ParsableValueArgument},
+    ///         ArgumentIdentification,
+    ///     },
+    /// };
+    ///
+    /// // find . -name '*.rs' | program --files main.rs --stdin
+    /// let mut argument_files = ParsableValueArgument::new_path_list(
+    ///     ArgumentIdentification::Long(String::from("files")),
+    ///     ListOptions::new().stdin("--stdin"),
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_files);
+    /// args_list
+    ///     .parse_args(std::env::args().skip(1).collect())
+    ///     .unwrap();
+    /// ```
+    pub fn stdin(self, marker: &str) -> ListOptions {
+        self.lines_from(marker, read_stdin)
+    }
+
+    /// Like stdin, but lines are returned by `read` instead of read from standard input.
+    pub fn lines_from(mut self, marker: &str, read: fn() -> io::Result<String>) -> ListOptions {
+        self.external = Some((String::from(marker), read));
+        self
+    }
+
+    /// Check if value is marker requesting items from outside of the command line.
+    fn is_external(&self, value: &str) -> bool {
+        matches!(&self.external, Some((marker, _)) if marker == value)
+    }
+
+    fn read_external<V>(
+        &self,
+        items: &mut Vec<V>,
+        parse: &dyn Fn(&str) -> Result<V, String>,
+    ) -> Result<(), String> {
+        if let Some((marker, read)) = &self.external {
+            let input = read().map_err(|err| format!("Could not read {}: {}", marker, err))?;
+            for line in input.lines().filter(|line| !line.trim().is_empty()) {
+                items.push(parse(line)?);
+            }
+        }
+        Result::Ok(())
+    }
+
+    /// Check if value should stop list, because it is a next argument.
+    fn is_argument(value: &str) -> bool {
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some('-'), Some('-')) => value.len() > 2,
+            (Some('-'), Some(c)) => c.is_alphabetic(),
+            _ => false,
+        }
+    }
+
+    fn read_items<V>(
+        &self,
+        input_iter: &mut InputCursor<'_>,
+        parse: &dyn Fn(&str) -> Result<V, String>,
+    ) -> Result<Vec<V>, String> {
+        let mut items = Vec::new();
+        match self.delimiter {
+            Some(delimiter) => {
+                if let Some(v) = input_iter.next() {
+                    if self.is_external(v) {
+                        self.read_external(&mut items, parse)?;
+                    } else {
+                        for item in v.split(delimiter).filter(|item| !item.is_empty()) {
+                            items.push(parse(item).inspect_err(|_| input_iter.reject(item))?);
+                        }
+                    }
                 }
-                match v.parse() {
-                    Result::Ok(v) => {
-                        values.push(v);
-                        Ok(())
+            }
+            None => {
+                while let Some(v) =
+                    input_iter.next_if(|v| !ListOptions::is_argument(v) || self.is_external(v))
+                {
+                    if self.is_external(v) {
+                        self.read_external(&mut items, parse)?;
+                        continue;
                     }
-                    Result::Err(err) => Result::Err(format!("{}", err)),
+                    if self.max_items == Some(items.len()) {
+                        return Result::Err(format!("Expected at most {} items.", items.len()));
+                    }
+                    items.push(parse(v).inspect_err(|_| input_iter.reject(v))?);
                 }
-            } else {
-                Result::Err(String::from("No remaining input values."))
             }
+        }
+        if items.len() < self.min_items {
+            return Result::Err(format!("Expected at least {} items.", self.min_items));
+        }
+        if let Some(max_items) = self.max_items {
+            if items.len() > max_items {
+                return Result::Err(format!("Expected at most {} items.", max_items));
+            }
+        }
+        Result::Ok(items)
+    }
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        ListOptions::new()
+    }
+}
+
+impl<V: 'static> ParsableValueArgument<V> {
+    /// Creates argument reading a list of items in every occurrence. Items of all occurrences are
+    /// stored together in values.
+    pub fn new_list<F>(
+        identification: ArgumentIdentification,
+        options: ListOptions,
+        parse: F,
+    ) -> Self
+    where
+        F: Fn(&str) -> Result<V, String> + 'static,
+    {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<V>| {
+            values.append(&mut options.read_items(input_iter, &parse)?);
+            Result::Ok(())
         };
         ParsableValueArgument::new(identification, handler)
     }
 }
 
+impl ParsableValueArgument<Vec<String>> {
+    /// Argument capturing next `count` input values verbatim, even if they look like arguments.
+    /// Every occurrence adds one value containing captured input values.
+    pub fn new_raw(
+        identification: ArgumentIdentification,
+        count: usize,
+    ) -> ParsableValueArgument<Vec<String>> {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<Vec<String>>| {
+            let captured = input_iter.take(count).to_vec();
+            if captured.len() < count {
+                return Result::Err(format!(
+                    "Expected {} values, but only {} were given.",
+                    count,
+                    captured.len()
+                ));
+            }
+            values.push(captured);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+
+    /// Argument capturing input values verbatim until one matching `is_end` is found, like
+    /// `-exec` of `find`. Terminating value is consumed, but not captured.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_exec = ParsableValueArgument::new_raw_until(
+    ///     ArgumentIdentification::Long(String::from("exec")),
+    ///     |v| v == ";",
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_exec);
+    /// let input = ["--exec", "cp", "-r", "{}", "dest", ";", "src"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|v| String::from(*v)).collect())
+    ///     .unwrap();
+    /// assert_eq!(args_list.dangling_values, vec!["src"]);
+    /// assert_eq!(argument_exec.first_value().unwrap(), &vec!["cp", "-r", "{}", "dest"]);
+    /// ```
+    pub fn new_raw_until<P>(
+        identification: ArgumentIdentification,
+        is_end: P,
+    ) -> ParsableValueArgument<Vec<String>>
+    where
+        P: Fn(&str) -> bool + 'static,
+    {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<Vec<String>>| {
+            let captured = input_iter.take_while(|v| !is_end(v)).to_vec();
+            // Terminating value is consumed, but not captured.
+            match input_iter.next() {
+                Some(_) => {
+                    values.push(captured);
+                    Result::Ok(())
+                }
+                None => Result::Err(String::from("Missing value terminating captured values.")),
+            }
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+}
+
+impl ParsableValueArgument<i64> {
+    /// List of integers (`--ids 1 2 3`).
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{
+    ///         parsable_argument::{ListOptions, ParsableValueArgument},
+    ///         ArgumentIdentification,
+    ///     },
+    /// };
+    ///
+    /// let mut argument_ids = ParsableValueArgument::new_integer_list(
+    ///     ArgumentIdentification::Long(String::from("ids")),
+    ///     ListOptions::new().delimiter(','),
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_ids);
+    /// args_list
+    ///     .parse_args(vec![String::from("--ids"), String::from("1,2,3")])
+    ///     .unwrap();
+    /// assert_eq!(argument_ids.values(), &vec![1, 2, 3]);
+    /// ```
+    pub fn new_integer_list(
+        identification: ArgumentIdentification,
+        options: ListOptions,
+    ) -> ParsableValueArgument<i64> {
+        ParsableValueArgument::new_list(
+            identification,
+            options,
+            ParsableValueArgument::parse_integer,
+        )
+        .set_value_name("INT")
+    }
+}
+
+impl ParsableValueArgument<String> {
+    /// List of strings (`--names a b c`).
+    pub fn new_string_list(
+        identification: ArgumentIdentification,
+        options: ListOptions,
+    ) -> ParsableValueArgument<String> {
+        ParsableValueArgument::new_list(identification, options, |v| Result::Ok(String::from(v)))
+    }
+}
+
+impl ParsableValueArgument<PathBuf> {
+    /// Default path type argument value handler.
+    pub fn new_path(identification: ArgumentIdentification) -> ParsableValueArgument<PathBuf> {
+        ParsableValueArgument::new_of(identification)
+    }
+
+    /// List of paths (`--files a.txt b.txt`).
+    pub fn new_path_list(
+        identification: ArgumentIdentification,
+        options: ListOptions,
+    ) -> ParsableValueArgument<PathBuf> {
+        ParsableValueArgument::new_list(identification, options, |v| Result::Ok(PathBuf::from(v)))
+            .set_value_name("PATH")
+    }
+}
+
+impl<V: Clone + 'static> ParsableValueArgument<V> {
+    /// Creates argument taking single value per occurrence which is parsed through given cache.
+    /// Identical input values are parsed only once, even across multiple arguments or parses
+    /// sharing the same cache.
+    pub fn new_cached(identification: ArgumentIdentification, cache: ValueCache<V>) -> Self {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<V>| {
+            values.push(input_iter.next_decoded(|v| cache.get(v))?);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+
+    /// Creates argument accumulating all occurrences into a single value. Every occurrence takes
+    /// one input value which is combined with current result by `fold`. The first occurrence is
+    /// combined with `init`. Accumulated value can be read with `first_value`, it is not available
+    /// when argument was not used.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_path = ParsableValueArgument::new_fold(
+    ///     ArgumentIdentification::Long(String::from("path")),
+    ///     String::new(),
+    ///     |acc, v| Result::Ok(format!("{}/{}", acc, v)),
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_path);
+    /// let input = vec!["--path", "usr", "--path", "bin"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// assert_eq!(argument_path.first_value().unwrap(), "/usr/bin");
+    /// ```
+    pub fn new_fold<F>(identification: ArgumentIdentification, init: V, fold: F) -> Self
+    where
+        F: Fn(V, &str) -> Result<V, String> + 'static,
+    {
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<V>| {
+            let acc = values.first().unwrap_or(&init).clone();
+            let result = input_iter.next_decoded(|v| fold(acc, v))?;
+            values.clear();
+            values.push(result);
+            Result::Ok(())
+        };
+        ParsableValueArgument::new(identification, handler)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<V: Sync + 'static> ParsableValueArgument<V> {
+    /// Sets validator invoked for every collected value after the whole input was tokenized.
+    /// Values are validated in parallel on rayon thread pool, which pays off when validation is
+    /// expensive and value list is long. The first rejected value in input order is reported.
+    /// Runs after finalizer set before. Available with `parallel` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_int = ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+    ///     .set_parallel_validator(|v| {
+    ///         if *v % 2 == 0 {
+    ///             Result::Ok(())
+    ///         } else {
+    ///             Result::Err(format!("{} is odd", v))
+    ///         }
+    ///     });
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_int);
+    /// args_list
+    ///     .parse_args(vec![String::from("-n"), String::from("2"), String::from("-n"), String::from("4")])
+    ///     .unwrap();
+    /// assert_eq!(argument_int.values(), &vec![2, 4]);
+    /// ```
+    pub fn set_parallel_validator<F>(mut self, validator: F) -> ParsableValueArgument<V>
+    where
+        F: Fn(&V) -> Result<(), String> + Sync + Send + 'static,
+    {
+        use rayon::prelude::*;
+
+        let previous = self.finalizer.take();
+        self.finalizer = Some(Box::new(move |values: &mut Vec<V>| {
+            if let Some(previous) = &previous {
+                previous(values)?;
+            }
+            let error = values
+                .par_iter()
+                .enumerate()
+                .filter_map(|(i, v)| validator(v).err().map(|e| (i, e)))
+                .min_by_key(|(i, _)| *i);
+            match error {
+                Some((_, e)) => Result::Err(e),
+                None => Result::Ok(()),
+            }
+        }));
+        self
+    }
+}
+
 impl ParsableValueArgument<String> {
     /**
      * Default string type argument value handler.
      */
     pub fn new_string(identification: ArgumentIdentification) -> ParsableValueArgument<String> {
-        let handler = |input_iter: &mut Peekable<&mut std::slice::Iter<'_, String>>,
-                       values: &mut Vec<String>| {
-            if let Some(v) = input_iter.next() {
-                values.push(String::from(v));
-                Result::Ok(())
-            } else {
-                Result::Err(String::from("No remaining input values."))
-            }
+        ParsableValueArgument::new_of(identification)
+    }
+}
+
+impl ParsableValueArgument<String> {
+    /// Creates argument accepting only one of given values. Misspelled values are rejected with
+    /// suggestion of the closest allowed value.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    ///     error::ParseError,
+    /// };
+    ///
+    /// let mut argument_color = ParsableValueArgument::new_choice(
+    ///     ArgumentIdentification::Long(String::from("color")),
+    ///     &["auto", "always", "never"],
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_color);
+    /// assert_eq!(
+    ///     args_list.parse_args(vec![String::from("--color"), String::from("nevr")]),
+    ///     Err(ParseError::InvalidValue {
+    ///         argument: String::from("--color"),
+    ///         message: String::from("error in --color: 'nevr' is not valid; did you mean 'never'?"),
+    ///         usage: String::from("--color <auto|always|never>"),
+    ///         value: Some(String::from("nevr")),
+    ///         suggestion: Some(String::from("never")),
+    ///     })
+    /// );
+    /// ```
+    pub fn new_choice(
+        identification: ArgumentIdentification,
+        choices: &[&str],
+    ) -> ParsableValueArgument<String> {
+        let allowed: Vec<String> = choices.iter().map(|c| String::from(*c)).collect();
+        let handler_choices = allowed.clone();
+        let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<String>| {
+            let value = input_iter.next_decoded(|v| {
+                if !handler_choices.iter().any(|c| c == v) {
+                    return Result::Err(suggest::invalid_choice(
+                        v,
+                        handler_choices.iter().map(String::as_str),
+                    ));
+                }
+                Result::Ok(String::from(v))
+            })?;
+            values.push(value);
+            Result::Ok(())
         };
-        ParsableValueArgument::new(identification, handler)
+        let mut argument = ParsableValueArgument::new(identification, handler);
+        argument.choices = allowed;
+        argument
+    }
+}
+
+impl ParsableValueArgument<bool> {
+    /// Creates flag argument which does not take any value. Flag is set when argument was given
+    /// at least once.
+    pub fn new_flag(identification: ArgumentIdentification) -> ParsableValueArgument<bool> {
+        let handler = |_: &mut InputCursor<'_>, values: &mut Vec<bool>| {
+            values.clear();
+            values.push(true);
+            Result::Ok(())
+        };
+        let mut argument = ParsableValueArgument::new(identification, handler);
+        argument.arg_type = ArgType::Flag;
+        argument
+    }
+
+    /// Makes every occurrence of the flag switch its state, so flag given an even number of times
+    /// is not set. Useful when aliases or wrapper scripts inject flags which user wants to cancel.
+    /// Should be used only with flags created by new_flag.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_color =
+    ///     ParsableValueArgument::new_flag(ArgumentIdentification::Long(String::from("color")))
+    ///         .toggleable();
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_color);
+    /// args_list
+    ///     .parse_args(vec![String::from("--color"), String::from("--color")])
+    ///     .unwrap();
+    /// assert!(!argument_color.is_set());
+    /// ```
+    pub fn toggleable(mut self) -> ParsableValueArgument<bool> {
+        self.handler = Box::new(|_: &mut InputCursor<'_>, values: &mut Vec<bool>| {
+            let set = values.first().copied().unwrap_or(false);
+            values.clear();
+            values.push(!set);
+            Result::Ok(())
+        });
+        self
+    }
+
+    /// Check if flag is set.
+    pub fn is_set(&self) -> bool {
+        self.first_value().copied().unwrap_or(false)
+    }
+
+    /// Creates flag disabled with `-x` and enabled with `+x` (requires
+    /// ParserOptions::plus_options). The last occurrence wins and polarity tells whether the flag
+    /// was given at all.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    ///     options::ParserOptions,
+    /// };
+    ///
+    /// let mut argument_trace = ParsableValueArgument::new_polarity(ArgumentIdentification::Short('x'));
+    /// let mut argument_echo = ParsableValueArgument::new_polarity(ArgumentIdentification::Short('e'));
+    /// let mut argument_unset = ParsableValueArgument::new_polarity(ArgumentIdentification::Short('u'));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.set_options(ParserOptions::new().plus_options(true));
+    /// args_list.register_parsable(&mut argument_trace);
+    /// args_list.register_parsable(&mut argument_echo);
+    /// args_list.register_parsable(&mut argument_unset);
+    /// let input = vec!["-x", "+x", "-e"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// drop(args_list);
+    /// assert_eq!(argument_trace.polarity(), Some(true));
+    /// assert_eq!(argument_echo.polarity(), Some(false));
+    /// assert_eq!(argument_unset.polarity(), None);
+    /// ```
+    pub fn new_polarity(identification: ArgumentIdentification) -> ParsableValueArgument<bool> {
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<bool>| {
+            let enabled = input_iter
+                .context()
+                .is_some_and(|context| context.form().starts_with('+'));
+            values.clear();
+            values.push(enabled);
+            Result::Ok(())
+        };
+        let mut argument = ParsableValueArgument::new(identification, handler);
+        argument.arg_type = ArgType::Flag;
+        argument
+    }
+
+    /// Returns Some(true) when flag created with new_polarity was enabled with `+`, Some(false)
+    /// when it was disabled with `-` and None when it was not given.
+    pub fn polarity(&self) -> Option<bool> {
+        self.first_value().copied()
     }
 }
 
 impl<'a, V> HandleableArgument<'a> for ParsableValueArgument<V> {
-    fn handle(
-        &mut self,
-        input_iter: &mut Peekable<&mut std::slice::Iter<'_, String>>,
-    ) -> Result<(), String> {
-        (self.handler)(input_iter, &mut self.values)?;
+    fn handle(&mut self, input_iter: &mut InputCursor<'_>) -> Result<(), String> {
+        let count = self.values.len();
+        if let Result::Err(message) = (self.handler)(input_iter, &mut self.values) {
+            // Values of failed occurrence are discarded, like input it consumed.
+            self.values.truncate(count);
+            let token = input_iter.rejected().filter(|_| !self.secret);
+            return Result::Err(self.error_context(token, &message));
+        }
+        let end = self.values.len();
+        if self.arg_type == ArgType::Value {
+            // Handlers of single value arguments replace values of earlier occurrences.
+            self.occurrence_ranges = vec![(0, end)];
+        } else if end > count {
+            self.occurrence_ranges.push((count, end));
+        }
         Result::Ok(())
     }
 
@@ -131,13 +1652,79 @@ impl<'a, V> HandleableArgument<'a> for ParsableValueArgument<V> {
     fn identification(&self) -> &ArgumentIdentification {
         &self.identification
     }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn localized_descriptions(&self) -> Vec<(String, String)> {
+        self.localized_descriptions.clone()
+    }
+
+    fn finalize(&mut self) -> Result<(), String> {
+        if let Some(finalizer) = &self.source_finalizer {
+            finalizer(&mut self.values)?;
+        }
+        match &self.finalizer {
+            Some(finalizer) => finalizer(&mut self.values),
+            None => Result::Ok(()),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.required
+    }
+
+    fn is_secret(&self) -> bool {
+        self.secret
+    }
+
+    fn default_value(&self) -> Option<&str> {
+        self.default.as_ref().map(|(_, display)| display.as_str())
+    }
+
+    fn choices(&self) -> Vec<String> {
+        self.choices.clone()
+    }
+
+    fn examples(&self) -> Vec<String> {
+        self.examples.clone()
+    }
+
+    fn value_name(&self) -> Option<&str> {
+        self.value_names.first().map(String::as_str)
+    }
+
+    fn value_names(&self) -> Vec<&str> {
+        self.value_names.iter().map(String::as_str).collect()
+    }
+
+    fn tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+
+    fn has_optional_value(&self) -> bool {
+        self.optional_value
+    }
+
+    fn take_warnings(&mut self) -> Vec<String> {
+        self.warnings.take()
+    }
+
+    fn arg_type(&self) -> ArgType {
+        self.arg_type
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::borrow::BorrowMut;
+    use crate::cursor::InputCursor;
 
-    use super::{HandleableArgument, ParsableValueArgument};
+    use std::path::PathBuf;
+
+    use super::{
+        BareNumber, DuplicatePolicy, HandleableArgument, ListOptions, ParsableValueArgument,
+    };
 
     #[test]
     fn new_parsable_value_argument_works() {
@@ -168,46 +1755,685 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::get_first, clippy::useless_vec)]
     fn basic_integer_argument_works() {
         let mut arg =
             ParsableValueArgument::<i64>::new_integer(super::ArgumentIdentification::Short('i'));
         assert!(arg
-            .handle(&mut vec![String::from("123")].iter().borrow_mut().peekable())
+            .handle(&mut InputCursor::new(&vec![String::from("123")]))
             .is_ok());
         assert_eq!(arg.values.get(0).unwrap(), &123);
         assert!(arg
-            .handle(&mut vec![String::from("333")].iter().borrow_mut().peekable())
+            .handle(&mut InputCursor::new(&vec![String::from("333")]))
             .is_ok());
         assert_eq!(2, arg.values.len());
         assert_eq!(arg.values.get(0).unwrap(), &123);
         assert_eq!(arg.values.get(1).unwrap(), &333);
         assert!(arg
-            .handle(&mut vec![String::from("-333")].iter().borrow_mut().peekable())
+            .handle(&mut InputCursor::new(&vec![String::from("-333")]))
             .is_ok());
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn basic_integer_argument_handler_fails_invalid_number() {
         let mut arg =
             ParsableValueArgument::<i64>::new_integer(super::ArgumentIdentification::Short('i'));
         assert!(arg
-            .handle(&mut vec![String::from("-")].iter().borrow_mut().peekable())
+            .handle(&mut InputCursor::new(&vec![String::from("-")]))
+            .is_err());
+        assert!(arg
+            .handle(&mut InputCursor::new(&vec![String::from("12a")]))
             .is_err());
         assert!(arg
-            .handle(&mut vec![String::from("12a")].iter().borrow_mut().peekable())
+            .handle(&mut InputCursor::new(&vec![String::from("123.12")]))
             .is_err());
+    }
+
+    #[test]
+    fn integer_literal_works() {
+        let mut arg =
+            ParsableValueArgument::new_integer_literal(super::ArgumentIdentification::Short('m'));
+        for input in ["0x1F", "-0b1010", "0O17", "42"].iter() {
+            arg.handle(&mut InputCursor::new(&[String::from(*input)]))
+                .unwrap();
+        }
+        assert_eq!(arg.values(), &vec![31, -10, 15, 42]);
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("0b102")])),
+            Err(String::from(
+                "error in -m: '0b102': Input is not a valid base 2 number"
+            ))
+        );
         assert!(arg
-            .handle(&mut vec![String::from("123.12")].iter().borrow_mut().peekable())
+            .handle(&mut InputCursor::new(&[String::from("0x")]))
             .is_err());
+        arg.handle(&mut InputCursor::new(&[String::from(
+            "-0x8000000000000000",
+        )]))
+        .unwrap();
+        assert_eq!(arg.values().last(), Some(&i64::MIN));
+    }
+
+    #[test]
+    fn percentage_works() {
+        let mut arg = ParsableValueArgument::new_percentage(
+            super::ArgumentIdentification::Short('r'),
+            BareNumber::Percent,
+        );
+        assert_eq!(arg.value_name(), Some("PERCENT"));
+        for input in ["75", "5%", "100"].iter() {
+            arg.handle(&mut InputCursor::new(&[String::from(*input)]))
+                .unwrap();
+        }
+        assert_eq!(arg.values(), &vec![0.75, 0.05, 1.0]);
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("120%")])),
+            Err(String::from(
+                "error in -r: '120%' is not between 0% and 100%"
+            ))
+        );
+        for input in ["-1", "NaN", "half"].iter() {
+            assert!(arg
+                .handle(&mut InputCursor::new(&[String::from(*input)]))
+                .is_err());
+        }
     }
 
     #[test]
+    fn unsigned_arguments_check_overflow() {
+        let mut arg = ParsableValueArgument::new_u8(super::ArgumentIdentification::Short('n'));
+        arg.handle(&mut InputCursor::new(&[String::from("255")]))
+            .unwrap();
+        assert_eq!(arg.first_value(), Some(&255));
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("256")])),
+            Err(String::from("error in -n: Value 256 is greater than 255"))
+        );
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("-1")])),
+            Err(String::from(
+                "error in -n: '-1': Input is not a non-negative number"
+            ))
+        );
+        let mut arg = ParsableValueArgument::new_u64(super::ArgumentIdentification::Short('n'));
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from(
+                "18446744073709551616"
+            )]))
+            .is_err());
+    }
+
+    #[test]
+    fn allow_saturating_keeps_handlers() {
+        let mut arg = ParsableValueArgument::new_u8(super::ArgumentIdentification::Short('n'))
+            .optional_value(1)
+            .allow_saturating();
+        let input = [String::from("300"), String::from("-v")];
+        let mut cursor = InputCursor::new(&input);
+        arg.handle(&mut cursor).unwrap();
+        arg.handle(&mut cursor).unwrap();
+        assert_eq!(arg.values(), &vec![255, 1]);
+        assert_eq!(
+            arg.take_warnings(),
+            vec![String::from("Value was saturated to 255.")]
+        );
+        let mut arg = ParsableValueArgument::new(
+            super::ArgumentIdentification::Short('n'),
+            |input_iter, values: &mut Vec<u8>| {
+                let v = input_iter
+                    .next()
+                    .ok_or_else(|| String::from("No remaining input values."))?;
+                values.push(v.len() as u8);
+                Result::Ok(())
+            },
+        )
+        .allow_saturating();
+        arg.handle(&mut InputCursor::new(&[String::from("300")]))
+            .unwrap();
+        assert_eq!(arg.values(), &vec![3]);
+        assert!(arg.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn char_argument_works() {
+        let mut arg = ParsableValueArgument::new_char(super::ArgumentIdentification::Short('d'));
+        arg.handle(&mut InputCursor::new(&[String::from("ż")]))
+            .unwrap();
+        assert_eq!(arg.first_value(), Some(&'ż'));
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from(",;")]))
+            .is_err());
+        assert!(arg.handle(&mut InputCursor::new(&[String::new()])).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::useless_vec)]
     fn first_value_works() {
         let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'));
         assert!(arg.first_value().is_none());
         assert!(arg
-            .handle(&mut vec![String::from("123")].iter().borrow_mut().peekable())
+            .handle(&mut InputCursor::new(&vec![String::from("123")]))
             .is_ok());
         assert_eq!(arg.first_value().unwrap(), &123);
     }
+
+    #[test]
+    fn finalize_works() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .set_finalizer(|values| {
+                if values.len() > 1 {
+                    return Result::Err(String::from("Too many values"));
+                }
+                Result::Ok(())
+            });
+        assert!(arg.finalize().is_ok());
+        assert!(arg
+            .handle(&mut InputCursor::new(&[
+                String::from("1"),
+                String::from("2")
+            ]))
+            .is_ok());
+        assert!(arg.finalize().is_ok());
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("2")]))
+            .is_ok());
+        assert_eq!(arg.finalize().unwrap_err(), "Too many values");
+    }
+
+    #[test]
+    fn integer_sum_works() {
+        let mut arg =
+            ParsableValueArgument::new_integer_sum(super::ArgumentIdentification::Short('w'));
+        assert!(arg.first_value().is_none());
+        for v in ["3", "4"].iter() {
+            assert!(arg
+                .handle(&mut InputCursor::new(&[String::from(*v)]))
+                .is_ok());
+        }
+        assert_eq!(arg.values(), &vec![7]);
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("x")]))
+            .is_err());
+        assert_eq!(arg.values(), &vec![7]);
+    }
+
+    #[test]
+    fn integer_min_max_works() {
+        let mut min =
+            ParsableValueArgument::new_integer_min(super::ArgumentIdentification::Short('m'));
+        let mut max =
+            ParsableValueArgument::new_integer_max(super::ArgumentIdentification::Short('x'));
+        for v in ["3", "-4", "10"].iter() {
+            let input = [String::from(*v)];
+            assert!(min.handle(&mut InputCursor::new(&input)).is_ok());
+            assert!(max.handle(&mut InputCursor::new(&input)).is_ok());
+        }
+        assert_eq!(min.first_value().unwrap(), &-4);
+        assert_eq!(max.first_value().unwrap(), &10);
+    }
+
+    #[test]
+    fn integer_list_works() {
+        let mut arg = ParsableValueArgument::new_integer_list(
+            super::ArgumentIdentification::Short('i'),
+            ListOptions::new(),
+        );
+        let input = [
+            String::from("1"),
+            String::from("-2"),
+            String::from("3"),
+            String::from("--next"),
+        ];
+        let mut input_iter = InputCursor::new(&input);
+        assert!(arg.handle(&mut input_iter).is_ok());
+        assert_eq!(arg.values(), &vec![1, -2, 3]);
+        assert_eq!(input_iter.next().unwrap(), "--next");
+    }
+
+    #[test]
+    fn string_list_delimiter_works() {
+        let mut arg = ParsableValueArgument::new_string_list(
+            super::ArgumentIdentification::Short('s'),
+            ListOptions::new().delimiter(','),
+        );
+        assert!(arg
+            .handle(&mut InputCursor::new(&[
+                String::from("a,b"),
+                String::from("c")
+            ]))
+            .is_ok());
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("d")]))
+            .is_ok());
+        assert_eq!(
+            arg.values(),
+            &vec![String::from("a"), String::from("b"), String::from("d")]
+        );
+    }
+
+    #[test]
+    fn list_reads_external_lines() {
+        fn read() -> std::io::Result<String> {
+            Ok(String::from("b\n\nc\r\n"))
+        }
+        let mut arg = ParsableValueArgument::new_string_list(
+            super::ArgumentIdentification::Short('s'),
+            ListOptions::new().lines_from("--stdin", read),
+        );
+        arg.handle(&mut InputCursor::new(&[
+            String::from("a"),
+            String::from("--stdin"),
+            String::from("d"),
+        ]))
+        .unwrap();
+        assert_eq!(arg.values(), &vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn raw_captures_values_verbatim() {
+        let mut arg = ParsableValueArgument::new_raw(super::ArgumentIdentification::Short('x'), 2);
+        let input = [String::from("-v"), String::from("--a"), String::from("b")];
+        let mut input_iter = InputCursor::new(&input);
+        arg.handle(&mut input_iter).unwrap();
+        assert_eq!(arg.first_value().unwrap(), &vec!["-v", "--a"]);
+        assert_eq!(
+            arg.handle(&mut input_iter),
+            Err(String::from(
+                "error in -x: Expected 2 values, but only 1 were given."
+            ))
+        );
+        let mut arg =
+            ParsableValueArgument::new_raw_until(super::ArgumentIdentification::Short('x'), |v| {
+                v == ";"
+            });
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("ls")]))
+            .is_err());
+    }
+
+    #[test]
+    fn path_list_limits_work() {
+        let mut arg = ParsableValueArgument::new_path_list(
+            super::ArgumentIdentification::Short('f'),
+            ListOptions::new().min_items(2).max_items(3),
+        );
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("a")]))
+                .unwrap_err(),
+            "error in -f: Expected at least 2 items."
+        );
+        let input: Vec<String> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|s| String::from(*s))
+            .collect();
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&input)).unwrap_err(),
+            "error in -f: Expected at most 3 items."
+        );
+        assert!(arg.handle(&mut InputCursor::new(&input[..2])).is_ok());
+        assert_eq!(arg.values(), &vec![PathBuf::from("a"), PathBuf::from("b")]);
+    }
+
+    #[test]
+    fn map_works() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .set_description("Size in kilobytes")
+            .map(|v| v * 1024);
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("2")]))
+            .is_ok());
+        assert_eq!(arg.first_value().unwrap(), &2048);
+        assert_eq!(arg.description().unwrap(), "Size in kilobytes");
+        let arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .set_default(4)
+            .map(|v| v * 1024);
+        assert_eq!(arg.first_value(), Some(&4096));
+        assert_eq!(arg.default_value(), Some("4"));
+    }
+
+    #[test]
+    fn map_keeps_finalizer_set_before() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .set_finalizer(|values| {
+                values.sort();
+                Result::Ok(())
+            })
+            .map(|v| v.to_string());
+        for value in ["3", "1", "2"] {
+            arg.handle(&mut InputCursor::new(&[String::from(value)]))
+                .unwrap();
+        }
+        arg.finalize().unwrap();
+        assert_eq!(arg.values(), &vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn map_keeps_handlers_reading_values() {
+        let handle = |arg: &mut ParsableValueArgument<String>, input: &[&str]| {
+            let input: Vec<String> = input.iter().map(|v| String::from(*v)).collect();
+            arg.handle(&mut InputCursor::new(&input))
+        };
+        let mut arg = ParsableValueArgument::new_string(super::ArgumentIdentification::Short('o'))
+            .single(DuplicatePolicy::Error)
+            .map(|v| v.to_uppercase());
+        assert!(handle(&mut arg, &["a"]).is_ok());
+        assert_eq!(
+            handle(&mut arg, &["b"]),
+            Err(String::from("error in -o: Value already assigned"))
+        );
+        assert_eq!(arg.values(), &vec!["A"]);
+
+        let mut arg =
+            ParsableValueArgument::new_integer_sum(super::ArgumentIdentification::Short('w'))
+                .map(|v| v * 2);
+        for value in ["3", "4"] {
+            arg.handle(&mut InputCursor::new(&[String::from(value)]))
+                .unwrap();
+        }
+        assert_eq!(arg.values(), &vec![14]);
+
+        let mut arg = ParsableValueArgument::new_fold(
+            super::ArgumentIdentification::Short('p'),
+            String::new(),
+            |path, value| Result::Ok(format!("{}/{}", path, value)),
+        )
+        .map(|path| path.len());
+        for value in ["usr", "lib"] {
+            arg.handle(&mut InputCursor::new(&[String::from(value)]))
+                .unwrap();
+        }
+        assert_eq!(arg.values(), &vec![8]);
+    }
+
+    #[test]
+    fn consuming_accessors_work() {
+        struct Token(String);
+        let handler = |input_iter: &mut InputCursor<'_>, values: &mut Vec<Token>| {
+            values.extend(input_iter.next().map(|v| Token(v.clone())));
+            Result::Ok(())
+        };
+        let mut arg =
+            ParsableValueArgument::new(super::ArgumentIdentification::Short('t'), handler);
+        arg.handle(&mut InputCursor::new(&[String::from("a")]))
+            .unwrap();
+        arg.handle(&mut InputCursor::new(&[String::from("b")]))
+            .unwrap();
+        let tokens: Vec<String> = arg.take_values().into_iter().map(|t| t.0).collect();
+        assert_eq!(tokens, vec!["a", "b"]);
+        let arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('n'))
+            .set_default(3);
+        assert_eq!(arg.into_first(), Some(3));
+        let arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('n'));
+        assert!(arg.take_values().is_empty());
+    }
+
+    #[test]
+    fn single_works() {
+        let identification = super::ArgumentIdentification::Short('n');
+        let input = [String::from("1"), String::from("2"), String::from("x")];
+        let mut arg = ParsableValueArgument::new_integer(identification.clone())
+            .single(DuplicatePolicy::Error);
+        assert_eq!(arg.arg_type(), super::ArgType::Value);
+        let mut cursor = InputCursor::new(&input);
+        arg.handle(&mut cursor).unwrap();
+        assert_eq!(
+            arg.handle(&mut cursor),
+            Err(String::from("error in -n: Value already assigned"))
+        );
+        assert_eq!(cursor.consumed(), 1);
+        let mut arg = ParsableValueArgument::new_integer(identification.clone())
+            .single(DuplicatePolicy::First);
+        let mut cursor = InputCursor::new(&input);
+        arg.handle(&mut cursor).unwrap();
+        arg.handle(&mut cursor).unwrap();
+        assert!(arg.handle(&mut cursor).is_err());
+        assert_eq!(arg.values(), &vec![1]);
+        let mut arg =
+            ParsableValueArgument::new_integer(identification).single(DuplicatePolicy::Last);
+        let mut cursor = InputCursor::new(&input);
+        arg.handle(&mut cursor).unwrap();
+        arg.handle(&mut cursor).unwrap();
+        assert!(arg.handle(&mut cursor).is_err());
+        assert_eq!(arg.values(), &vec![2]);
+    }
+
+    #[test]
+    fn optional_value_works() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Long(
+            String::from("jobs"),
+        ))
+        .optional_value(0);
+        assert!(arg.has_optional_value());
+        let input = [String::from("4"), String::from("-v")];
+        let mut cursor = InputCursor::new(&input);
+        arg.handle(&mut cursor).unwrap();
+        arg.handle(&mut cursor).unwrap();
+        assert_eq!(cursor.remaining(), &input[1..]);
+        arg.handle(&mut InputCursor::new(&[])).unwrap();
+        assert_eq!(arg.values(), &vec![4, 0, 0]);
+        let mut args_list = crate::ArgumentList::new();
+        args_list.register_parsable(&mut arg);
+        args_list
+            .parse_args(vec![String::from("--jobs=-1"), String::from("--jobs")])
+            .unwrap();
+        drop(args_list);
+        assert_eq!(arg.values(), &vec![4, 0, 0, -1, 0]);
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("x")]))
+            .is_err());
+        let mut arg = ParsableValueArgument::new_string(super::ArgumentIdentification::Long(
+            String::from("input"),
+        ))
+        .optional_value(String::from("default"));
+        let input = [String::from("-"), String::from("-v")];
+        let mut cursor = InputCursor::new(&input);
+        arg.handle(&mut cursor).unwrap();
+        arg.handle(&mut cursor).unwrap();
+        assert_eq!(
+            arg.values(),
+            &vec![String::from("-"), String::from("default")]
+        );
+    }
+
+    #[test]
+    fn grouped_values_work() {
+        let mut arg = ParsableValueArgument::new_integer_list(
+            super::ArgumentIdentification::Short('p'),
+            ListOptions::new(),
+        );
+        arg.handle(&mut InputCursor::new(&[
+            String::from("1"),
+            String::from("2"),
+        ]))
+        .unwrap();
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("x")]))
+            .is_err());
+        arg.handle(&mut InputCursor::new(&[String::from("3")]))
+            .unwrap();
+        assert_eq!(arg.grouped_values(), vec![&[1, 2][..], &[3][..]]);
+        assert_eq!(arg.take_grouped_values(), vec![vec![1, 2], vec![3]]);
+        let mut arg =
+            ParsableValueArgument::new_integer_sum(super::ArgumentIdentification::Short('w'));
+        arg.handle(&mut InputCursor::new(&[String::from("3")]))
+            .unwrap();
+        arg.handle(&mut InputCursor::new(&[String::from("4")]))
+            .unwrap();
+        assert_eq!(arg.grouped_values(), vec![&[7][..]]);
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('n'))
+            .single(DuplicatePolicy::First);
+        arg.handle(&mut InputCursor::new(&[String::from("1")]))
+            .unwrap();
+        arg.handle(&mut InputCursor::new(&[String::from("2")]))
+            .unwrap();
+        assert_eq!(arg.grouped_values(), vec![&[1][..]]);
+        assert!(
+            ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('n'))
+                .grouped_values()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn handler_errors_have_context() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Long(
+            String::from("level"),
+        ));
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("high")])),
+            Err(String::from(
+                "error in --level: 'high': Input is not a number"
+            ))
+        );
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[])),
+            Err(String::from("error in --level: No remaining input values."))
+        );
+        let mut arg = arg.set_secret(true);
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("hunter2")])),
+            Err(String::from("error in --level: Input is not a number"))
+        );
+        // Value accepted before the rejected one is not reported.
+        let mut arg =
+            ParsableValueArgument::<(u8, u8)>::new_of(super::ArgumentIdentification::Short('p'));
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[
+                String::from("x"),
+                String::from("1")
+            ])),
+            Err(String::from(
+                "error in -p: 'x': Input is not a non-negative number"
+            ))
+        );
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[
+                String::from("1"),
+                String::from("x")
+            ])),
+            Err(String::from(
+                "error in -p: 'x': Input is not a non-negative number"
+            ))
+        );
+        let mut arg = ParsableValueArgument::new(
+            super::ArgumentIdentification::Short('c'),
+            |input_iter: &mut InputCursor<'_>, _: &mut Vec<String>| {
+                input_iter.next();
+                Result::Err(String::from("Server is not reachable"))
+            },
+        );
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("host")])),
+            Err(String::from("error in -c: Server is not reachable"))
+        );
+    }
+
+    #[test]
+    fn and_then_works() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .and_then(|v| {
+                if v < 0 {
+                    return Result::Err(String::from("Value cannot be negative"));
+                }
+                Result::Ok(v as u64)
+            });
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("5")]))
+            .is_ok());
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("-5")]))
+                .unwrap_err(),
+            "error in -i: '-5': Value cannot be negative"
+        );
+        assert_eq!(arg.values(), &vec![5u64]);
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .set_default(-1)
+            .and_then(|v| {
+                if v < 0 {
+                    return Result::Err(String::from("Value cannot be negative"));
+                }
+                Result::Ok(v as u64)
+            });
+        assert_eq!(arg.first_value(), None);
+        assert_eq!(
+            arg.take_warnings(),
+            vec![String::from(
+                "Default value -1 was rejected: Value cannot be negative"
+            )]
+        );
+    }
+
+    #[test]
+    fn set_default_works() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .set_default(10);
+        assert_eq!(arg.first_value().unwrap(), &10);
+        assert!(arg.values().is_empty());
+        assert_eq!(arg.default_value().unwrap(), "10");
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("1")]))
+            .is_ok());
+        assert_eq!(arg.first_value().unwrap(), &1);
+    }
+
+    #[test]
+    fn set_description_works() {
+        let arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'));
+        assert!(arg.description().is_none());
+        let arg = arg.set_description("Number of iterations");
+        assert_eq!(arg.description().unwrap(), "Number of iterations");
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn set_parallel_validator_reports_first_error() {
+        let mut arg = ParsableValueArgument::new_integer(super::ArgumentIdentification::Short('i'))
+            .set_parallel_validator(|v| {
+                if *v < 0 {
+                    Result::Err(format!("{} is negative", v))
+                } else {
+                    Result::Ok(())
+                }
+            });
+        for v in &["1", "-2", "3", "-4"] {
+            arg.handle(&mut InputCursor::new(&[String::from(*v)]))
+                .unwrap();
+        }
+        assert_eq!(arg.finalize().unwrap_err(), "-2 is negative");
+    }
+
+    #[test]
+    fn new_choice_works() {
+        let mut arg = ParsableValueArgument::new_choice(
+            super::ArgumentIdentification::Short('f'),
+            &["text", "json"],
+        );
+        assert_eq!(arg.choices(), vec!["text", "json"]);
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("json")]))
+            .is_ok());
+        assert_eq!(
+            arg.handle(&mut InputCursor::new(&[String::from("jsn")]))
+                .unwrap_err(),
+            "error in -f: 'jsn' is not valid; did you mean 'json'?"
+        );
+        assert_eq!(arg.values(), &vec![String::from("json")]);
+    }
+
+    #[test]
+    fn toggleable_flag_works() {
+        let empty: Vec<String> = Vec::new();
+        let mut flag = ParsableValueArgument::new_flag(super::ArgumentIdentification::Short('f'));
+        let mut toggle =
+            ParsableValueArgument::new_flag(super::ArgumentIdentification::Short('t')).toggleable();
+        assert!(!flag.is_set());
+        for count in 1..=3 {
+            flag.handle(&mut InputCursor::new(&empty)).unwrap();
+            toggle.handle(&mut InputCursor::new(&empty)).unwrap();
+            assert!(flag.is_set());
+            assert_eq!(toggle.is_set(), count % 2 == 1);
+        }
+        assert_eq!(flag.arg_type(), super::ArgType::Flag);
+    }
 }
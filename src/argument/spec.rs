@@ -0,0 +1,305 @@
+use super::{
+    legacy_argument::{ArgType, Argument},
+    parsable_argument::HandleableArgument,
+    ArgumentIdentification,
+};
+
+///
+/// Read-only description of a registered argument. Shared by legacy and parsable arguments so
+/// external tooling (documentation generators, GUI builders) can enumerate the whole CLI.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+/// };
+///
+/// let mut argument_int = ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+///     .set_description("Number of iterations")
+///     .set_default(1);
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_parsable(&mut argument_int);
+/// for spec in args_list.iter_specs() {
+///     println!("{} - {}", spec.identification(), spec.description().unwrap_or(""));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgSpec {
+    identification: ArgumentIdentification,
+    arg_type: ArgType,
+    required: bool,
+    description: Option<String>,
+    localized_descriptions: Vec<(String, String)>,
+    default: Option<String>,
+    choices: Vec<String>,
+    examples: Vec<String>,
+    value_names: Vec<String>,
+    optional_value: bool,
+    tags: Vec<String>,
+}
+
+impl ArgSpec {
+    pub(crate) fn new(
+        identification: ArgumentIdentification,
+        arg_type: ArgType,
+        required: bool,
+        description: Option<&str>,
+        default: Option<&str>,
+        choices: Vec<String>,
+    ) -> ArgSpec {
+        ArgSpec {
+            identification,
+            arg_type,
+            required,
+            description: description.map(String::from),
+            localized_descriptions: Vec::new(),
+            default: default.map(String::from),
+            choices,
+            examples: Vec::new(),
+            value_names: Vec::new(),
+            optional_value: false,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Sets name of value displayed in usage.
+    pub(crate) fn with_value_name(mut self, value_name: Option<&str>) -> ArgSpec {
+        self.value_names = value_name.into_iter().map(String::from).collect();
+        self
+    }
+
+    /// Sets names of every value displayed in usage.
+    pub(crate) fn with_value_names(mut self, value_names: Vec<&str>) -> ArgSpec {
+        self.value_names = value_names.into_iter().map(String::from).collect();
+        self
+    }
+
+    /// Sets descriptions in other languages.
+    pub(crate) fn with_localized_descriptions(
+        mut self,
+        localized_descriptions: Vec<(String, String)>,
+    ) -> ArgSpec {
+        self.localized_descriptions = localized_descriptions;
+        self
+    }
+
+    /// Marks value of the argument as optional.
+    pub(crate) fn with_optional_value(mut self, optional_value: bool) -> ArgSpec {
+        self.optional_value = optional_value;
+        self
+    }
+
+    /// Sets tags of the argument.
+    pub(crate) fn with_tags(mut self, tags: Vec<String>) -> ArgSpec {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets examples of using the argument.
+    pub(crate) fn with_examples(mut self, examples: Vec<String>) -> ArgSpec {
+        self.examples = examples;
+        self
+    }
+
+    pub(crate) fn of_legacy(argument: &Argument) -> ArgSpec {
+        ArgSpec::new(
+            argument.identification(),
+            *argument.arg_type(),
+            false,
+            None,
+            None,
+            Vec::new(),
+        )
+    }
+
+    pub(crate) fn of_parsable<'a, A>(argument: &A) -> ArgSpec
+    where
+        A: HandleableArgument<'a> + ?Sized,
+    {
+        ArgSpec::new(
+            argument.identification().clone(),
+            argument.arg_type(),
+            argument.is_required(),
+            argument.description(),
+            argument.default_value(),
+            argument.choices(),
+        )
+        .with_examples(argument.examples())
+        .with_value_names(argument.value_names())
+        .with_optional_value(argument.has_optional_value())
+        .with_tags(argument.tags())
+        .with_localized_descriptions(argument.localized_descriptions())
+    }
+
+    pub fn identification(&self) -> &ArgumentIdentification {
+        &self.identification
+    }
+
+    pub fn arg_type(&self) -> &ArgType {
+        &self.arg_type
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Description in given language. Tags are compared ignoring case, with `_` accepted as
+    /// separator. Description for the language without region (`pt` for `pt-BR`) is used when
+    /// there is no exact match.
+    pub fn localized_description(&self, language: &str) -> Option<&str> {
+        let language = language.replace('_', "-");
+        let base = language.split('-').next().unwrap_or(&language);
+        let find = |tag: &str| {
+            self.localized_descriptions
+                .iter()
+                .find(|(l, _)| l.replace('_', "-").eq_ignore_ascii_case(tag))
+                .map(|(_, description)| description.as_str())
+        };
+        find(&language).or_else(|| find(base))
+    }
+
+    /// Description in given language, falling back to the default description.
+    pub(crate) fn description_for(&self, language: Option<&str>) -> Option<&str> {
+        language
+            .and_then(|language| self.localized_description(language))
+            .or(self.description())
+    }
+
+    /// Default value formatted for display.
+    pub fn default_value(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// All accepted values, empty when any value is accepted.
+    pub fn choices(&self) -> &Vec<String> {
+        &self.choices
+    }
+
+    /// Name of value displayed in usage, `VALUE` unless set by the argument. First name for
+    /// arguments reading more than one value.
+    pub fn value_name(&self) -> &str {
+        self.value_names.first().map_or("VALUE", String::as_str)
+    }
+
+    /// Names of every value of an occurrence, e.g. `["INT", "INT"]` for pairs.
+    pub fn value_names(&self) -> Vec<&str> {
+        if self.value_names.is_empty() {
+            vec!["VALUE"]
+        } else {
+            self.value_names.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Example command lines using the argument, e.g. `-p ./config.toml`.
+    pub fn examples(&self) -> &Vec<String> {
+        &self.examples
+    }
+
+    /// Tags of the argument, e.g. `experimental`.
+    pub fn tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    /// Check if argument can be given without a value.
+    pub fn has_optional_value(&self) -> bool {
+        self.optional_value
+    }
+
+    /// Check if the argument has any of given tags.
+    pub(crate) fn has_any_tag(&self, tags: &[String]) -> bool {
+        self.tags.iter().any(|tag| tags.contains(tag))
+    }
+
+    /// Names of the argument followed by value placeholder, e.g. `-p, --path <PATH>`. Optional
+    /// value is enclosed in brackets (`--color [<WHEN>]`).
+    pub(crate) fn synopsis(&self) -> String {
+        let mut synopsis = format!("{}", self.identification);
+        if let ArgType::Value | ArgType::ValueList = self.arg_type {
+            let placeholder = if self.choices.is_empty() {
+                self.value_names()
+                    .iter()
+                    .map(|name| format!("<{}>", name))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            } else {
+                format!("<{}>", self.choices.join("|"))
+            };
+            if self.optional_value {
+                synopsis.push_str(&format!(" [{}]", placeholder));
+            } else {
+                synopsis.push_str(&format!(" {}", placeholder));
+            }
+        }
+        synopsis
+    }
+
+    /// Single line describing how the argument is used, e.g.
+    /// `--level <INT>  Verbosity (default: 1)`. Choices replace the value name placeholder.
+    pub fn usage(&self) -> String {
+        let mut usage = self.synopsis();
+        if let Some(description) = &self.description {
+            usage.push_str(&format!("  {}", description));
+        }
+        if let Some(default) = &self.default {
+            usage.push_str(&format!(" (default: {})", default));
+        }
+        usage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ArgSpec;
+    use crate::argument::{legacy_argument::ArgType, ArgumentIdentification};
+
+    #[test]
+    fn usage_works() {
+        let spec = ArgSpec::new(
+            ArgumentIdentification::Long(String::from("level")),
+            ArgType::Value,
+            false,
+            Some("Verbosity"),
+            Some("1"),
+            Vec::new(),
+        );
+        assert_eq!(spec.usage(), "--level <VALUE>  Verbosity (default: 1)");
+        let spec = ArgSpec::new(
+            ArgumentIdentification::Short('c'),
+            ArgType::Value,
+            false,
+            None,
+            None,
+            vec![String::from("auto"), String::from("never")],
+        );
+        assert_eq!(spec.usage(), "-c <auto|never>");
+    }
+
+    #[test]
+    fn localized_description_works() {
+        let spec = ArgSpec::new(
+            ArgumentIdentification::Short('o'),
+            ArgType::Value,
+            false,
+            Some("Output"),
+            None,
+            Vec::new(),
+        )
+        .with_localized_descriptions(vec![
+            (String::from("pt"), String::from("Saída")),
+            (String::from("pt-BR"), String::from("Arquivo de saída")),
+        ]);
+        assert_eq!(
+            spec.localized_description("pt_br"),
+            Some("Arquivo de saída")
+        );
+        assert_eq!(spec.localized_description("pt-PT"), Some("Saída"));
+        assert_eq!(spec.localized_description("en"), None);
+        assert_eq!(spec.description_for(Some("en")), Some("Output"));
+        assert_eq!(spec.description_for(None), Some("Output"));
+    }
+}
@@ -0,0 +1,359 @@
+use super::{
+    legacy_argument::ArgType,
+    parsable_argument::{DuplicatePolicy, ParsableValueArgument},
+    spec::ArgSpec,
+    ArgumentIdentification,
+};
+use crate::ArgumentList;
+
+/// Check if bytes of two strings are equal. Usable in constant context.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Panics when short name cannot be typed as an argument.
+const fn check_short(name: char) {
+    if name == '-' || name == '=' || name.is_ascii_whitespace() {
+        panic!("Short name must not be '-', '=' or whitespace.");
+    }
+}
+
+/// Panics when long name cannot be typed as an argument.
+const fn check_long(name: &str) {
+    let bytes = name.as_bytes();
+    if bytes.is_empty() {
+        panic!("Long name must not be empty.");
+    }
+    if bytes[0] == b'-' {
+        panic!("Long name must be given without `--` prefix.");
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' || bytes[i].is_ascii_whitespace() {
+            panic!("Long name must not contain '=' or whitespace.");
+        }
+        i += 1;
+    }
+}
+
+///
+/// Identification which can be created in constant context. Names are validated by the
+/// constructors, so invalid names used in a `const` or `static` fail compilation.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::argument::{table::StaticIdentification, ArgumentIdentification};
+///
+/// const PATH: StaticIdentification = StaticIdentification::both('p', "path");
+/// assert_eq!(
+///     ArgumentIdentification::from(PATH),
+///     ArgumentIdentification::Both('p', String::from("path"))
+/// );
+/// ```
+///
+/// ```compile_fail
+/// use trivial_argument_parser::argument::table::StaticIdentification;
+///
+/// const PATH: StaticIdentification = StaticIdentification::long("--path");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaticIdentification {
+    short: Option<char>,
+    long: Option<&'static str>,
+}
+
+impl StaticIdentification {
+    pub const fn short(name: char) -> StaticIdentification {
+        check_short(name);
+        StaticIdentification {
+            short: Some(name),
+            long: None,
+        }
+    }
+
+    pub const fn long(name: &'static str) -> StaticIdentification {
+        check_long(name);
+        StaticIdentification {
+            short: None,
+            long: Some(name),
+        }
+    }
+
+    pub const fn both(short: char, long: &'static str) -> StaticIdentification {
+        check_short(short);
+        check_long(long);
+        StaticIdentification {
+            short: Some(short),
+            long: Some(long),
+        }
+    }
+
+    /// Check if both identifications share at least one name. Usable in constant context.
+    pub const fn overlaps(&self, other: &StaticIdentification) -> bool {
+        if let (Some(a), Some(b)) = (self.short, other.short) {
+            if a == b {
+                return true;
+            }
+        }
+        if let (Some(a), Some(b)) = (self.long, other.long) {
+            if str_eq(a, b) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl From<StaticIdentification> for ArgumentIdentification {
+    fn from(identification: StaticIdentification) -> Self {
+        match (identification.short, identification.long) {
+            (Some(short), Some(long)) => ArgumentIdentification::Both(short, String::from(long)),
+            (None, Some(long)) => ArgumentIdentification::Long(String::from(long)),
+            // Constructors always set at least one name.
+            (short, None) => ArgumentIdentification::Short(short.unwrap_or_default()),
+        }
+    }
+}
+
+///
+/// Definition of argument which can be created in constant context, so large CLIs can keep
+/// their arguments in `static` tables without runtime construction. Tables are registered with
+/// ArgumentList::register_static and values are read from ParsedArgs.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::table::{check_table, StaticArgSpec, StaticIdentification},
+/// };
+///
+/// static ARGUMENTS: &[StaticArgSpec] = &[
+///     StaticArgSpec::flag(StaticIdentification::both('v', "verbose"))
+///         .set_description("Print more details"),
+///     StaticArgSpec::value(StaticIdentification::long("output"))
+///         .set_value_name("PATH")
+///         .set_required(true),
+/// ];
+/// // Fails compilation when names are used by more than one argument.
+/// const _: () = check_table(ARGUMENTS);
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_static(ARGUMENTS);
+/// args_list
+///     .parse_args(vec![String::from("-v"), String::from("--output"), String::from("out")])
+///     .unwrap();
+/// assert_eq!(args_list.flag_count('v'), 1);
+/// assert_eq!(args_list.value_of("output"), Some("out"));
+/// assert_eq!(
+///     args_list.help_message(),
+///     "Options:\n  -v, --verbose    Print more details\n  --output <PATH>\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaticArgSpec {
+    identification: StaticIdentification,
+    arg_type: ArgType,
+    required: bool,
+    description: Option<&'static str>,
+    value_name: Option<&'static str>,
+}
+
+impl StaticArgSpec {
+    const fn new(identification: StaticIdentification, arg_type: ArgType) -> StaticArgSpec {
+        StaticArgSpec {
+            identification,
+            arg_type,
+            required: false,
+            description: None,
+            value_name: None,
+        }
+    }
+
+    /// Argument which does not take any value.
+    pub const fn flag(identification: StaticIdentification) -> StaticArgSpec {
+        StaticArgSpec::new(identification, ArgType::Flag)
+    }
+
+    /// Argument taking a single value.
+    pub const fn value(identification: StaticIdentification) -> StaticArgSpec {
+        StaticArgSpec::new(identification, ArgType::Value)
+    }
+
+    /// Argument taking a value every time it is given.
+    pub const fn value_list(identification: StaticIdentification) -> StaticArgSpec {
+        StaticArgSpec::new(identification, ArgType::ValueList)
+    }
+
+    pub const fn set_required(self, required: bool) -> StaticArgSpec {
+        StaticArgSpec { required, ..self }
+    }
+
+    pub const fn set_description(self, description: &'static str) -> StaticArgSpec {
+        StaticArgSpec {
+            description: Some(description),
+            ..self
+        }
+    }
+
+    pub const fn set_value_name(self, value_name: &'static str) -> StaticArgSpec {
+        StaticArgSpec {
+            value_name: Some(value_name),
+            ..self
+        }
+    }
+
+    pub const fn identification(&self) -> &StaticIdentification {
+        &self.identification
+    }
+
+    /// Applies properties of the spec other than names and type to the argument.
+    fn configure<V>(&self, argument: ParsableValueArgument<V>) -> ParsableValueArgument<V> {
+        let argument = argument.set_required(self.required);
+        let argument = match self.description {
+            Some(description) => argument.set_description(description),
+            None => argument,
+        };
+        match self.value_name {
+            Some(value_name) => argument.set_value_name(value_name),
+            None => argument,
+        }
+    }
+
+    /// Runtime description of the argument, like ones returned by ArgumentList::iter_specs.
+    pub fn to_spec(&self) -> ArgSpec {
+        ArgSpec::new(
+            self.identification.into(),
+            self.arg_type,
+            self.required,
+            self.description,
+            None,
+            Vec::new(),
+        )
+        .with_value_name(self.value_name)
+    }
+}
+
+/// Panics when names are used by more than one argument of the table. Meant to be evaluated in
+/// a constant (`const _: () = check_table(TABLE);`), so mistakes fail compilation.
+pub const fn check_table(specs: &[StaticArgSpec]) {
+    let mut i = 0;
+    while i < specs.len() {
+        let mut j = i + 1;
+        while j < specs.len() {
+            if specs[i].identification.overlaps(&specs[j].identification) {
+                panic!("Argument name is used by more than one argument.");
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Registers arguments defined in a static table. Values are recorded in ParsedArgs and can
+    /// be read with value_of, values_of and flag_count.
+    pub fn register_static(&mut self, specs: &'static [StaticArgSpec]) {
+        for spec in specs {
+            let identification = spec.identification.into();
+            match spec.arg_type {
+                ArgType::Flag => {
+                    let argument = ParsableValueArgument::new_flag(identification);
+                    self.register_parsable_boxed(Box::new(spec.configure(argument)));
+                }
+                // Like legacy value arguments, a single value can be given only once.
+                ArgType::Value => {
+                    let argument = ParsableValueArgument::new_string(identification)
+                        .single(DuplicatePolicy::Error);
+                    self.register_parsable_boxed(Box::new(spec.configure(argument)));
+                }
+                ArgType::ValueList => {
+                    let argument = ParsableValueArgument::new_string(identification)
+                        .with_arg_type(ArgType::ValueList);
+                    self.register_parsable_boxed(Box::new(spec.configure(argument)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_table, StaticArgSpec, StaticIdentification};
+    use crate::{
+        argument::{legacy_argument::ArgType, ArgumentIdentification},
+        error::ParseError,
+        ArgumentList,
+    };
+
+    static TABLE: &[StaticArgSpec] = &[
+        StaticArgSpec::flag(StaticIdentification::short('q')),
+        StaticArgSpec::value_list(StaticIdentification::both('I', "include"))
+            .set_description("Include directory"),
+        StaticArgSpec::value(StaticIdentification::long("name")).set_required(true),
+    ];
+    const _: () = check_table(TABLE);
+
+    #[test]
+    fn register_static_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.register_static(TABLE);
+        let specs: Vec<_> = args_list.iter_specs().collect();
+        let expected: Vec<_> = TABLE.iter().map(StaticArgSpec::to_spec).collect();
+        assert_eq!(specs, expected);
+        assert_eq!(specs[0].arg_type(), &ArgType::Flag);
+        assert_eq!(
+            specs[1].identification(),
+            &ArgumentIdentification::Both('I', String::from("include"))
+        );
+        assert_eq!(
+            args_list.parse_args(vec![String::from("-q")]),
+            Err(ParseError::MissingArgument(String::from("--name")))
+        );
+        let input = ["-I", "a", "--name", "x", "--include=b"];
+        let mut args_list = ArgumentList::new();
+        args_list.register_static(TABLE);
+        args_list
+            .parse_args(input.iter().map(|s| String::from(*s)).collect())
+            .unwrap();
+        assert_eq!(args_list.values_of("include"), vec!["a", "b"]);
+        assert_eq!(args_list.value_of("name"), Some("x"));
+        assert_eq!(args_list.flag_count('q'), 0);
+    }
+
+    #[test]
+    fn register_static_rejects_repeated_value() {
+        let mut args_list = ArgumentList::new();
+        args_list.register_static(TABLE);
+        let input = ["--name", "x", "--name", "y"];
+        assert_eq!(
+            args_list.parse_args(input.iter().map(|s| String::from(*s)).collect()),
+            Err(ParseError::InvalidValue {
+                argument: String::from("--name"),
+                message: String::from("error in --name: Value already assigned"),
+                usage: String::from("--name <VALUE>"),
+                value: None,
+                suggestion: None,
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Argument name is used by more than one argument.")]
+    fn check_table_panics() {
+        check_table(&[
+            StaticArgSpec::flag(StaticIdentification::both('v', "verbose")),
+            StaticArgSpec::flag(StaticIdentification::short('v')),
+        ]);
+    }
+}
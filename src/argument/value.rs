@@ -0,0 +1,308 @@
+use std::{convert::TryFrom, error::Error, ffi::OsString, fmt, path::PathBuf};
+
+use super::parsable_argument::ParsableValueArgument;
+use crate::cursor::InputCursor;
+
+/// Reason why ArgValue could not be decoded from input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueError {
+    /// Input ended before all values were read.
+    Missing,
+    /// Value was read, but could not be decoded.
+    Invalid { value: String, message: String },
+}
+
+impl ValueError {
+    pub fn invalid(value: &str, message: &str) -> ValueError {
+        ValueError::Invalid {
+            value: String::from(value),
+            message: String::from(message),
+        }
+    }
+
+    /// Input value which could not be decoded. None when input ended.
+    pub fn value(&self) -> Option<&str> {
+        match self {
+            ValueError::Missing => None,
+            ValueError::Invalid { value, .. } => Some(value),
+        }
+    }
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueError::Missing => write!(f, "No remaining input values."),
+            ValueError::Invalid { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for ValueError {}
+
+///
+/// Type which can be decoded from input values, used by ParsableValueArgument::new_of. Unlike
+/// FromStr decoding reads values from InputCursor, so a single value can consume more than one
+/// input value (e.g. tuples), and errors tell which input value was rejected.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::{
+///         parsable_argument::ParsableValueArgument,
+///         value::{ArgValue, ValueError},
+///         ArgumentIdentification,
+///     },
+///     cursor::InputCursor,
+/// };
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Size(u64, u64);
+///
+/// impl ArgValue for Size {
+///     fn value_name() -> Option<&'static str> {
+///         Some("WxH")
+///     }
+///
+///     fn decode(input: &mut InputCursor<'_>) -> Result<Size, ValueError> {
+///         let value = input.next().ok_or(ValueError::Missing)?;
+///         let invalid = || ValueError::invalid(value, "Size must be given as WxH");
+///         let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+///         Ok(Size(
+///             width.parse().map_err(|_| invalid())?,
+///             height.parse().map_err(|_| invalid())?,
+///         ))
+///     }
+/// }
+///
+/// let mut argument_size = ParsableValueArgument::<Size>::new_of(ArgumentIdentification::Short('s'));
+/// let mut argument_point =
+///     ParsableValueArgument::<(i64, i64)>::new_of(ArgumentIdentification::Short('p'));
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_parsable(&mut argument_size);
+/// args_list.register_parsable(&mut argument_point);
+/// let input = vec!["-s", "640x480", "-p", "3", "-4"];
+/// args_list
+///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+///     .unwrap();
+/// assert_eq!(args_list.help_message(), "Options:\n  -s <WxH>\n  -p <INT> <INT>\n");
+/// drop(args_list);
+/// assert_eq!(argument_size.first_value(), Some(&Size(640, 480)));
+/// assert_eq!(argument_point.first_value(), Some(&(3, -4)));
+/// ```
+pub trait ArgValue: Sized {
+    /// Name of value displayed in usage (e.g. `INT`). None displays the default name.
+    fn value_name() -> Option<&'static str> {
+        None
+    }
+
+    /// Names of every input value read by decode, displayed as separate placeholders (e.g.
+    /// `<INT> <INT>`). Contains only value_name by default.
+    fn value_names() -> Vec<&'static str> {
+        vec![Self::value_name().unwrap_or("VALUE")]
+    }
+
+    /// Reads value from input. Values consumed before an error are rolled back by the parser.
+    fn decode(input: &mut InputCursor<'_>) -> Result<Self, ValueError>;
+}
+
+/// Reads a single value and decodes it with `parse`.
+fn decode_with<V, F>(input: &mut InputCursor<'_>, parse: F) -> Result<V, ValueError>
+where
+    F: FnOnce(&str) -> Result<V, String>,
+{
+    let value = input.next().ok_or(ValueError::Missing)?;
+    parse(value).map_err(|message| ValueError::invalid(value, &message))
+}
+
+impl ArgValue for String {
+    fn decode(input: &mut InputCursor<'_>) -> Result<String, ValueError> {
+        decode_with(input, |v| Ok(String::from(v)))
+    }
+}
+
+impl ArgValue for OsString {
+    fn decode(input: &mut InputCursor<'_>) -> Result<OsString, ValueError> {
+        decode_with(input, |v| Ok(OsString::from(v)))
+    }
+}
+
+impl ArgValue for PathBuf {
+    fn value_name() -> Option<&'static str> {
+        Some("PATH")
+    }
+
+    fn decode(input: &mut InputCursor<'_>) -> Result<PathBuf, ValueError> {
+        decode_with(input, |v| Ok(PathBuf::from(v)))
+    }
+}
+
+impl ArgValue for char {
+    fn value_name() -> Option<&'static str> {
+        Some("CHAR")
+    }
+
+    fn decode(input: &mut InputCursor<'_>) -> Result<char, ValueError> {
+        decode_with(input, |v| {
+            let mut chars = v.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(format!("Expected a single character, got '{}'", v)),
+            }
+        })
+    }
+}
+
+impl ArgValue for bool {
+    fn value_name() -> Option<&'static str> {
+        Some("BOOL")
+    }
+
+    fn decode(input: &mut InputCursor<'_>) -> Result<bool, ValueError> {
+        decode_with(input, |v| {
+            v.parse()
+                .map_err(|_| format!("Expected true or false, got '{}'", v))
+        })
+    }
+}
+
+/// Implements ArgValue for floating point numbers.
+macro_rules! float_arg_value {
+    ($($t:ty),*) => {
+        $(
+            impl ArgValue for $t {
+                fn value_name() -> Option<&'static str> {
+                    Some("NUMBER")
+                }
+
+                fn decode(input: &mut InputCursor<'_>) -> Result<$t, ValueError> {
+                    decode_with(input, |v| {
+                        v.parse().map_err(|_| format!("'{}' is not a number", v))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+float_arg_value!(f32, f64);
+
+impl ArgValue for i64 {
+    fn value_name() -> Option<&'static str> {
+        Some("INT")
+    }
+
+    fn decode(input: &mut InputCursor<'_>) -> Result<i64, ValueError> {
+        decode_with(input, ParsableValueArgument::parse_integer)
+    }
+}
+
+/// Implements ArgValue for unsigned integers, rejecting values which do not fit.
+macro_rules! unsigned_arg_value {
+    ($($t:ty),*) => {
+        $(
+            impl ArgValue for $t {
+                fn value_name() -> Option<&'static str> {
+                    Some("INT")
+                }
+
+                fn decode(input: &mut InputCursor<'_>) -> Result<$t, ValueError> {
+                    decode_with(input, |v| {
+                        ParsableValueArgument::<$t>::parse_unsigned(v, <$t>::MAX as u64)
+                    })
+                }
+            }
+        )*
+    };
+}
+
+unsigned_arg_value!(u8, u16, u32, u64, usize);
+
+/// Implements ArgValue for signed integers narrower than i64, rejecting values which do not fit.
+macro_rules! signed_arg_value {
+    ($($t:ty),*) => {
+        $(
+            impl ArgValue for $t {
+                fn value_name() -> Option<&'static str> {
+                    Some("INT")
+                }
+
+                fn decode(input: &mut InputCursor<'_>) -> Result<$t, ValueError> {
+                    decode_with(input, |v| {
+                        let value = ParsableValueArgument::parse_integer(v)?;
+                        <$t>::try_from(value).map_err(|_| {
+                            format!("Value {} is not between {} and {}", v, <$t>::MIN, <$t>::MAX)
+                        })
+                    })
+                }
+            }
+        )*
+    };
+}
+
+signed_arg_value!(i8, i16, i32, isize);
+
+/// Pair of values given one after another (`--point 3 4`). Both values are named in usage.
+impl<A: ArgValue, B: ArgValue> ArgValue for (A, B) {
+    fn value_names() -> Vec<&'static str> {
+        let mut names = A::value_names();
+        names.extend(B::value_names());
+        names
+    }
+
+    fn decode(input: &mut InputCursor<'_>) -> Result<(A, B), ValueError> {
+        Ok((A::decode(input)?, B::decode(input)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ArgValue, ValueError};
+    use crate::cursor::InputCursor;
+    use std::{ffi::OsString, path::PathBuf};
+
+    fn decode<V: ArgValue>(values: &[&str]) -> Result<V, ValueError> {
+        let input: Vec<String> = values.iter().map(|v| String::from(*v)).collect();
+        V::decode(&mut InputCursor::new(&input))
+    }
+
+    #[test]
+    fn std_values_decode() {
+        assert_eq!(decode::<String>(&["a"]), Ok(String::from("a")));
+        assert_eq!(decode::<PathBuf>(&["a/b"]), Ok(PathBuf::from("a/b")));
+        assert_eq!(decode::<char>(&[","]), Ok(','));
+        assert_eq!(decode::<bool>(&["false"]), Ok(false));
+        assert_eq!(decode::<f64>(&["0.5"]), Ok(0.5));
+        assert_eq!(decode::<i64>(&["-7"]), Ok(-7));
+        assert_eq!(decode::<u16>(&["8080"]), Ok(8080));
+        assert_eq!(decode::<usize>(&["3"]), Ok(3));
+        assert_eq!(decode::<i8>(&["-128"]), Ok(-128));
+        assert_eq!(decode::<i32>(&["-7"]), Ok(-7));
+        assert_eq!(decode::<isize>(&["7"]), Ok(7));
+        assert_eq!(decode::<f32>(&["0.5"]), Ok(0.5));
+        assert_eq!(decode::<OsString>(&["a"]), Ok(OsString::from("a")));
+        assert_eq!(
+            decode::<i16>(&["40000"]),
+            Err(ValueError::invalid(
+                "40000",
+                "Value 40000 is not between -32768 and 32767"
+            ))
+        );
+        assert_eq!(<(i64, PathBuf)>::value_names(), vec!["INT", "PATH"]);
+        assert_eq!(<(String, u8)>::value_names(), vec!["VALUE", "INT"]);
+        assert_eq!(
+            decode::<(u8, String)>(&["1", "x"]),
+            Ok((1, String::from("x")))
+        );
+        assert_eq!(decode::<u8>(&[]), Err(ValueError::Missing));
+        assert_eq!(decode::<(u8, u8)>(&["1"]), Err(ValueError::Missing));
+        let error = decode::<u8>(&["256"]).unwrap_err();
+        assert_eq!(error.value(), Some("256"));
+        assert_eq!(format!("{}", error), "Value 256 is greater than 255");
+        assert_eq!(
+            decode::<i64>(&["high"]),
+            Err(ValueError::invalid("high", "Input is not a number"))
+        );
+    }
+}
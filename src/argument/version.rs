@@ -0,0 +1,91 @@
+use super::{parsable_argument::ParsableValueArgument, ArgumentIdentification};
+use crate::cursor::InputCursor;
+use semver::{Version, VersionReq};
+use std::str::FromStr;
+
+/// Creates argument parsing its values with FromStr implementation of semver type.
+fn new_semver_argument<V>(
+    identification: ArgumentIdentification,
+    kind: &'static str,
+) -> ParsableValueArgument<V>
+where
+    V: FromStr + 'static,
+    V::Err: std::fmt::Display,
+{
+    let handler = move |input_iter: &mut InputCursor<'_>, values: &mut Vec<V>| {
+        let value = input_iter.next_decoded(|v| {
+            v.parse()
+                .map_err(|err| format!("'{}' is not a valid {}: {}", v, kind, err))
+        })?;
+        values.push(value);
+        Result::Ok(())
+    };
+    ParsableValueArgument::new(identification, handler)
+}
+
+impl ParsableValueArgument<Version> {
+    /// Argument accepting semantic version (`1.2.3-beta.1`). Available with `semver` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_version =
+    ///     ParsableValueArgument::new_semver(ArgumentIdentification::Long(String::from("version")));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_version);
+    /// args_list
+    ///     .parse_args(vec![String::from("--version"), String::from("1.2.3")])
+    ///     .unwrap();
+    /// assert_eq!(argument_version.first_value().unwrap().minor, 2);
+    /// ```
+    pub fn new_semver(identification: ArgumentIdentification) -> ParsableValueArgument<Version> {
+        new_semver_argument(identification, "version")
+    }
+}
+
+impl ParsableValueArgument<VersionReq> {
+    /// Argument accepting version requirement (`>=1.2, <2`). Available with `semver` feature.
+    pub fn new_version_req(
+        identification: ArgumentIdentification,
+    ) -> ParsableValueArgument<VersionReq> {
+        new_semver_argument(identification, "version requirement")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cursor::InputCursor;
+
+    use semver::Version;
+
+    use crate::argument::{
+        parsable_argument::{HandleableArgument, ParsableValueArgument},
+        ArgumentIdentification,
+    };
+
+    #[test]
+    fn version_req_works() {
+        let mut arg = ParsableValueArgument::new_version_req(ArgumentIdentification::Short('r'));
+        arg.handle(&mut InputCursor::new(&[String::from(">=1.2, <2")]))
+            .unwrap();
+        let requirement = arg.first_value().unwrap();
+        assert!(requirement.matches(&Version::new(1, 4, 0)));
+        assert!(!requirement.matches(&Version::new(2, 0, 0)));
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("=>1")]))
+            .unwrap_err()
+            .starts_with("error in -r: '=>1' is not a valid version requirement: "));
+    }
+
+    #[test]
+    fn semver_rejects_invalid() {
+        let mut arg = ParsableValueArgument::new_semver(ArgumentIdentification::Short('v'));
+        assert!(arg
+            .handle(&mut InputCursor::new(&[String::from("1.2")]))
+            .is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use crate::ArgumentList;
+use crate::{argument::legacy_argument::Argument, error::ParseError, parsed::ParsedArgs, suggest};
+
+///
+/// Subcommand which can be executed after its arguments were parsed. Implemented by the type
+/// representing each subcommand and registered in Subcommands under its name.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::legacy_argument::*,
+///     command::{Dispatch, Subcommands},
+///     options::ParserOptions,
+///     parsed::ParsedArgs,
+/// };
+///
+/// struct Add;
+///
+/// impl Dispatch for Add {
+///     type Error = String;
+///
+///     fn arguments() -> Vec<Argument> {
+///         vec![Argument::new(Some('f'), Some("force"), ArgType::Flag).unwrap()]
+///     }
+///
+///     fn run(parsed: &ParsedArgs) -> Result<(), String> {
+///         assert!(parsed.is_present("force"));
+///         assert_eq!(parsed.positionals(), vec!["file.txt"]);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut args_list = ArgumentList::new();
+/// // Everything after subcommand name is left for the subcommand.
+/// args_list.set_options(ParserOptions::new().options_after_positionals(false));
+/// args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+/// let input = vec!["-v", "add", "--force", "file.txt"];
+/// args_list
+///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+///     .unwrap();
+/// let subcommands = Subcommands::new().register::<Add>("add");
+/// let command = subcommands.parse(args_list.get_dangling_values().clone()).unwrap();
+/// assert_eq!(command.name(), "add");
+/// command.dispatch().unwrap();
+/// ```
+pub trait Dispatch {
+    /// Error returned when running the subcommand.
+    type Error;
+
+    /// Arguments accepted by the subcommand.
+    fn arguments() -> Vec<Argument> {
+        Vec::new()
+    }
+
+    /// Executes the subcommand with its parsed arguments.
+    fn run(parsed: &ParsedArgs) -> Result<(), Self::Error>;
+}
+
+/// Subcommand registered under a name.
+struct SubcommandEntry<E> {
+    name: String,
+    arguments: fn() -> Vec<Argument>,
+    run: fn(&ParsedArgs) -> Result<(), E>,
+}
+
+/// Set of subcommands sharing error type. Selects subcommand by the first input value.
+pub struct Subcommands<E> {
+    entries: Vec<SubcommandEntry<E>>,
+}
+
+impl<E> Subcommands<E> {
+    pub fn new() -> Subcommands<E> {
+        Subcommands {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registers subcommand under given name.
+    pub fn register<D: Dispatch<Error = E>>(mut self, name: &str) -> Subcommands<E> {
+        self.entries.push(SubcommandEntry {
+            name: String::from(name),
+            arguments: D::arguments,
+            run: D::run,
+        });
+        self
+    }
+
+    /// Names of all registered subcommands in order they were added.
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    /// Selects subcommand named by the first input value and parses the remaining values with its
+    /// arguments.
+    pub fn parse(&self, input: Vec<String>) -> Result<Command<'_, E>, ParseError> {
+        let mut input = input.into_iter();
+        let name = match input.next() {
+            Some(name) => name,
+            None => return Err(ParseError::MissingSubcommand),
+        };
+        let entry = match self.entries.iter().find(|e| e.name == name) {
+            Some(entry) => entry,
+            None => {
+                return Err(ParseError::UnknownSubcommand {
+                    suggestion: suggest::closest(&name, self.names()).map(String::from),
+                    name,
+                })
+            }
+        };
+        let mut args_list = ArgumentList::new();
+        for argument in (entry.arguments)() {
+            args_list.append_arg(argument);
+        }
+        args_list.parse_args(input.collect())?;
+        Ok(Command {
+            name: entry.name.as_str(),
+            parsed: args_list.parsed().clone(),
+            run: entry.run,
+        })
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Parses dangling values left by the last parse_args with selected subcommand. Results of
+    /// subcommand are also available in nested form through ParsedArgs::subcommand, so arguments
+    /// with the same names in main command and subcommand do not collide. Dangling values and
+    /// positional values of the main command are removed, since they were given to the
+    /// subcommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::legacy_argument::*,
+    ///     command::{Dispatch, Subcommands},
+    ///     options::ParserOptions,
+    ///     parsed::ParsedArgs,
+    /// };
+    ///
+    /// struct Build;
+    ///
+    /// impl Dispatch for Build {
+    ///     type Error = String;
+    ///
+    ///     fn arguments() -> Vec<Argument> {
+    ///         vec![Argument::new(Some('v'), None, ArgType::Value).unwrap()]
+    ///     }
+    ///
+    ///     fn run(_: &ParsedArgs) -> Result<(), String> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.set_options(ParserOptions::new().options_after_positionals(false));
+    /// args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+    /// let input = vec!["-v", "build", "-v", "2"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// let subcommands = Subcommands::new().register::<Build>("build");
+    /// args_list.parse_subcommand(&subcommands).unwrap();
+    /// let (name, build) = args_list.parsed().subcommand().unwrap();
+    /// assert_eq!(name, "build");
+    /// assert_eq!(build.value_of("v"), Some("2"));
+    /// assert_eq!(args_list.parsed().value_of("v"), None);
+    /// ```
+    pub fn parse_subcommand<'s, E>(
+        &mut self,
+        subcommands: &'s Subcommands<E>,
+    ) -> Result<Command<'s, E>, ParseError> {
+        let command = subcommands.parse(self.get_dangling_values().clone())?;
+        // Subcommand name and values following it belong to the subcommand.
+        self.dangling_values.clear();
+        self.parsed.truncate_positionals();
+        self.parsed
+            .set_subcommand(command.name(), command.parsed().clone());
+        Ok(command)
+    }
+}
+
+impl<E> Default for Subcommands<E> {
+    fn default() -> Self {
+        Subcommands::new()
+    }
+}
+
+/// Subcommand selected by the user together with its parsed arguments.
+pub struct Command<'s, E> {
+    name: &'s str,
+    parsed: ParsedArgs,
+    run: fn(&ParsedArgs) -> Result<(), E>,
+}
+
+impl<'s, E> Command<'s, E> {
+    /// Name of selected subcommand.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Arguments given to selected subcommand.
+    pub fn parsed(&self) -> &ParsedArgs {
+        &self.parsed
+    }
+
+    /// Runs selected subcommand.
+    pub fn dispatch(&self) -> Result<(), E> {
+        (self.run)(&self.parsed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Dispatch, Subcommands};
+    use crate::{
+        argument::legacy_argument::{ArgType, Argument},
+        error::ParseError,
+        options::ParserOptions,
+        parsed::ParsedArgs,
+        ArgumentList,
+    };
+
+    struct Remove;
+
+    impl Dispatch for Remove {
+        type Error = String;
+
+        fn arguments() -> Vec<Argument> {
+            vec![Argument::new_short('r', ArgType::Flag)]
+        }
+
+        fn run(parsed: &ParsedArgs) -> Result<(), String> {
+            if parsed.is_present("r") {
+                Ok(())
+            } else {
+                Err(String::from("not recursive"))
+            }
+        }
+    }
+
+    #[test]
+    fn dispatch_works() {
+        let subcommands = Subcommands::new().register::<Remove>("remove");
+        let command = subcommands
+            .parse(vec![String::from("remove"), String::from("-r")])
+            .unwrap();
+        assert_eq!(command.name(), "remove");
+        assert_eq!(command.dispatch(), Ok(()));
+        let command = subcommands.parse(vec![String::from("remove")]).unwrap();
+        assert_eq!(command.dispatch(), Err(String::from("not recursive")));
+    }
+
+    #[test]
+    fn parse_fails_unknown_subcommand() {
+        let subcommands = Subcommands::new().register::<Remove>("remove");
+        assert_eq!(
+            subcommands.parse(vec![String::from("remov")]).err(),
+            Some(ParseError::UnknownSubcommand {
+                name: String::from("remov"),
+                suggestion: Some(String::from("remove")),
+            })
+        );
+        assert_eq!(
+            subcommands.parse(Vec::new()).err(),
+            Some(ParseError::MissingSubcommand)
+        );
+    }
+
+    #[test]
+    fn parse_subcommand_nests_results() {
+        let subcommands = Subcommands::new().register::<Remove>("remove");
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('r', ArgType::Value));
+        args_list
+            .parse_args(vec![
+                String::from("-r"),
+                String::from("a"),
+                String::from("rm"),
+            ])
+            .unwrap();
+        assert!(args_list.parse_subcommand(&subcommands).is_err());
+        assert_eq!(args_list.parsed().subcommand(), None);
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().options_after_positionals(false));
+        args_list.append_arg(Argument::new_short('r', ArgType::Value));
+        let input = ["-r", "a", "remove", "-r"];
+        args_list
+            .parse_args(input.iter().map(|s| String::from(*s)).collect())
+            .unwrap();
+        let command = args_list.parse_subcommand(&subcommands).unwrap();
+        assert_eq!(command.dispatch(), Ok(()));
+        let (name, remove) = args_list.parsed().subcommand().unwrap();
+        assert_eq!(name, "remove");
+        assert!(remove.is_present("r"));
+        assert_eq!(args_list.parsed().value_of("r"), Some("a"));
+        assert!(args_list.get_dangling_values().is_empty());
+        assert!(args_list.parsed().positionals().is_empty());
+    }
+}
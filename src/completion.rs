@@ -0,0 +1,256 @@
+use std::{fmt, str::FromStr};
+
+use crate::ArgumentList;
+
+/// Shells for which completion scripts can be generated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+    Elvish,
+}
+
+impl Shell {
+    /// All supported shells.
+    pub const ALL: [Shell; 5] = [
+        Shell::Bash,
+        Shell::Zsh,
+        Shell::Fish,
+        Shell::PowerShell,
+        Shell::Elvish,
+    ];
+
+    /// Name of the shell as typed by the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+            Shell::PowerShell => "powershell",
+            Shell::Elvish => "elvish",
+        }
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Shell::ALL
+            .iter()
+            .find(|shell| shell.name() == s)
+            .copied()
+            .ok_or_else(|| format!("Unsupported shell '{}'.", s))
+    }
+}
+
+/// Option which can be completed, with all names it can be written as.
+struct CompletionEntry {
+    short: Option<char>,
+    long: Option<String>,
+    description: String,
+}
+
+impl CompletionEntry {
+    fn words(&self) -> Vec<String> {
+        let mut words = Vec::new();
+        if let Some(short) = self.short {
+            words.push(format!("-{}", short));
+        }
+        if let Some(long) = &self.long {
+            words.push(format!("--{}", long));
+        }
+        words
+    }
+}
+
+/// Quotes text in single quotes, escaping single quotes the way POSIX shells and fish do.
+pub(crate) fn single_quoted(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// Quotes text in single quotes for PowerShell and Elvish, which escape quote by doubling it.
+fn single_quoted_doubled(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+impl<'a> ArgumentList<'a> {
+    fn completion_entries(&self) -> Vec<CompletionEntry> {
+        self.visible_specs()
+            .map(|spec| CompletionEntry {
+                short: spec.identification().short(),
+                long: spec.identification().long().map(String::from),
+                description: String::from(
+                    spec.description_for(self.language.as_deref()).unwrap_or(""),
+                ),
+            })
+            .collect()
+    }
+
+    /// Generates script completing names of registered arguments in given shell. Script has to be
+    /// loaded by the shell (e.g. sourced from `.bashrc` or saved in fish completions directory).
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    ///     completion::Shell,
+    /// };
+    ///
+    /// let mut argument_str = ParsableValueArgument::new_string(ArgumentIdentification::Both(
+    ///     'p',
+    ///     String::from("path"),
+    /// ));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_str);
+    /// let script = args_list.generate_completions(Shell::Bash, "myapp");
+    /// assert!(script.contains("compgen -W \"-p --path\""));
+    /// ```
+    pub fn generate_completions(&self, shell: Shell, bin_name: &str) -> String {
+        let entries = self.completion_entries();
+        match shell {
+            Shell::Bash => bash(&entries, bin_name),
+            Shell::Zsh => zsh(&entries, bin_name),
+            Shell::Fish => fish(&entries, bin_name),
+            Shell::PowerShell => powershell(&entries, bin_name),
+            Shell::Elvish => elvish(&entries, bin_name),
+        }
+    }
+}
+
+fn function_name(bin_name: &str) -> String {
+    bin_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn bash(entries: &[CompletionEntry], bin_name: &str) -> String {
+    let words: Vec<String> = entries.iter().flat_map(CompletionEntry::words).collect();
+    let function = function_name(bin_name);
+    format!(
+        "_{function}() {{\n    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"\n    COMPREPLY=($(compgen -W \"{words}\" -- \"$cur\"))\n}}\ncomplete -F _{function} {bin}\n",
+        function = function,
+        words = words.join(" "),
+        bin = bin_name
+    )
+}
+
+fn zsh(entries: &[CompletionEntry], bin_name: &str) -> String {
+    let mut script = format!("#compdef {}\n\n_arguments \\\n", bin_name);
+    for entry in entries {
+        let description = entry.description.replace('\'', "'\\''").replace(']', "\\]");
+        for word in entry.words() {
+            script.push_str(&format!("  '{}[{}]' \\\n", word, description));
+        }
+    }
+    script.push_str("  '*::arg:_files'\n");
+    script
+}
+
+fn fish(entries: &[CompletionEntry], bin_name: &str) -> String {
+    let mut script = String::new();
+    for entry in entries {
+        script.push_str(&format!("complete -c {}", bin_name));
+        if let Some(short) = entry.short {
+            script.push_str(&format!(" -s {}", short));
+        }
+        if let Some(long) = &entry.long {
+            script.push_str(&format!(" -l {}", long));
+        }
+        if !entry.description.is_empty() {
+            script.push_str(&format!(" -d {}", single_quoted(&entry.description)));
+        }
+        script.push('\n');
+    }
+    script
+}
+
+fn powershell(entries: &[CompletionEntry], bin_name: &str) -> String {
+    let mut script = format!(
+        "using namespace System.Management.Automation\n\nRegister-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{\n    param($wordToComplete, $commandAst, $cursorPosition)\n    @(\n",
+        single_quoted_doubled(bin_name)
+    );
+    for entry in entries {
+        // Tooltip cannot be empty.
+        let tooltip = if entry.description.is_empty() {
+            entry.words().join(", ")
+        } else {
+            entry.description.clone()
+        };
+        for word in entry.words() {
+            script.push_str(&format!(
+                "        [CompletionResult]::new({word}, {word}, [CompletionResultType]::ParameterName, {tooltip})\n",
+                word = single_quoted_doubled(&word),
+                tooltip = single_quoted_doubled(&tooltip)
+            ));
+        }
+    }
+    script.push_str("    ) | Where-Object { $_.CompletionText -like \"$wordToComplete*\" }\n}\n");
+    script
+}
+
+fn elvish(entries: &[CompletionEntry], bin_name: &str) -> String {
+    let words: Vec<String> = entries
+        .iter()
+        .flat_map(CompletionEntry::words)
+        .map(|w| single_quoted_doubled(&w))
+        .collect();
+    format!(
+        "use str\n\nset edit:completion:arg-completer[{bin}] = {{|@words|\n    for candidate [{words}] {{\n        if (str:has-prefix $candidate $words[-1]) {{\n            put $candidate\n        }}\n    }}\n}}\n",
+        bin = bin_name,
+        words = words.join(" ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::Shell;
+    use crate::{
+        argument::{
+            legacy_argument::*, parsable_argument::ParsableValueArgument, ArgumentIdentification,
+        },
+        ArgumentList,
+    };
+
+    #[test]
+    fn shell_from_str_works() {
+        assert_eq!("powershell".parse::<Shell>(), Ok(Shell::PowerShell));
+        assert_eq!("elvish".parse::<Shell>(), Ok(Shell::Elvish));
+        assert!("cmd".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn generate_completions_works() {
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("path")))
+                .set_description("Input's path");
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.register_parsable(&mut argument_str);
+        assert_eq!(
+            args_list.generate_completions(Shell::Fish, "my-app"),
+            "complete -c my-app -s v\ncomplete -c my-app -l path -d 'Input'\\''s path'\n"
+        );
+        assert_eq!(
+            args_list.generate_completions(Shell::Elvish, "my-app"),
+            "use str\n\nset edit:completion:arg-completer[my-app] = {|@words|\n    for candidate ['-v' '--path'] {\n        if (str:has-prefix $candidate $words[-1]) {\n            put $candidate\n        }\n    }\n}\n"
+        );
+        let powershell = args_list.generate_completions(Shell::PowerShell, "my-app");
+        assert!(powershell.contains(
+            "[CompletionResult]::new('--path', '--path', [CompletionResultType]::ParameterName, 'Input''s path')"
+        ));
+        assert!(args_list
+            .generate_completions(Shell::Bash, "my-app")
+            .contains("complete -F _my_app my-app"));
+    }
+}
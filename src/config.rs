@@ -0,0 +1,131 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::stats::ParseReport;
+
+/// Exit codes used by ArgumentList::parse_or_exit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExitCodes {
+    /// Returned when user input could not be parsed.
+    pub usage_error: i32,
+    /// Returned when argument handler panicked while parsing or arguments are misconfigured.
+    pub internal_error: i32,
+    /// Returned after help message was printed.
+    pub help_shown: i32,
+}
+
+impl ExitCodes {
+    /// Exit codes following sysexits.h convention (EX_USAGE and EX_SOFTWARE).
+    pub fn sysexits() -> ExitCodes {
+        ExitCodes {
+            usage_error: 64,
+            internal_error: 70,
+            help_shown: 0,
+        }
+    }
+}
+
+impl Default for ExitCodes {
+    fn default() -> Self {
+        ExitCodes {
+            usage_error: 2,
+            internal_error: 1,
+            help_shown: 0,
+        }
+    }
+}
+
+/// What parse_or_exit does when a registered argument uses a name reserved for built-in help
+/// (`-h` or `--help`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ReservedNamePolicy {
+    /// Registered argument replaces built-in help for the names it uses. Default.
+    #[default]
+    Override,
+    /// Built-in help keeps reserved names. Registered argument is available by its other names
+    /// only, e.g. `-h, --host` can be given as `--host`.
+    Coexist,
+    /// Using reserved name is a configuration error. parse_or_exit writes the error and exits
+    /// with ExitCodes::internal_error without parsing.
+    Error,
+}
+
+///
+/// Configuration of behaviour surrounding parsing, like exiting the process and where messages are
+/// written. Help is written to `output` and errors to `error_output`, which default to standard
+/// output and standard error. Can be redirected to any writer, e.g. a buffer in tests.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, config::{ExitCodes, ParserConfig}};
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.set_config(ParserConfig {
+///     exit_codes: ExitCodes::sysexits(),
+///     error_output: Box::new(std::io::sink()),
+///     ..ParserConfig::default()
+/// });
+/// assert_eq!(args_list.config().exit_codes.usage_error, 64);
+/// ```
+pub struct ParserConfig {
+    pub exit_codes: ExitCodes,
+    /// Destination of help message.
+    pub output: Box<dyn Write>,
+    /// Destination of error messages.
+    pub error_output: Box<dyn Write>,
+    /// Enables hidden `__generate` subcommand handled by parse_or_exit, which prints shell
+    /// completions (`__generate completions bash`) or manual page (`__generate man`) for use by
+    /// packaging scripts. Disabled by default.
+    pub generate_command: bool,
+    /// Called after every parse_args with anonymized summary of the parse, successful or not.
+    /// Allows instrumenting CLI usage without wrapping the parser.
+    pub on_parse_complete: Option<fn(&ParseReport)>,
+    /// Decides whether registered arguments can use names of built-in help.
+    pub reserved_names: ReservedNamePolicy,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        ParserConfig {
+            exit_codes: ExitCodes::default(),
+            output: Box::new(io::stdout()),
+            error_output: Box::new(io::stderr()),
+            generate_command: false,
+            on_parse_complete: None,
+            reserved_names: ReservedNamePolicy::default(),
+        }
+    }
+}
+
+impl fmt::Debug for ParserConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParserConfig")
+            .field("exit_codes", &self.exit_codes)
+            .field("generate_command", &self.generate_command)
+            .field("on_parse_complete", &self.on_parse_complete)
+            .field("reserved_names", &self.reserved_names)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExitCodes;
+
+    #[test]
+    fn default_exit_codes_work() {
+        let codes = ExitCodes::default();
+        assert_eq!(codes.usage_error, 2);
+        assert_eq!(codes.internal_error, 1);
+        assert_eq!(codes.help_shown, 0);
+    }
+
+    #[test]
+    fn sysexits_works() {
+        let codes = ExitCodes::sysexits();
+        assert_eq!(codes.usage_error, 64);
+        assert_eq!(codes.internal_error, 70);
+    }
+}
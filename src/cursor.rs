@@ -0,0 +1,267 @@
+use crate::{options::ParserOptions, parsed::ParsedArgs};
+
+///
+/// Input values given to argument handlers. Values are read with next, inspected without
+/// consuming with peek and consumption can be undone by rolling back to a checkpoint. The parser
+/// rolls back values consumed by a handler which returned Err on its own.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::cursor::InputCursor;
+///
+/// let input = vec![String::from("1"), String::from("2"), String::from("-v")];
+/// let mut cursor = InputCursor::new(&input);
+/// let checkpoint = cursor.checkpoint();
+/// assert_eq!(cursor.next(), Some(&input[0]));
+/// cursor.rollback(checkpoint);
+/// assert_eq!(cursor.take_while(|v| !v.starts_with('-')), &input[..2]);
+/// assert_eq!(cursor.peek(), Some(&input[2]));
+/// assert_eq!(cursor.consumed(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct InputCursor<'i> {
+    input: &'i [String],
+    position: usize,
+    context: Option<ParseContext<'i>>,
+    rejected: Option<String>,
+    inline: bool,
+}
+
+///
+/// Read-only state of the parser available to argument handlers through InputCursor::context.
+/// Contains only arguments given before the handled one, so handlers can depend on earlier
+/// arguments (e.g. `--unit kb --size 10`).
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+/// };
+///
+/// let mut argument_unit = ParsableValueArgument::new_string(ArgumentIdentification::Long(
+///     String::from("unit"),
+/// ));
+/// let mut argument_size = ParsableValueArgument::new(
+///     ArgumentIdentification::Long(String::from("size")),
+///     |input_iter, values: &mut Vec<u64>| {
+///         let multiplier = match input_iter.context().and_then(|c| c.parsed().value_of("unit")) {
+///             Some("kb") => 1024,
+///             _ => 1,
+///         };
+///         let size: u64 = input_iter
+///             .next()
+///             .ok_or_else(|| String::from("No remaining input values."))?
+///             .parse()
+///             .map_err(|_| String::from("Size must be a number"))?;
+///         values.push(size * multiplier);
+///         Result::Ok(())
+///     },
+/// );
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_parsable(&mut argument_unit);
+/// args_list.register_parsable(&mut argument_size);
+/// let input = vec!["--unit", "kb", "--size", "2"];
+/// args_list
+///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+///     .unwrap();
+/// drop(args_list);
+/// assert_eq!(argument_size.first_value(), Some(&2048));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ParseContext<'c> {
+    parsed: &'c ParsedArgs,
+    options: &'c ParserOptions,
+    form: &'c str,
+}
+
+impl<'c> ParseContext<'c> {
+    pub(crate) fn new(
+        parsed: &'c ParsedArgs,
+        options: &'c ParserOptions,
+        form: &'c str,
+    ) -> ParseContext<'c> {
+        ParseContext {
+            parsed,
+            options,
+            form,
+        }
+    }
+
+    /// Arguments and dangling values parsed before the handled argument.
+    pub fn parsed(&self) -> &'c ParsedArgs {
+        self.parsed
+    }
+
+    pub fn options(&self) -> &'c ParserOptions {
+        self.options
+    }
+
+    /// Input value which named the handled argument as typed by the user (e.g. `-v`, `+v`,
+    /// `--path` or `-vp` when bundled). Value given after `=` is not included.
+    pub fn form(&self) -> &'c str {
+        self.form
+    }
+}
+
+/// Position of InputCursor, which cursor can be rolled back to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Checkpoint(usize);
+
+impl<'i> InputCursor<'i> {
+    pub fn new(input: &'i [String]) -> InputCursor<'i> {
+        InputCursor {
+            input,
+            position: 0,
+            context: None,
+            rejected: None,
+            inline: false,
+        }
+    }
+
+    /// Creates cursor over value given after `=` (`--jobs=4`).
+    pub(crate) fn new_inline(input: &'i [String]) -> InputCursor<'i> {
+        InputCursor {
+            inline: true,
+            ..InputCursor::new(input)
+        }
+    }
+
+    /// Creates cursor over values not consumed yet, which handlers can use to read the context.
+    pub(crate) fn with_context<'c>(&'c self, context: ParseContext<'c>) -> InputCursor<'c> {
+        InputCursor {
+            input: self.remaining(),
+            position: 0,
+            context: Some(context),
+            rejected: None,
+            inline: self.inline,
+        }
+    }
+
+    /// State of the parser. None when cursor was not created by the parser.
+    pub fn context(&self) -> Option<&ParseContext<'i>> {
+        self.context.as_ref()
+    }
+
+    /// Check if values were given after `=` in the same input value as the argument name
+    /// (`--jobs=4`), so they belong to the argument whatever they look like.
+    pub fn is_inline(&self) -> bool {
+        self.inline
+    }
+
+    /// Returns next value without consuming it.
+    pub fn peek(&self) -> Option<&'i String> {
+        self.input.get(self.position)
+    }
+
+    /// Consumes and returns next value. Cursor is not an Iterator, so it can gain new methods
+    /// without clashing with iterator adapters.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&'i String> {
+        let value = self.input.get(self.position)?;
+        self.position += 1;
+        Some(value)
+    }
+
+    /// Consumes and returns next value only if it satisfies `func`.
+    pub fn next_if<F>(&mut self, func: F) -> Option<&'i String>
+    where
+        F: FnOnce(&String) -> bool,
+    {
+        match self.peek() {
+            Some(value) if func(value) => self.next(),
+            _ => None,
+        }
+    }
+
+    /// Consumes next value and decodes it with `decode`. Value rejected by `decode` is marked with
+    /// reject, so the error names it.
+    pub fn next_decoded<V, F>(&mut self, decode: F) -> Result<V, String>
+    where
+        F: FnOnce(&'i str) -> Result<V, String>,
+    {
+        let value = self
+            .next()
+            .ok_or_else(|| String::from("No remaining input values."))?;
+        decode(value).inspect_err(|_| self.reject(value))
+    }
+
+    /// Consumes values while they satisfy `predicate`. First value not satisfying it is not
+    /// consumed.
+    pub fn take_while<P>(&mut self, mut predicate: P) -> &'i [String]
+    where
+        P: FnMut(&String) -> bool,
+    {
+        let start = self.position;
+        while self.next_if(&mut predicate).is_some() {}
+        &self.input[start..self.position]
+    }
+
+    /// Consumes up to `count` values.
+    pub fn take(&mut self, count: usize) -> &'i [String] {
+        let start = self.position;
+        self.position = self.input.len().min(start + count);
+        &self.input[start..self.position]
+    }
+
+    /// Value consumed most recently. None when nothing was consumed yet.
+    pub fn previous(&self) -> Option<&'i String> {
+        self.position.checked_sub(1).and_then(|i| self.input.get(i))
+    }
+
+    /// Marks value as rejected by the handler. Errors of parsable arguments name the rejected
+    /// value, e.g. `error in --level: 'high': Input is not a number`.
+    pub fn reject(&mut self, value: &str) {
+        self.rejected = Some(String::from(value));
+    }
+
+    /// Value marked with reject.
+    pub fn rejected(&self) -> Option<&str> {
+        self.rejected.as_deref()
+    }
+
+    /// Values not consumed yet.
+    pub fn remaining(&self) -> &'i [String] {
+        &self.input[self.position..]
+    }
+
+    /// Number of values consumed since cursor was created.
+    pub fn consumed(&self) -> usize {
+        self.position
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.position)
+    }
+
+    /// Makes values consumed after checkpoint available again.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.position = checkpoint.0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InputCursor;
+
+    #[test]
+    fn input_cursor_works() {
+        let input: Vec<String> = ["a", "b", "-c"].iter().map(|s| String::from(*s)).collect();
+        let mut cursor = InputCursor::new(&input);
+        assert_eq!(cursor.next_if(|v| v == "b"), None);
+        assert_eq!(cursor.next_if(|v| v == "a"), Some(&input[0]));
+        let checkpoint = cursor.checkpoint();
+        assert_eq!(cursor.take(5), &input[1..]);
+        assert_eq!(cursor.next(), None);
+        assert!(cursor.remaining().is_empty());
+        cursor.rollback(checkpoint);
+        assert_eq!(cursor.remaining(), &input[1..]);
+        assert_eq!(cursor.take_while(|_| false), &input[..0]);
+        assert_eq!(cursor.consumed(), 1);
+        assert_eq!(cursor.previous(), Some(&input[0]));
+        assert_eq!(cursor.rejected(), None);
+        cursor.reject("a");
+        assert_eq!(cursor.rejected(), Some("a"));
+        assert!(cursor.context().is_none());
+    }
+}
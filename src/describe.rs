@@ -0,0 +1,314 @@
+use std::fmt;
+
+use crate::{
+    argument::{legacy_argument::ArgType, spec::ArgSpec, ArgumentIdentification},
+    source, ArgumentList,
+};
+
+/// Change making command lines accepted by older version of CLI fail or behave differently.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakingChange {
+    /// Name of argument (e.g. `--path`) is no longer accepted.
+    RemovedName(String),
+    /// Argument takes different number of values. Contains old and new type.
+    ChangedArity {
+        argument: String,
+        from: ArgType,
+        to: ArgType,
+    },
+    /// Argument became required or new required argument was added.
+    BecameRequired(String),
+}
+
+/// Describes number of values taken by argument of given type.
+fn arity(arg_type: &ArgType) -> &'static str {
+    match arg_type {
+        ArgType::Flag => "no value",
+        ArgType::Value => "single value",
+        ArgType::ValueList => "list of values",
+    }
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakingChange::RemovedName(name) => write!(f, "{} was removed.", name),
+            BreakingChange::ChangedArity { argument, from, to } => write!(
+                f,
+                "{} takes {} instead of {}.",
+                argument,
+                arity(to),
+                arity(from)
+            ),
+            BreakingChange::BecameRequired(argument) => write!(f, "{} is required.", argument),
+        }
+    }
+}
+
+///
+/// Description of the whole CLI tagged with version set by ArgumentList::set_spec_version. Can be
+/// compared with description of an older version to detect accidental breakage, e.g. in tests of
+/// applications which keep definitions of released versions.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, describe::BreakingChange};
+///
+/// let mut v1 = ArgumentList::new();
+/// v1.set_spec_version("1.0.0");
+/// v1.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+/// v1.append_arg(Argument::new_long("fast", ArgType::Flag));
+/// let mut v2 = ArgumentList::new();
+/// v2.set_spec_version("2.0.0");
+/// v2.append_arg(Argument::new(Some('p'), Some("path"), ArgType::ValueList).unwrap());
+/// v2.append_arg(Argument::new_long("jobs", ArgType::Value));
+/// let changes = v2.describe().compatible_with(&v1.describe());
+/// assert_eq!(changes, vec![BreakingChange::RemovedName(String::from("--fast"))]);
+/// assert_eq!(v2.describe().version(), Some("2.0.0"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliDescription {
+    version: Option<String>,
+    arguments: Vec<ArgSpec>,
+}
+
+impl CliDescription {
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn arguments(&self) -> &Vec<ArgSpec> {
+        &self.arguments
+    }
+
+    ///
+    /// Formats the whole description as text which does not depend on order in which arguments
+    /// were registered, so it can be checked into the repository and CLI changes can be reviewed
+    /// as diffs. Every argument is a block of `key value` lines sorted by argument name (long
+    /// name when present). Texts are quoted with Rust escaping and tags are sorted.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{legacy_argument::*, parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.set_spec_version("1.2.0");
+    /// args_list.register_parsable_boxed(Box::new(
+    ///     ParsableValueArgument::new_integer(ArgumentIdentification::Both('j', String::from("jobs")))
+    ///         .set_description("Number of \"workers\"")
+    ///         .set_default(4),
+    /// ));
+    /// args_list.append_arg(Argument::new_long("debug", ArgType::Flag));
+    /// assert_eq!(
+    ///     args_list.describe().canonical_string(),
+    ///     "cli 1.2.0\n\
+    ///      \n\
+    ///      argument --debug\n\
+    ///      type flag\n\
+    ///      \n\
+    ///      argument -j, --jobs\n\
+    ///      type value-list\n\
+    ///      value-name \"INT\"\n\
+    ///      default \"4\"\n\
+    ///      description \"Number of \\\"workers\\\"\"\n"
+    /// );
+    /// ```
+    pub fn canonical_string(&self) -> String {
+        let mut text = match &self.version {
+            Some(version) => format!("cli {}\n", version),
+            None => String::from("cli\n"),
+        };
+        let mut arguments: Vec<&ArgSpec> = self.arguments.iter().collect();
+        arguments.sort_by_key(|spec| source::key(spec.identification()));
+        for spec in arguments {
+            text.push_str(&format!("\nargument {}\n", spec.identification()));
+            let arg_type = match spec.arg_type() {
+                ArgType::Flag => "flag",
+                ArgType::Value => "value",
+                ArgType::ValueList => "value-list",
+            };
+            text.push_str(&format!("type {}\n", arg_type));
+            if *spec.arg_type() != ArgType::Flag && spec.choices().is_empty() {
+                text.push_str(&format!("value-name {:?}\n", spec.value_name()));
+            }
+            if spec.is_required() {
+                text.push_str("required\n");
+            }
+            if let Some(default) = spec.default_value() {
+                text.push_str(&format!("default {:?}\n", default));
+            }
+            for choice in spec.choices() {
+                text.push_str(&format!("choice {:?}\n", choice));
+            }
+            if let Some(description) = spec.description() {
+                text.push_str(&format!("description {:?}\n", description));
+            }
+            for example in spec.examples() {
+                text.push_str(&format!("example {:?}\n", example));
+            }
+            let mut tags = spec.tags().clone();
+            tags.sort();
+            for tag in tags {
+                text.push_str(&format!("tag {:?}\n", tag));
+            }
+        }
+        text
+    }
+
+    /// Returns changes which make input accepted by `older` description fail or change meaning.
+    /// Taking a list of values instead of a single value is not considered breaking.
+    pub fn compatible_with(&self, older: &CliDescription) -> Vec<BreakingChange> {
+        let mut changes = Vec::new();
+        for old in &older.arguments {
+            let identification = old.identification();
+            let names = identification
+                .short()
+                .map(ArgumentIdentification::Short)
+                .into_iter()
+                .chain(
+                    identification
+                        .long()
+                        .map(|long| ArgumentIdentification::Long(String::from(long))),
+                );
+            for name in names {
+                match self
+                    .arguments
+                    .iter()
+                    .find(|new| new.identification().overlaps(&name))
+                {
+                    None => changes.push(BreakingChange::RemovedName(format!("{}", name))),
+                    Some(new) => {
+                        let compatible = old.arg_type() == new.arg_type()
+                            || (old.arg_type(), new.arg_type())
+                                == (&ArgType::Value, &ArgType::ValueList);
+                        if !compatible {
+                            changes.push(BreakingChange::ChangedArity {
+                                argument: format!("{}", name),
+                                from: *old.arg_type(),
+                                to: *new.arg_type(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        for new in self.arguments.iter().filter(|new| new.is_required()) {
+            let was_required = older.arguments.iter().any(|old| {
+                old.is_required() && old.identification().overlaps(new.identification())
+            });
+            if !was_required {
+                changes.push(BreakingChange::BecameRequired(format!(
+                    "{}",
+                    new.identification()
+                )));
+            }
+        }
+        changes
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Tags CLI described by describe with version.
+    pub fn set_spec_version(&mut self, version: &str) {
+        self.spec_version = Some(String::from(version));
+    }
+
+    /// Returns description of all registered arguments and version set by set_spec_version.
+    pub fn describe(&self) -> CliDescription {
+        CliDescription {
+            version: self.spec_version.clone(),
+            arguments: self.iter_specs().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BreakingChange;
+    use crate::{
+        argument::{
+            legacy_argument::{ArgType, Argument},
+            parsable_argument::ParsableValueArgument,
+            ArgumentIdentification,
+        },
+        ArgumentList,
+    };
+
+    #[test]
+    fn compatible_with_works() {
+        let mut older = ArgumentList::new();
+        older.append_arg(Argument::new(Some('v'), Some("verbose"), ArgType::Flag).unwrap());
+        older.append_arg(Argument::new_long("name", ArgType::ValueList));
+        let mut newer = ArgumentList::new();
+        newer.append_arg(Argument::new_long("verbose", ArgType::Value));
+        newer.append_arg(Argument::new_long("name", ArgType::Value));
+        newer.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                .set_required(true),
+        ));
+        let changes = newer.describe().compatible_with(&older.describe());
+        assert_eq!(
+            changes,
+            vec![
+                BreakingChange::RemovedName(String::from("-v")),
+                BreakingChange::ChangedArity {
+                    argument: String::from("--verbose"),
+                    from: ArgType::Flag,
+                    to: ArgType::Value,
+                },
+                BreakingChange::ChangedArity {
+                    argument: String::from("--name"),
+                    from: ArgType::ValueList,
+                    to: ArgType::Value,
+                },
+                BreakingChange::BecameRequired(String::from("-n")),
+            ]
+        );
+        assert_eq!(
+            format!("{}", changes[1]),
+            "--verbose takes single value instead of no value."
+        );
+        assert!(older
+            .describe()
+            .compatible_with(&older.describe())
+            .is_empty());
+    }
+
+    #[test]
+    fn canonical_string_ignores_registration_order() {
+        let list = |reversed: bool| {
+            let mut args_list = ArgumentList::new();
+            let mut arguments = vec![
+                Argument::new_short('v', ArgType::Flag),
+                Argument::new(Some('a'), Some("zone"), ArgType::Value).unwrap(),
+            ];
+            if reversed {
+                arguments.reverse();
+            }
+            for argument in arguments {
+                args_list.append_arg(argument);
+            }
+            args_list.register_parsable_boxed(Box::new(
+                ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from(
+                    "mode",
+                )))
+                .set_required(true)
+                .add_tag("b")
+                .add_tag("a"),
+            ));
+            args_list
+        };
+        let canonical = list(false).describe().canonical_string();
+        assert_eq!(canonical, list(true).describe().canonical_string());
+        assert_eq!(
+            canonical,
+            "cli\n\
+             \nargument --mode\ntype value-list\nvalue-name \"VALUE\"\nrequired\ntag \"a\"\ntag \"b\"\n\
+             \nargument -v\ntype flag\n\
+             \nargument -a, --zone\ntype value\nvalue-name \"VALUE\"\n"
+        );
+    }
+}
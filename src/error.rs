@@ -0,0 +1,558 @@
+use std::{error::Error, fmt};
+
+use crate::{
+    argument::{spec::ArgSpec, strip_name_prefix},
+    suggest,
+};
+
+/// Limit of input size which can be exceeded (see ParserLimits).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Limit {
+    Tokens,
+    ValueLength,
+    ListItems,
+    PresetDepth,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Tokens => write!(f, "input values"),
+            Limit::ValueLength => write!(f, "bytes in a single value"),
+            Limit::ListItems => write!(f, "values of a single argument"),
+            Limit::PresetDepth => write!(f, "nested presets"),
+        }
+    }
+}
+
+/// POSIX Utility Syntax Guideline broken by input (see Compliance).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PosixRule {
+    /// Option was given after the first operand (guideline 9).
+    OptionAfterOperand,
+    /// `-W` is reserved for vendor options (guideline 3).
+    ReservedOption,
+    /// Options are single characters, long options are allowed only in Compliance::Gnu
+    /// (guideline 3).
+    LongOption,
+}
+
+impl fmt::Display for PosixRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PosixRule::OptionAfterOperand => write!(f, "options must precede operands"),
+            PosixRule::ReservedOption => write!(f, "-W is reserved for vendor options"),
+            PosixRule::LongOption => write!(f, "options must be single characters"),
+        }
+    }
+}
+
+/// Errors returned while parsing user input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// Token looked like an argument but no argument with such name is registered.
+    UnknownArgument(String),
+    /// Short option inside of a bundle (e.g. `x` in `-vxd`) is not registered.
+    UnknownBundledArgument { bundle: String, option: char },
+    /// Option requiring a value was not placed at the end of a bundle.
+    IllegalBundle {
+        bundle: String,
+        option: char,
+        suggestion: String,
+    },
+    /// Error returned by argument while handling its values.
+    Handler(String),
+    /// Argument requiring a value was the last input value. Contains usage of the argument.
+    MissingValue { argument: String, usage: String },
+    /// Value of argument was rejected by its handler. Contains usage of the argument, rejected
+    /// input value (None when handler did not name it or argument is secret) and the closest
+    /// allowed choice.
+    InvalidValue {
+        argument: String,
+        message: String,
+        usage: String,
+        value: Option<String>,
+        suggestion: Option<String>,
+    },
+    /// Value was attached with `=` to argument which does not take a value.
+    UnexpectedValue { argument: String, value: String },
+    /// Argument from scoped list was used without its guard.
+    LockedArgument { argument: String, guard: String },
+    /// Required argument was not present in input.
+    MissingArgument(String),
+    /// Values of argument were rejected by its finalizer.
+    InvalidValues { argument: String, message: String },
+    /// Parse result was rejected by validator registered in ArgumentList.
+    Validation {
+        arguments: Vec<String>,
+        message: String,
+    },
+    /// Input value at given index is empty or contains only whitespace.
+    BlankValue(usize),
+    /// Lone `-` was given at input index while ParserOptions forbid it.
+    LoneDash(usize),
+    /// Preset with given name is not defined.
+    UnknownPreset(String),
+    /// Argument was given explicitly and also by a preset.
+    PresetConflict { preset: String, argument: String },
+    /// Input did not name any subcommand.
+    MissingSubcommand,
+    /// Subcommand with given name is not registered.
+    UnknownSubcommand {
+        name: String,
+        suggestion: Option<String>,
+    },
+    /// Argument removed in a newer version of the program was given. Contains migration note.
+    RemovedArgument { argument: String, note: String },
+    /// Input exceeded one of configured limits.
+    LimitExceeded { limit: Limit, maximum: usize },
+    /// Input value breaks POSIX rule enforced by ParserOptions::compliance.
+    PosixViolation { value: String, rule: PosixRule },
+    /// All errors found in input when parsing with ErrorMode::Aggregate.
+    Multiple(Vec<ParseError>),
+}
+
+impl ParseError {
+    /// Creates error describing value-taking option placed in the middle of a bundle. Suggests
+    /// moving that option out of the bundle so it can be followed by its value.
+    pub(crate) fn illegal_bundle(bundle: &str, option: char) -> ParseError {
+        let rest: String = bundle.chars().skip(1).filter(|c| c != &option).collect();
+        let suggestion = if rest.is_empty() {
+            format!("-{} <value>", option)
+        } else {
+            format!("-{} -{} <value>", rest, option)
+        };
+        ParseError::IllegalBundle {
+            bundle: String::from(bundle),
+            option,
+            suggestion,
+        }
+    }
+}
+
+impl ParseError {
+    /// Creates error of argument whose handler failed. Handler failing without any remaining
+    /// input is considered to miss its value. `rejected` is the input value named by the handler
+    /// (see InputCursor::reject); when it is not one of choices, the closest one is suggested.
+    pub(crate) fn handler(
+        spec: &ArgSpec,
+        had_input: bool,
+        rejected: Option<&str>,
+        message: String,
+    ) -> ParseError {
+        let argument = format!("{}", spec.identification());
+        if had_input {
+            let choices = spec.choices();
+            let suggestion = rejected
+                .filter(|value| !choices.iter().any(|choice| choice == value))
+                .and_then(|value| suggest::closest(value, choices.iter().map(String::as_str)));
+            ParseError::InvalidValue {
+                argument,
+                message,
+                usage: spec.usage(),
+                value: rejected.map(String::from),
+                suggestion: suggestion.map(String::from),
+            }
+        } else {
+            ParseError::MissingValue {
+                argument,
+                usage: spec.usage(),
+            }
+        }
+    }
+
+    /// Machine-readable name of the error variant (e.g. `unknown_argument`). Does not contain any
+    /// user input, so it can be logged or collected safely.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ParseError::UnknownArgument(_) => "unknown_argument",
+            ParseError::UnknownBundledArgument { .. } => "unknown_bundled_argument",
+            ParseError::IllegalBundle { .. } => "illegal_bundle",
+            ParseError::Handler(_) => "handler",
+            ParseError::MissingValue { .. } => "missing_value",
+            ParseError::InvalidValue { .. } => "invalid_value",
+            ParseError::UnexpectedValue { .. } => "unexpected_value",
+            ParseError::LockedArgument { .. } => "locked_argument",
+            ParseError::MissingArgument(_) => "missing_argument",
+            ParseError::InvalidValues { .. } => "invalid_values",
+            ParseError::Validation { .. } => "validation",
+            ParseError::BlankValue(_) => "blank_value",
+            ParseError::LoneDash(_) => "lone_dash",
+            ParseError::UnknownPreset(_) => "unknown_preset",
+            ParseError::PresetConflict { .. } => "preset_conflict",
+            ParseError::MissingSubcommand => "missing_subcommand",
+            ParseError::UnknownSubcommand { .. } => "unknown_subcommand",
+            ParseError::RemovedArgument { .. } => "removed_argument",
+            ParseError::LimitExceeded { .. } => "limit_exceeded",
+            ParseError::PosixViolation { .. } => "posix_violation",
+            ParseError::Multiple(_) => "multiple",
+        }
+    }
+
+    /// Argument which caused the error.
+    pub(crate) fn argument(&self) -> Option<String> {
+        match self {
+            ParseError::UnknownArgument(argument)
+            | ParseError::MissingValue { argument, .. }
+            | ParseError::InvalidValue { argument, .. }
+            | ParseError::UnexpectedValue { argument, .. }
+            | ParseError::LockedArgument { argument, .. }
+            | ParseError::MissingArgument(argument)
+            | ParseError::InvalidValues { argument, .. }
+            | ParseError::PresetConflict { argument, .. }
+            | ParseError::RemovedArgument { argument, .. } => Some(argument.clone()),
+            ParseError::UnknownBundledArgument { option, .. }
+            | ParseError::IllegalBundle { option, .. } => Some(format!("-{}", option)),
+            ParseError::Validation { arguments, .. } if !arguments.is_empty() => {
+                Some(arguments.join(", "))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns all errors described by this error, flattening ParseError::Multiple.
+    pub fn errors(&self) -> Vec<&ParseError> {
+        match self {
+            ParseError::Multiple(errors) => errors.iter().flat_map(|e| e.errors()).collect(),
+            _ => vec![self],
+        }
+    }
+}
+
+///
+/// Errors collected by ArgumentList::parse_args_best_effort. Parsing did not stop at any of them,
+/// so arguments which were given correctly hold their values and the rest hold defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartialParse {
+    errors: Vec<ParseError>,
+}
+
+impl PartialParse {
+    pub(crate) fn new(errors: Vec<ParseError>) -> PartialParse {
+        PartialParse { errors }
+    }
+
+    /// Check if every input value was parsed without errors.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &Vec<ParseError> {
+        &self.errors
+    }
+
+    /// Errors caused by argument identified by long name or single character short name.
+    pub fn errors_of(&self, name: &str) -> Vec<&ParseError> {
+        let name = strip_name_prefix(name);
+        self.errors
+            .iter()
+            .filter(|error| {
+                error.argument().is_some_and(|argument| {
+                    argument
+                        .split(", ")
+                        .any(|form| form.trim_start_matches(['-', '/']) == name)
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownArgument(word) => {
+                write!(f, "Could not find argument identified by {}.", word)
+            }
+            ParseError::UnknownBundledArgument { bundle, option } => write!(
+                f,
+                "Could not find argument identified by -{} (in {}).",
+                option, bundle
+            ),
+            ParseError::IllegalBundle {
+                bundle,
+                option,
+                suggestion,
+            } => write!(
+                f,
+                "Option -{} in {} requires a value and must be the last one in a group. Try: {}",
+                option, bundle, suggestion
+            ),
+            ParseError::Handler(message) => write!(f, "{}", message),
+            ParseError::MissingValue { argument, usage } => {
+                write!(f, "Missing value of {}.\nUsage: {}", argument, usage)
+            }
+            ParseError::InvalidValue { message, usage, .. } => {
+                write!(f, "{}\nUsage: {}", message, usage)
+            }
+            ParseError::UnexpectedValue { argument, value } => write!(
+                f,
+                "Argument {} does not take a value, but '{}' was given.",
+                argument, value
+            ),
+            ParseError::LockedArgument { argument, guard } => write!(
+                f,
+                "Argument {} can only be used together with {}.",
+                argument, guard
+            ),
+            ParseError::MissingArgument(argument) => {
+                write!(f, "Missing required argument {}.", argument)
+            }
+            ParseError::InvalidValues { argument, message } => {
+                write!(f, "Invalid values of {}: {}", argument, message)
+            }
+            ParseError::Validation { arguments, message } => {
+                if arguments.is_empty() {
+                    write!(f, "{}", message)
+                } else {
+                    write!(f, "{}: {}", arguments.join(", "), message)
+                }
+            }
+            ParseError::BlankValue(index) => write!(
+                f,
+                "Input value at position {} is empty or contains only whitespace.",
+                index
+            ),
+            ParseError::LoneDash(index) => write!(
+                f,
+                "Input value at position {} is a lone '-', which is not accepted.",
+                index
+            ),
+            ParseError::UnknownPreset(name) => write!(f, "Unknown preset '{}'.", name),
+            ParseError::PresetConflict { preset, argument } => write!(
+                f,
+                "Argument {} is already set by preset '{}'.",
+                argument, preset
+            ),
+            ParseError::MissingSubcommand => write!(f, "Missing subcommand."),
+            ParseError::UnknownSubcommand { name, suggestion } => match suggestion {
+                Some(suggestion) => write!(
+                    f,
+                    "Unknown subcommand '{}'; did you mean '{}'?",
+                    name, suggestion
+                ),
+                None => write!(f, "Unknown subcommand '{}'.", name),
+            },
+            ParseError::RemovedArgument { argument, note } => {
+                write!(f, "Argument {} was removed.", argument)?;
+                if !note.is_empty() {
+                    write!(f, " {}", note)?;
+                }
+                Ok(())
+            }
+            ParseError::LimitExceeded { limit, maximum } => {
+                write!(f, "Input exceeds limit of {} {}.", maximum, limit)
+            }
+            ParseError::PosixViolation { value, rule } => {
+                write!(f, "Value '{}' is not allowed: {}.", value, rule)
+            }
+            ParseError::Multiple(errors) => {
+                let lines: Vec<String> = errors.iter().map(|e| format!("{}", e)).collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(feature = "serde")]
+impl ParseError {
+    /// Input value or name related to the error.
+    fn value(&self) -> Option<&str> {
+        match self {
+            ParseError::UnknownBundledArgument { bundle, .. }
+            | ParseError::IllegalBundle { bundle, .. } => Some(bundle),
+            ParseError::UnexpectedValue { value, .. } => Some(value),
+            ParseError::UnknownPreset(name) | ParseError::UnknownSubcommand { name, .. } => {
+                Some(name)
+            }
+            ParseError::PresetConflict { preset, .. } => Some(preset),
+            ParseError::PosixViolation { value, .. } => Some(value),
+            ParseError::InvalidValue {
+                value: Some(value), ..
+            } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Corrections which user could apply.
+    fn suggestions(&self) -> Vec<&str> {
+        match self {
+            ParseError::IllegalBundle { suggestion, .. } => vec![suggestion],
+            ParseError::LockedArgument { guard, .. } => vec![guard],
+            ParseError::UnknownSubcommand {
+                suggestion: Some(suggestion),
+                ..
+            } => vec![suggestion],
+            ParseError::InvalidValue {
+                suggestion: Some(suggestion),
+                ..
+            } => vec![suggestion],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Serializes error as a structured payload: `kind`, human readable `message`, related
+/// `argument` and `value`, `suggestions` and nested `errors` of ParseError::Multiple.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParseError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut error = serializer.serialize_struct("ParseError", 6)?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("message", &format!("{}", self))?;
+        error.serialize_field("argument", &self.argument())?;
+        error.serialize_field("value", &self.value())?;
+        error.serialize_field("suggestions", &self.suggestions())?;
+        let errors: &[ParseError] = match self {
+            ParseError::Multiple(errors) => errors,
+            _ => &[],
+        };
+        error.serialize_field("errors", errors)?;
+        error.end()
+    }
+}
+
+#[cfg(feature = "json")]
+impl ParseError {
+    /// Renders error as JSON, so programs running the CLI (GUIs, editors, web frontends) can
+    /// display rich diagnostics. See Serialize implementation for the structure.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, options::ParserOptions};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.set_options(ParserOptions::new().short_bundling(true));
+    /// args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+    /// args_list.append_arg(Argument::new_short('o', ArgType::Value));
+    /// let error = args_list.parse_args(vec![String::from("-ov")]).unwrap_err();
+    /// assert_eq!(
+    ///     error.to_json(),
+    ///     concat!(
+    ///         r#"{"kind":"illegal_bundle","message":"Option -o in -ov requires a value and must "#,
+    ///         r#"be the last one in a group. Try: -v -o <value>","argument":"-o","value":"-ov","#,
+    ///         r#""suggestions":["-v -o <value>"],"errors":[]}"#
+    ///     )
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("ParseError serialization does not fail")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ParseError;
+
+    #[test]
+    fn illegal_bundle_suggestion_works() {
+        assert_eq!(
+            ParseError::illegal_bundle("-vdo", 'd'),
+            ParseError::IllegalBundle {
+                bundle: String::from("-vdo"),
+                option: 'd',
+                suggestion: String::from("-vo -d <value>"),
+            }
+        );
+        assert_eq!(
+            format!("{}", ParseError::illegal_bundle("-dv", 'd')),
+            "Option -d in -dv requires a value and must be the last one in a group. Try: -v -d <value>"
+        );
+    }
+
+    #[test]
+    fn display_works() {
+        assert_eq!(
+            format!("{}", ParseError::UnknownArgument(String::from("--path"))),
+            "Could not find argument identified by --path."
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                ParseError::UnknownBundledArgument {
+                    bundle: String::from("-vx"),
+                    option: 'x'
+                }
+            ),
+            "Could not find argument identified by -x (in -vx)."
+        );
+        assert_eq!(
+            format!("{}", ParseError::Handler(String::from("Expected value"))),
+            "Expected value"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                ParseError::InvalidValue {
+                    argument: String::from("--level"),
+                    message: String::from("'high' is not a number"),
+                    usage: String::from("--level <VALUE>  Verbosity (default: 1)"),
+                    value: Some(String::from("high")),
+                    suggestion: None,
+                }
+            ),
+            "'high' is not a number\nUsage: --level <VALUE>  Verbosity (default: 1)"
+        );
+    }
+
+    #[test]
+    fn multiple_works() {
+        let error = ParseError::Multiple(vec![
+            ParseError::UnknownArgument(String::from("-x")),
+            ParseError::MissingArgument(String::from("--path")),
+        ]);
+        assert_eq!(error.errors().len(), 2);
+        assert_eq!(
+            format!("{}", error),
+            "Could not find argument identified by -x.\nMissing required argument --path."
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_works() {
+        let error = ParseError::Multiple(vec![
+            ParseError::UnknownArgument(String::from("--colour")),
+            ParseError::LimitExceeded {
+                limit: super::Limit::Tokens,
+                maximum: 10,
+            },
+        ]);
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["kind"], "multiple");
+        assert_eq!(json["errors"][0]["kind"], "unknown_argument");
+        assert_eq!(json["errors"][0]["argument"], "--colour");
+        assert_eq!(json["errors"][1]["argument"], serde_json::Value::Null);
+        assert_eq!(
+            json["errors"][1]["message"],
+            "Input exceeds limit of 10 input values."
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_invalid_choice_works() {
+        use crate::{
+            argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+            ArgumentList,
+        };
+
+        let mut argument_format = ParsableValueArgument::new_choice(
+            ArgumentIdentification::Long(String::from("format")),
+            &["text", "json"],
+        );
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_format);
+        let error = args_list
+            .parse_args(vec![String::from("--format"), String::from("jsn")])
+            .unwrap_err();
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["kind"], "invalid_value");
+        assert_eq!(json["argument"], "--format");
+        assert_eq!(json["value"], "jsn");
+        assert_eq!(json["suggestions"], serde_json::json!(["json"]));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    argument::{spec::ArgSpec, ArgumentIdentification},
+    error::ParseError,
+    options::{Compliance, ErrorMode},
+    parsed::ParsedOccurrence,
+    stats::ParseStats,
+    token::Token,
+    ArgumentList,
+};
+
+impl<'a> ArgumentList<'a> {
+    /// Check if list contains only legacy flags, so no argument can consume input values. Lists
+    /// enforcing POSIX compliance, permuting options, migrating arguments or defining presets
+    /// always use parse_tokens. Types are read from the index, which has to be refreshed first.
+    pub(crate) fn is_flag_only(&self) -> bool {
+        self.options.compliance == Compliance::None
+            && !self.options.permute
+            && self.parsable_arguments.is_empty()
+            && self.boxed_arguments.is_empty()
+            && self.scopes.is_empty()
+            && self.migrations.is_empty()
+            && self.presets.is_empty()
+            && self.terminal.is_empty()
+            && self.index.only_flags()
+    }
+
+    ///
+    /// Version of parse_tokens used when is_flag_only is true. Since flags never consume input,
+    /// values are visited by index without InputCursor and nothing is copied except values recorded
+    /// in results. Produces the same results and errors as parse_tokens.
+    pub(crate) fn parse_flag_tokens(
+        &mut self,
+        input: &[String],
+        errors: &mut Vec<ParseError>,
+        stats: &mut ParseStats,
+    ) -> Result<Option<ArgumentIdentification>, ParseError> {
+        let fail_fast = self.options.error_mode == ErrorMode::FailFast;
+        let mut only_positionals = false;
+        let mut dashes_given = false;
+        for (index, word) in input.iter().enumerate() {
+            if only_positionals {
+                stats.allocations += 2;
+                match self.handle_dangling_value(word, index) {
+                    Ok(()) => (),
+                    Err(error) if fail_fast => return Err(error),
+                    Err(error) => errors.push(error),
+                }
+                continue;
+            }
+            // Handlers of other arguments would see following value as their input.
+            let had_input = index + 1 < input.len();
+            let token = self.tokenize(word);
+            #[cfg(feature = "tracing")]
+            tracing::trace!(index = index, word, ?token, "classified input value");
+            if !dashes_given && self.is_help_token(&token) {
+                self.help_requested = true;
+                return Ok(Some(Self::help_identification()));
+            }
+            dashes_given |= word == "--";
+            let result = match token {
+                Token::Short(name) => {
+                    stats.lookups += 1;
+                    match self.index.find_short(&self.arguments, name) {
+                        Some(position) => self.set_flag_at(position, had_input).map(|id| vec![id]),
+                        None => Err(ParseError::UnknownArgument(String::from(word))),
+                    }
+                }
+                Token::Long {
+                    prefix,
+                    name,
+                    value,
+                } => {
+                    stats.lookups += 1;
+                    match (self.index.find_long(&self.arguments, name), value) {
+                        (Some(position), None) => {
+                            self.set_flag_at(position, had_input).map(|id| vec![id])
+                        }
+                        (Some(position), Some(value)) => {
+                            let argument = &mut self.arguments[position];
+                            match argument.set_flag() {
+                                Ok(()) => Err(ParseError::UnexpectedValue {
+                                    argument: format!("{}{}", prefix, name),
+                                    value: String::from(value),
+                                }),
+                                Err(message) => Err(ParseError::handler(
+                                    &ArgSpec::of_legacy(argument),
+                                    true,
+                                    None,
+                                    message,
+                                )),
+                            }
+                        }
+                        (None, _) => {
+                            Err(ParseError::UnknownArgument(format!("{}{}", prefix, name)))
+                        }
+                    }
+                }
+                Token::Bundle(options) => {
+                    stats.lookups += options.len();
+                    options
+                        .iter()
+                        .map(
+                            |option| match self.index.find_short(&self.arguments, *option) {
+                                Some(position) => self.set_flag_at(position, had_input),
+                                None => Err(ParseError::UnknownBundledArgument {
+                                    bundle: String::from(word),
+                                    option: *option,
+                                }),
+                            },
+                        )
+                        .collect()
+                }
+                Token::Value => {
+                    stats.allocations += 2;
+                    self.handle_dangling_value(word, index).map(|()| {
+                        if !self.options.options_after_positionals {
+                            only_positionals = true;
+                        }
+                        Vec::new()
+                    })
+                }
+            };
+            #[cfg(feature = "tracing")]
+            if let Err(error) = &result {
+                tracing::debug!(index = index, %error, "input value rejected");
+            }
+            match result {
+                Ok(handled) => {
+                    // Like in parse_tokens, occurrences are recorded only when every option of
+                    // a bundle was handled.
+                    for identification in handled {
+                        stats.allocations += 2;
+                        #[cfg(feature = "tracing")]
+                        tracing::trace!(argument = %identification, "matched argument");
+                        self.parsed.push_occurrence(
+                            ParsedOccurrence::new(identification, Vec::new(), index)
+                                .with_form(word),
+                        );
+                    }
+                }
+                Err(error) if fail_fast => return Err(error),
+                Err(error) => errors.push(error),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Sets flag stored at given position.
+    fn set_flag_at(
+        &mut self,
+        position: usize,
+        had_input: bool,
+    ) -> Result<ArgumentIdentification, ParseError> {
+        let argument = &mut self.arguments[position];
+        argument.set_flag().map_err(|message| {
+            ParseError::handler(&ArgSpec::of_legacy(argument), had_input, None, message)
+        })?;
+        Ok(argument.identification())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        argument::legacy_argument::{ArgType, Argument},
+        options::{ErrorMode, ParserOptions},
+        stats::ParseStats,
+        ArgumentList,
+    };
+
+    fn new_list(options: ParserOptions) -> ArgumentList<'static> {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(options);
+        args_list.append_arg(Argument::new(Some('a'), Some("all"), ArgType::Flag).unwrap());
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.append_arg(Argument::new_long("dry-run", ArgType::Flag));
+        args_list
+    }
+
+    #[test]
+    fn parse_flag_tokens_matches_parse_tokens() {
+        let inputs = [
+            vec!["-a", "file", "--dry-run", "-v"],
+            vec!["-av", "file", "--", "-x"],
+            vec!["--all=yes", "-v", "-va", "--unknown", "-vx", "-v"],
+            vec!["-va", "--all", "file"],
+        ];
+        let options = [
+            ParserOptions::new().short_bundling(true),
+            ParserOptions::new()
+                .short_bundling(true)
+                .options_after_positionals(false),
+            ParserOptions::new()
+                .short_bundling(true)
+                .error_mode(ErrorMode::Aggregate),
+        ];
+        for input in inputs.iter() {
+            let input: Vec<String> = input.iter().map(|s| String::from(*s)).collect();
+            for options in options.iter() {
+                let mut expected_list = new_list(options.clone());
+                let mut expected_errors = Vec::new();
+                let mut expected_stats = ParseStats::default();
+                let expected =
+                    expected_list.parse_tokens(&input, &mut expected_errors, &mut expected_stats);
+                let mut args_list = new_list(options.clone());
+                assert!(args_list.is_flag_only());
+                let mut errors = Vec::new();
+                let mut stats = ParseStats::default();
+                let result = args_list.parse_flag_tokens(&input, &mut errors, &mut stats);
+                assert_eq!(result, expected, "{:?}", input);
+                assert_eq!(errors, expected_errors);
+                assert_eq!(stats, expected_stats);
+                assert_eq!(args_list.parsed(), expected_list.parsed());
+                assert_eq!(args_list.dangling_values, expected_list.dangling_values);
+                for (argument, expected) in args_list.arguments.iter().zip(&expected_list.arguments)
+                {
+                    assert_eq!(argument.arg_result, expected.arg_result);
+                }
+            }
+        }
+    }
+}
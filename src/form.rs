@@ -0,0 +1,154 @@
+use crate::{
+    argument::{legacy_argument::ArgType, spec::ArgSpec, ArgumentIdentification},
+    ArgumentList,
+};
+
+/// Neutral description of an input widget which can be mapped onto any GUI toolkit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Widget {
+    /// On/off switch used for flags.
+    Checkbox,
+    /// Single line of text used for arguments taking a single value.
+    TextField,
+    /// Editable list of text values used for arguments taking multiple values.
+    TextList,
+    /// Selection from a fixed set of values.
+    Dropdown(Vec<String>),
+}
+
+/// Single field of a settings form generated from an argument.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormField {
+    identification: ArgumentIdentification,
+    label: String,
+    widget: Widget,
+    required: bool,
+    description: Option<String>,
+    default: Option<String>,
+}
+
+impl FormField {
+    /// Creates field from argument description. Arguments with choices become dropdowns, other
+    /// widgets are picked by argument type.
+    pub fn from_spec(spec: &ArgSpec) -> FormField {
+        let widget = if !spec.choices().is_empty() {
+            Widget::Dropdown(spec.choices().clone())
+        } else {
+            match spec.arg_type() {
+                ArgType::Flag => Widget::Checkbox,
+                ArgType::Value => Widget::TextField,
+                ArgType::ValueList => Widget::TextList,
+            }
+        };
+        let identification = spec.identification();
+        let label = match identification.long() {
+            Some(long) => String::from(long),
+            None => identification.short().map(String::from).unwrap_or_default(),
+        };
+        FormField {
+            identification: identification.clone(),
+            label,
+            widget,
+            required: spec.is_required(),
+            description: spec.description().map(String::from),
+            default: spec.default_value().map(String::from),
+        }
+    }
+
+    /// Identification of argument filled by this field.
+    pub fn identification(&self) -> &ArgumentIdentification {
+        &self.identification
+    }
+
+    /// Label of the field, long name of argument when available.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn widget(&self) -> &Widget {
+        &self.widget
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// Description which can be used as a tooltip.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Initial value of the field.
+    pub fn default_value(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Generates description of settings form containing a field for every registered argument.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::legacy_argument::*,
+    ///     form::Widget,
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new_long("verbose", ArgType::Flag));
+    /// let fields = args_list.form_fields();
+    /// assert_eq!(fields[0].label(), "verbose");
+    /// assert_eq!(fields[0].widget(), &Widget::Checkbox);
+    /// ```
+    pub fn form_fields(&self) -> Vec<FormField> {
+        self.iter_specs()
+            .map(|spec| FormField::from_spec(&spec))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Widget;
+    use crate::{
+        argument::{
+            legacy_argument::{ArgType, Argument},
+            output_format::OutputFormatArg,
+            parsable_argument::ParsableValueArgument,
+            ArgumentIdentification,
+        },
+        ArgumentList,
+    };
+
+    #[test]
+    fn form_fields_works() {
+        let mut output_format = OutputFormatArg::new();
+        let mut argument_int =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                .set_description("Number of iterations")
+                .set_default(1);
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        args_list.append_arg(Argument::new_long("path", ArgType::Value));
+        args_list.register_parsable(&mut argument_int);
+        args_list.register_parsable(&mut output_format);
+        let fields = args_list.form_fields();
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[0].label(), "d");
+        assert_eq!(fields[0].widget(), &Widget::Checkbox);
+        assert_eq!(fields[1].widget(), &Widget::TextField);
+        assert_eq!(fields[2].widget(), &Widget::TextList);
+        assert_eq!(fields[2].description(), Some("Number of iterations"));
+        assert_eq!(fields[2].default_value(), Some("1"));
+        assert_eq!(fields[3].label(), "output-format");
+        assert_eq!(
+            fields[3].widget(),
+            &Widget::Dropdown(vec![
+                String::from("text"),
+                String::from("json"),
+                String::from("yaml")
+            ])
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use crate::{completion::Shell, error::ParseError, token::Token, ArgumentList};
+
+/// Name of hidden subcommand generating artifacts used by packaging scripts.
+pub(crate) const GENERATE_COMMAND: &str = "__generate";
+
+impl<'a> ArgumentList<'a> {
+    /// Checks if input requests generating an artifact (`__generate completions <shell> [name]` or
+    /// `__generate man [name]`) and generates it. Command has to be the first word, or follow
+    /// program name, which is then used as default name of the binary. Word following an option
+    /// is never the command, since it can be value of that option.
+    pub(crate) fn generate_artifact(&self, input: &[String]) -> Option<Result<String, ParseError>> {
+        let position = match input {
+            [first, ..] if first == GENERATE_COMMAND => 0,
+            [program, second, ..]
+                if second == GENERATE_COMMAND && self.tokenize(program) == Token::Value =>
+            {
+                1
+            }
+            _ => return None,
+        };
+        let program = if position == 1 {
+            Path::new(&input[0])
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        } else {
+            None
+        };
+        let mut words = input[position + 1..].iter();
+        let kind = words.next().map(String::as_str);
+        let result = match kind {
+            Some("completions") => match words.next().map(|shell| shell.parse::<Shell>()) {
+                Some(Ok(shell)) => {
+                    let bin_name = words.next().cloned().or(program);
+                    Ok(self.generate_completions(shell, bin_name.as_deref().unwrap_or("app")))
+                }
+                Some(Err(message)) => Err(ParseError::InvalidValues {
+                    argument: format!("{} completions", GENERATE_COMMAND),
+                    message,
+                }),
+                None => Err(ParseError::MissingArgument(String::from("<shell>"))),
+            },
+            Some("man") => {
+                let bin_name = words.next().cloned().or(program);
+                Ok(self.generate_man_page(bin_name.as_deref().unwrap_or("app")))
+            }
+            Some(kind) => Err(ParseError::UnknownSubcommand {
+                name: String::from(kind),
+                suggestion: None,
+            }),
+            None => Err(ParseError::MissingSubcommand),
+        };
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{argument::legacy_argument::*, error::ParseError, ArgumentList};
+
+    fn input(words: &[&str]) -> Vec<String> {
+        words.iter().map(|s| String::from(*s)).collect()
+    }
+
+    #[test]
+    fn generate_artifact_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        assert!(args_list.generate_artifact(&input(&["-v"])).is_none());
+        assert!(args_list
+            .generate_artifact(&input(&["--name", "__generate", "man"]))
+            .is_none());
+        assert!(args_list
+            .generate_artifact(&input(&["-v", "__generate", "man"]))
+            .is_none());
+        assert!(args_list
+            .generate_artifact(&input(&["tool", "--name", "__generate", "man"]))
+            .is_none());
+        assert_eq!(
+            args_list
+                .generate_artifact(&input(&[
+                    "/usr/bin/tool",
+                    "__generate",
+                    "completions",
+                    "fish"
+                ]))
+                .unwrap()
+                .unwrap(),
+            "complete -c tool -s v\n"
+        );
+        assert!(args_list
+            .generate_artifact(&input(&["__generate", "man", "tool"]))
+            .unwrap()
+            .unwrap()
+            .starts_with(".TH TOOL 1\n"));
+        assert_eq!(
+            args_list
+                .generate_artifact(&input(&["__generate", "docs"]))
+                .unwrap(),
+            Err(ParseError::UnknownSubcommand {
+                name: String::from("docs"),
+                suggestion: None
+            })
+        );
+    }
+}
@@ -0,0 +1,575 @@
+use std::io::{self, Write};
+
+use crate::{argument::spec::ArgSpec, ArgumentList};
+
+/// Single row of generated help text. Left column contains names, right column description.
+struct HelpEntry {
+    names: String,
+    description: String,
+}
+
+/// Replaces `{name}` placeholders in a single pass, so substituted text is never interpreted as
+/// a template. Unknown placeholders are kept as they are.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+            values
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (end, value))
+        });
+        match value {
+            Some((end, value)) => {
+                filled.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// Removes indentation shared by all lines of description, trailing whitespace and blank lines at
+/// the start and end, so descriptions can be written as indented raw strings. Paragraphs stay
+/// separated by a single empty line.
+pub(crate) fn trim_description(description: &str) -> String {
+    let indent = description
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+    let mut trimmed = String::new();
+    let mut paragraph_end = false;
+    for line in description.lines() {
+        if line.trim().is_empty() {
+            paragraph_end = !trimmed.is_empty();
+            continue;
+        }
+        if paragraph_end {
+            trimmed.push_str("\n\n");
+        } else if !trimmed.is_empty() {
+            trimmed.push('\n');
+        }
+        paragraph_end = false;
+        trimmed.push_str(line[indent..].trim_end());
+    }
+    trimmed
+}
+
+/// Width of help rendered by ArgumentList::render_help_plain.
+pub const PLAIN_HELP_WIDTH: usize = 80;
+
+/// Wraps every line of text at whitespace, so lines are at most `columns` characters long unless
+/// they contain a longer word. Leading whitespace of a line is repeated on its wrapped parts.
+fn wrap(text: &str, columns: usize) -> String {
+    let mut wrapped = Vec::new();
+    for line in text.lines() {
+        let leading = &line[..line.len() - line.trim_start().len()];
+        let mut current = String::from(leading);
+        for word in line.split_whitespace() {
+            let started = current.len() > leading.len();
+            if started && current.chars().count() + 1 + word.chars().count() > columns {
+                wrapped.push(std::mem::replace(&mut current, String::from(leading)));
+            } else if started {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        wrapped.push(current);
+    }
+    wrapped.join("\n")
+}
+
+///
+/// Layout of help message. Every part is a template with placeholders replaced while rendering:
+/// - layout: `{usage}`, `{sections}` and `{footer}`,
+/// - section: `{title}` and `{entries}`,
+/// - entry: `{names}` (padded to the widest names) and `{description}`. Trailing whitespace of
+///   every rendered entry is removed.
+///
+/// Arguments are listed in section titled `Options`, followed by `Examples` when arguments have
+/// examples. Applications can add own sections and give them different templates. Text set with
+/// ArgumentList::set_after_help is placed after all sections. Default template renders only the
+/// sections.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, help::HelpTemplate};
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.append_arg(Argument::new(Some('d'), Some("debug"), ArgType::Flag).unwrap());
+/// args_list.set_help_template(
+///     HelpTemplate::new()
+///         .layout("{usage}\n\n{sections}\n{footer}")
+///         .usage("Usage: app [OPTIONS]")
+///         .footer("See app(1) for details.\n")
+///         .entry("    {names}  {description}")
+///         .extra_section("Examples", "    app -d\n")
+///         .section_for("Examples", "{title}\n{entries}"),
+/// );
+/// assert_eq!(
+///     args_list.help_message(),
+///     "Usage: app [OPTIONS]\n\nOptions:\n    -d, --debug\n\nExamples\n    app -d\n\nSee app(1) for details.\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelpTemplate {
+    layout: String,
+    usage: String,
+    footer: String,
+    section: String,
+    section_overrides: Vec<(String, String)>,
+    entry: String,
+    extra_sections: Vec<(String, String)>,
+}
+
+impl Default for HelpTemplate {
+    fn default() -> Self {
+        HelpTemplate {
+            layout: String::from("{sections}"),
+            usage: String::new(),
+            footer: String::new(),
+            section: String::from("{title}:\n{entries}"),
+            section_overrides: Vec::new(),
+            entry: String::from("  {names}  {description}"),
+            extra_sections: Vec::new(),
+        }
+    }
+}
+
+impl HelpTemplate {
+    /// Creates template producing default help message.
+    pub fn new() -> HelpTemplate {
+        HelpTemplate::default()
+    }
+
+    /// Sets template of the whole message.
+    pub fn layout(mut self, layout: &str) -> HelpTemplate {
+        self.layout = String::from(layout);
+        self
+    }
+
+    /// Sets text substituted for `{usage}`.
+    pub fn usage(mut self, usage: &str) -> HelpTemplate {
+        self.usage = String::from(usage);
+        self
+    }
+
+    /// Sets text substituted for `{footer}`.
+    pub fn footer(mut self, footer: &str) -> HelpTemplate {
+        self.footer = String::from(footer);
+        self
+    }
+
+    /// Sets template of sections without own template.
+    pub fn section(mut self, section: &str) -> HelpTemplate {
+        self.section = String::from(section);
+        self
+    }
+
+    /// Sets template of section with given title.
+    pub fn section_for(mut self, title: &str, section: &str) -> HelpTemplate {
+        self.section_overrides
+            .push((String::from(title), String::from(section)));
+        self
+    }
+
+    /// Sets template of a single argument.
+    pub fn entry(mut self, entry: &str) -> HelpTemplate {
+        self.entry = String::from(entry);
+        self
+    }
+
+    /// Adds section with given text placed after arguments. Sections are separated by an empty
+    /// line.
+    pub fn extra_section(mut self, title: &str, text: &str) -> HelpTemplate {
+        self.extra_sections
+            .push((String::from(title), String::from(text)));
+        self
+    }
+
+    fn render_section(&self, title: &str, entries: &str) -> String {
+        let template = self
+            .section_overrides
+            .iter()
+            .rev()
+            .find(|(t, _)| t == title)
+            .map_or(self.section.as_str(), |(_, template)| template.as_str());
+        fill(template, &[("title", title), ("entries", entries)])
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    fn help_entries(&self, hidden_tags: &[String], language: Option<&str>) -> Vec<HelpEntry> {
+        let mut entries = Vec::new();
+        for argument in &self.arguments {
+            entries.push(HelpEntry {
+                names: ArgSpec::of_legacy(argument).synopsis(),
+                description: String::new(),
+            });
+        }
+        for argument in self.parsables() {
+            let spec = ArgSpec::of_parsable(argument);
+            if spec.has_any_tag(hidden_tags) {
+                continue;
+            }
+            entries.push(HelpEntry {
+                names: spec.synopsis(),
+                description: trim_description(spec.description_for(language).unwrap_or("")),
+            });
+        }
+        for scope in &self.scopes {
+            for mut entry in scope.list.help_entries(hidden_tags, language) {
+                let requirement = format!("(requires {})", scope.guard);
+                entry.description = if entry.description.is_empty() {
+                    requirement
+                } else {
+                    format!("{} {}", entry.description, requirement)
+                };
+                entries.push(entry);
+            }
+        }
+        entries
+    }
+
+    /// Generates help text listing all registered arguments with their descriptions. Lines of
+    /// multi-line descriptions are aligned and their common indentation is removed.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// let mut argument_str = ParsableValueArgument::new_string(ArgumentIdentification::Long(
+    ///     String::from("path"),
+    /// ))
+    /// .set_description("Path to the input file");
+    /// args_list.register_parsable(&mut argument_str);
+    /// assert_eq!(
+    ///     args_list.help_message(),
+    ///     "Options:\n  --path <VALUE>  Path to the input file\n"
+    /// );
+    /// ```
+    pub fn help_message(&self) -> String {
+        self.render_help(&self.help_template, None)
+    }
+
+    ///
+    /// Renders help for snapshot tests of applications. Uses the default template, ignoring one
+    /// set with set_help_template, never uses colors and wraps descriptions at
+    /// PLAIN_HELP_WIDTH columns. Output for the same arguments changes only in major versions of
+    /// this crate, so snapshots do not break on minor updates.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    ///     help::HelpTemplate,
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable_boxed(Box::new(
+    ///     ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+    ///         .set_description("Number of iterations"),
+    /// ));
+    /// args_list.set_help_template(HelpTemplate::new().entry("{names}: {description}"));
+    /// assert_eq!(
+    ///     args_list.render_help_plain(),
+    ///     "Options:\n  -n <INT>  Number of iterations\n"
+    /// );
+    /// ```
+    pub fn render_help_plain(&self) -> String {
+        self.render_help(&HelpTemplate::default(), Some(PLAIN_HELP_WIDTH))
+    }
+
+    /// Renders help with given template, wrapping descriptions at `width` columns when set.
+    fn render_help(&self, template: &HelpTemplate, width: Option<usize>) -> String {
+        let entries = self.help_entries(&self.hidden_tags, self.language.as_deref());
+        let names_width = entries
+            .iter()
+            .map(|e| e.names.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut options = String::new();
+        for entry in entries {
+            let names = format!("{:width$}", entry.names, width = names_width);
+            // Following lines of description are aligned with the first one.
+            let indent = template.entry.find("{description}").map_or(0, |end| {
+                fill(&template.entry[..end], &[("names", &names)])
+                    .chars()
+                    .count()
+            });
+            let description = match width {
+                Some(width) if width > indent => wrap(&entry.description, width - indent),
+                _ => entry.description,
+            };
+            let description = description.replace('\n', &format!("\n{}", " ".repeat(indent)));
+            let rendered = fill(
+                &template.entry,
+                &[("names", &names), ("description", &description)],
+            );
+            for line in rendered.lines() {
+                options.push_str(line.trim_end());
+                options.push('\n');
+            }
+        }
+        let mut sections = vec![template.render_section("Options", &options)];
+        let examples: String = self
+            .visible_specs()
+            .flat_map(|spec| spec.examples().clone())
+            .map(|example| format!("  {}\n", example))
+            .collect();
+        if !examples.is_empty() {
+            sections.push(template.render_section("Examples", &examples));
+        }
+        for (title, text) in &template.extra_sections {
+            sections.push(template.render_section(title, text));
+        }
+        if let Some(after_help) = &self.after_help {
+            sections.push(format!("{}\n", after_help.trim_end()));
+        }
+        fill(
+            &template.layout,
+            &[
+                ("usage", &template.usage),
+                ("sections", &sections.join("\n")),
+                ("footer", &template.footer),
+            ],
+        )
+    }
+
+    /// Sets text placed at the end of help message and manual page, e.g. links to
+    /// documentation.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_path =
+    ///     ParsableValueArgument::new_string(ArgumentIdentification::Short('p'))
+    ///         .set_description("Configuration file")
+    ///         .add_example("-p ./config.toml");
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_path);
+    /// args_list.set_after_help("Report bugs to the issue tracker.");
+    /// assert_eq!(
+    ///     args_list.help_message(),
+    ///     "Options:\n  -p <VALUE>  Configuration file\n\nExamples:\n  -p ./config.toml\n\nReport bugs to the issue tracker.\n"
+    /// );
+    /// ```
+    pub fn set_after_help(&mut self, text: &str) {
+        self.after_help = Some(String::from(text));
+    }
+
+    ///
+    /// Hides arguments with any of given tags from help, manual page and completions and stops
+    /// requiring them. Hidden arguments are still parsed. Replaces previously hidden tags.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let show_experimental = std::env::args().any(|arg| arg == "--show-experimental");
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable_boxed(Box::new(
+    ///     ParsableValueArgument::new_flag(ArgumentIdentification::Long(String::from("turbo")))
+    ///         .add_tag("experimental"),
+    /// ));
+    /// if !show_experimental {
+    ///     args_list.set_hidden_tags(&["experimental"]);
+    /// }
+    /// assert_eq!(args_list.help_message(), "Options:\n");
+    /// args_list.parse_args(vec![String::from("--turbo")]).unwrap();
+    /// ```
+    pub fn set_hidden_tags(&mut self, tags: &[&str]) {
+        self.hidden_tags = tags.iter().map(|tag| String::from(*tag)).collect();
+    }
+
+    /// Selects language of descriptions in help, manual page and completions. Arguments without
+    /// description in the language (see ParsableValueArgument::add_localized_description) use
+    /// the default one.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable_boxed(Box::new(
+    ///     ParsableValueArgument::new_path(ArgumentIdentification::Short('o'))
+    ///         .set_description("Output file")
+    ///         .add_localized_description("pl", "Plik wynikowy"),
+    /// ));
+    /// args_list.set_language("pl-PL");
+    /// assert_eq!(args_list.help_message(), "Options:\n  -o <PATH>  Plik wynikowy\n");
+    /// args_list.set_language("de");
+    /// assert_eq!(args_list.help_message(), "Options:\n  -o <PATH>  Output file\n");
+    /// ```
+    pub fn set_language(&mut self, language: &str) {
+        self.language = Some(String::from(language));
+    }
+
+    /// Descriptions of registered arguments without hidden tags.
+    pub(crate) fn visible_specs(&self) -> std::vec::IntoIter<ArgSpec> {
+        self.iter_specs()
+            .filter(|spec| !spec.has_any_tag(&self.hidden_tags))
+            .collect::<Vec<ArgSpec>>()
+            .into_iter()
+    }
+
+    /// Replaces template used by help_message.
+    pub fn set_help_template(&mut self, template: HelpTemplate) {
+        self.help_template = template;
+    }
+
+    /// Writes help message to given writer instead of returning it.
+    pub fn write_help(&self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(self.help_message().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fill, trim_description, wrap};
+    use crate::{
+        argument::{
+            legacy_argument::{ArgType, Argument},
+            parsable_argument::ParsableValueArgument,
+            ArgumentIdentification,
+        },
+        completion::Shell,
+        ArgumentList,
+    };
+
+    #[test]
+    fn help_message_works() {
+        let mut args_list = ArgumentList::new();
+        let mut argument_int = ParsableValueArgument::new_integer(ArgumentIdentification::Both(
+            'n',
+            String::from("number"),
+        ))
+        .set_description("Number of iterations");
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        args_list.append_arg(Argument::new_long("path", ArgType::Value));
+        args_list.register_parsable(&mut argument_int);
+        assert_eq!(
+            args_list.help_message(),
+            "Options:\n  -d\n  --path <VALUE>\n  -n, --number <INT>  Number of iterations\n"
+        );
+    }
+
+    #[test]
+    fn multi_paragraph_description_works() {
+        assert_eq!(
+            trim_description("\n    First line\n      indented  \n\n\n    Second paragraph\n  "),
+            "First line\n  indented\n\nSecond paragraph"
+        );
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_string(ArgumentIdentification::Short('p')).set_description(
+                "
+                Path to the input file.
+
+                Use - to read standard input.
+                ",
+            ),
+        ));
+        args_list.append_arg(Argument::new_long("debug", ArgType::Flag));
+        assert_eq!(
+            args_list.help_message(),
+            "Options:\n  --debug\n  -p <VALUE>  Path to the input file.\n\n              Use - to read standard input.\n"
+        );
+    }
+
+    #[test]
+    fn hidden_tags_work() {
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("jobs")))
+                .set_required(true)
+                .add_tag("experimental")
+                .add_example("--jobs 4"),
+        ));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        assert!(args_list.parse_args(Vec::new()).is_err());
+        args_list.set_hidden_tags(&["internal", "experimental"]);
+        assert_eq!(args_list.help_message(), "Options:\n  -v\n");
+        assert!(!args_list
+            .generate_completions(Shell::Bash, "app")
+            .contains("--jobs"));
+        assert!(!args_list.generate_man_page("app").contains("jobs"));
+        assert!(args_list.parse_args(Vec::new()).is_ok());
+        args_list.set_hidden_tags(&[]);
+        assert!(args_list.help_message().contains("--jobs <INT>"));
+    }
+
+    #[test]
+    fn wrap_works() {
+        assert_eq!(wrap("aa bb cc\n  dd ee", 5), "aa bb\ncc\n  dd\n  ee");
+        assert_eq!(wrap("abcdefgh ij", 4), "abcdefgh\nij");
+    }
+
+    // Output of render_help_plain is covered by semver guarantee, so this test must only be
+    // changed together with the major version.
+    #[test]
+    fn render_help_plain_is_stable() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(Some('d'), Some("debug"), ArgType::Flag).unwrap());
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_path(ArgumentIdentification::Both(
+                'c',
+                String::from("config"),
+            ))
+            .set_description(
+                "Configuration file read before any other argument is applied. Values given on \
+                 the command line take precedence over values from this file.",
+            )
+            .add_example("-c ./app.toml"),
+        ));
+        args_list.set_after_help("Report bugs to the issue tracker.");
+        assert_eq!(
+            args_list.render_help_plain(),
+            "Options:
+  -d, --debug
+  -c, --config <PATH>  Configuration file read before any other argument is
+                       applied. Values given on the command line take precedence
+                       over values from this file.
+
+Examples:
+  -c ./app.toml
+
+Report bugs to the issue tracker.
+"
+        );
+    }
+
+    #[test]
+    fn fill_works() {
+        assert_eq!(
+            fill("{a} {b} {c} {a", &[("a", "{b}"), ("b", "2")]),
+            "{b} 2 {c} {a"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasherDefault, Hasher},
+};
+
+use crate::argument::legacy_argument::{ArgType, Argument};
+
+/// Hashes name with 64-bit FNV-1a. Cheap for short strings and stable between runs.
+fn hash_name(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Hasher passing precomputed name hashes through, so they are not hashed again by HashMap.
+#[derive(Debug, Default)]
+struct PrecomputedHasher(u64);
+
+impl Hasher for PrecomputedHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        // Only u64 keys are hashed, through write_u64.
+        for byte in bytes {
+            self.0 = (self.0 << 8) | u64::from(*byte);
+        }
+    }
+
+    fn write_u64(&mut self, hash: u64) {
+        self.0 = hash;
+    }
+}
+
+///
+/// Long names interned in a single buffer, each identified by its position in `spans`. Ids are
+/// grouped by precomputed hash, so a lookup hashes the searched name once and compares bytes only
+/// with names having the same hash and length. Interning and looking up a name take constant time
+/// on average.
+#[derive(Debug, Default)]
+struct NameTable {
+    text: String,
+    /// Start and length of every name in text.
+    spans: Vec<(usize, usize)>,
+    ids: HashMap<u64, Vec<usize>, BuildHasherDefault<PrecomputedHasher>>,
+}
+
+impl NameTable {
+    fn get(&self, name: &str) -> Option<usize> {
+        self.find(hash_name(name), name)
+    }
+
+    fn find(&self, hash: u64, name: &str) -> Option<usize> {
+        self.ids
+            .get(&hash)?
+            .iter()
+            .find(|id| self.spans[**id].1 == name.len() && self.name(**id) == name)
+            .copied()
+    }
+
+    fn name(&self, id: usize) -> &str {
+        let (start, len) = self.spans[id];
+        &self.text[start..start + len]
+    }
+
+    /// Returns id of given name, adding it when it is not present yet.
+    fn intern(&mut self, name: &str) -> usize {
+        let hash = hash_name(name);
+        if let Some(id) = self.find(hash, name) {
+            return id;
+        }
+        let id = self.spans.len();
+        self.ids.entry(hash).or_default().push(id);
+        self.spans.push((self.text.len(), name.len()));
+        self.text.push_str(name);
+        id
+    }
+
+    fn clear(&mut self) {
+        self.text.clear();
+        self.spans.clear();
+        self.ids.clear();
+    }
+}
+
+///
+/// Names and types of legacy arguments stored as columns, one entry per position in
+/// ArgumentList::arguments, with long names interned. Lookups while parsing read only the compact
+/// columns and name tables, and touch the argument only once it is found. Arguments themselves
+/// stay in the public vector, which the columns mirror, so code modifying the vector directly
+/// keeps working. Index is out of date when the vector no longer matches the columns; lookups
+/// verify found positions and, when length of the vector changed, scan it for missed names.
+/// Parsing refreshes the index first.
+#[derive(Debug, Default)]
+pub(crate) struct ArgumentIndex {
+    shorts: Vec<Option<char>>,
+    longs: Vec<Option<usize>>,
+    arg_types: Vec<ArgType>,
+    /// First position of every short name.
+    short_positions: HashMap<char, usize>,
+    names: NameTable,
+    /// First position of every interned long name, by id.
+    long_positions: Vec<usize>,
+}
+
+impl ArgumentIndex {
+    /// Records argument stored at given position, which is the next one not indexed yet. Earlier
+    /// arguments with the same name win, like in linear search.
+    pub(crate) fn insert(&mut self, position: usize, argument: &Argument) {
+        debug_assert_eq!(position, self.shorts.len());
+        let short = *argument.short();
+        if let Some(short) = short {
+            self.short_positions.entry(short).or_insert(position);
+        }
+        let long = argument.long().as_deref().map(|name| {
+            let id = self.names.intern(name);
+            if id == self.long_positions.len() {
+                self.long_positions.push(position);
+            }
+            id
+        });
+        self.shorts.push(short);
+        self.longs.push(long);
+        self.arg_types.push(*argument.arg_type());
+    }
+
+    /// Recreates index from scratch.
+    pub(crate) fn rebuild(&mut self, arguments: &[Argument]) {
+        self.shorts.clear();
+        self.longs.clear();
+        self.arg_types.clear();
+        self.short_positions.clear();
+        self.names.clear();
+        self.long_positions.clear();
+        for (position, argument) in arguments.iter().enumerate() {
+            self.insert(position, argument);
+        }
+    }
+
+    /// Rebuilds index unless every column matches the arguments, also catching arguments replaced
+    /// without changing length of the list. Takes time proportional to number of arguments.
+    pub(crate) fn refresh(&mut self, arguments: &[Argument]) {
+        let fresh = self.shorts.len() == arguments.len()
+            && arguments
+                .iter()
+                .enumerate()
+                .all(|(position, argument)| self.matches(position, argument));
+        if !fresh {
+            self.rebuild(arguments);
+        }
+    }
+
+    /// Checks if columns at given position describe the argument.
+    fn matches(&self, position: usize, argument: &Argument) -> bool {
+        let long = match (self.longs.get(position), argument.long()) {
+            (Some(Some(id)), Some(name)) => self.names.name(*id) == name,
+            (Some(None), None) => true,
+            _ => false,
+        };
+        long && self.shorts.get(position) == Some(argument.short())
+            && self.arg_types.get(position) == Some(argument.arg_type())
+    }
+
+    /// Checks if every indexed argument is a flag. Valid only for refreshed index.
+    pub(crate) fn only_flags(&self) -> bool {
+        self.arg_types
+            .iter()
+            .all(|arg_type| *arg_type == ArgType::Flag)
+    }
+
+    pub(crate) fn find_short(&self, arguments: &[Argument], name: char) -> Option<usize> {
+        let is_match = |argument: &Argument| argument.short() == &Some(name);
+        match self.short_positions.get(&name) {
+            Some(position) if arguments.get(*position).is_some_and(is_match) => Some(*position),
+            None if self.shorts.len() == arguments.len() => None,
+            _ => arguments.iter().position(is_match),
+        }
+    }
+
+    pub(crate) fn find_long(&self, arguments: &[Argument], name: &str) -> Option<usize> {
+        let is_match = |argument: &Argument| argument.long().as_deref() == Some(name);
+        match self.names.get(name).map(|id| self.long_positions[id]) {
+            Some(position) if arguments.get(position).is_some_and(is_match) => Some(position),
+            None if self.shorts.len() == arguments.len() => None,
+            _ => arguments.iter().position(is_match),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ArgumentIndex, NameTable};
+    use crate::argument::legacy_argument::{ArgType, Argument};
+
+    #[test]
+    fn find_works_with_outdated_index() {
+        let mut arguments = vec![
+            Argument::new(Some('a'), Some("all"), ArgType::Flag).unwrap(),
+            Argument::new_short('b', ArgType::Flag),
+        ];
+        let mut index = ArgumentIndex::default();
+        index.rebuild(&arguments);
+        assert_eq!(index.find_short(&arguments, 'b'), Some(1));
+        assert_eq!(index.find_long(&arguments, "all"), Some(0));
+        assert_eq!(index.find_long(&arguments, "none"), None);
+        arguments.remove(0);
+        assert_eq!(index.find_short(&arguments, 'b'), Some(0));
+        assert_eq!(index.find_long(&arguments, "all"), None);
+        arguments.push(Argument::new_long("new", ArgType::Flag));
+        index.refresh(&arguments);
+        assert_eq!(index.find_long(&arguments, "new"), Some(1));
+        assert_eq!(index.find_short(&arguments, 'a'), None);
+    }
+
+    #[test]
+    fn refresh_detects_replaced_arguments() {
+        let mut arguments = vec![
+            Argument::new_long("all", ArgType::Flag),
+            Argument::new_short('b', ArgType::Flag),
+        ];
+        let mut index = ArgumentIndex::default();
+        index.rebuild(&arguments);
+        assert!(index.only_flags());
+        arguments[1] = Argument::new_short('b', ArgType::Value);
+        index.refresh(&arguments);
+        assert!(!index.only_flags());
+        arguments[0] = Argument::new_long("any", ArgType::Flag);
+        index.refresh(&arguments);
+        assert_eq!(index.find_long(&arguments, "any"), Some(0));
+        assert_eq!(index.find_long(&arguments, "all"), None);
+    }
+
+    #[test]
+    fn name_table_works() {
+        let mut table = NameTable::default();
+        let names: Vec<String> = (0..100).map(|i| format!("name-{}", i)).collect();
+        for (id, name) in names.iter().enumerate() {
+            assert_eq!(table.intern(name), id);
+        }
+        // Interning the same name again returns its id.
+        assert_eq!(table.intern("name-7"), 7);
+        for (id, name) in names.iter().enumerate() {
+            assert_eq!(table.get(name), Some(id));
+            assert_eq!(table.name(id), name);
+        }
+        assert_eq!(table.get("name-"), None);
+        assert_eq!(table.get("name-100"), None);
+        table.clear();
+        assert_eq!(table.get("name-0"), None);
+    }
+}
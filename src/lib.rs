@@ -1,8 +1,75 @@
 pub mod argument;
+pub mod command;
+pub mod completion;
+pub mod config;
+pub mod cursor;
+pub mod describe;
+pub mod error;
+mod flags;
+pub mod form;
+mod generate;
+pub mod help;
+mod index;
+pub mod lint;
+mod man;
+pub mod migrations;
+pub mod options;
+pub mod parsed;
+mod posix;
+mod preset;
+pub mod provider;
+mod scope;
+#[cfg(feature = "script")]
+pub mod script;
+pub mod source;
+pub mod stats;
+mod suggest;
+pub mod token;
+pub mod tree;
+pub mod windows;
 
-use std::{borrow::BorrowMut, env, iter::Peekable};
+use std::{
+    any::Any,
+    collections::HashMap,
+    env,
+    ffi::OsString,
+    io::Write,
+    panic::{self, AssertUnwindSafe},
+    process,
+};
 
-use argument::{legacy_argument::Argument, parsable_argument::HandleableArgument};
+use argument::{
+    legacy_argument::{ArgType, Argument},
+    parsable_argument::{DynArgument, HandleableArgument},
+    spec::ArgSpec,
+    ArgumentIdentification,
+};
+use config::{ParserConfig, ReservedNamePolicy};
+use cursor::{InputCursor, ParseContext};
+use error::{Limit, ParseError, PartialParse};
+use help::HelpTemplate;
+use index::ArgumentIndex;
+use migrations::{Migration, MigrationWarning};
+use options::{BlankTokens, ErrorMode, LoneDash, ParserOptions};
+use parsed::{ParseOutcome, ParsedArgs, ParsedOccurrence};
+use preset::PresetUse;
+use provider::ValueProvider;
+use scope::Scope;
+use stats::{ParseReport, ParseStats};
+use token::{DefaultTokenizer, Token, Tokenizer};
+
+/// Signature of closures validating complete parse result.
+type Validator = dyn Fn(&ParsedArgs) -> Result<(), ParseError>;
+
+/// Reason for which parse_or_exit terminates the process.
+#[derive(Debug, PartialEq)]
+enum ExitReason {
+    Help,
+    Artifact(String),
+    UsageError(ParseError),
+    InternalError,
+    Misconfigured(String),
+}
 
 ///
 /// Acumulates arguments into list which then can be fed to parse.
@@ -15,10 +82,48 @@ use argument::{legacy_argument::Argument, parsable_argument::HandleableArgument}
 /// args_list.append_arg(Argument::new(Some('p'), None, ArgType::Value).unwrap());
 /// args_list.append_arg(Argument::new(Some('l'), Some("an-list"), ArgType::ValueList).unwrap());
 /// ```
+///
+/// # Performance
+/// Names and types of legacy arguments are kept in an internal index as columns, one entry per
+/// argument, so lookup time does not grow with number of arguments and parsing reads only the
+/// compact columns until an argument is found. Arguments with their values stay in the public
+/// `arguments` vector, which code can modify directly. Index is brought up to date when parsing
+/// starts; outside of parsing, searches scan the vector when its length changed since it was
+/// indexed. Long names are interned in a single string table with precomputed hashes: looking up
+/// a name hashes it once and compares bytes only with names of equal hash and length, and both
+/// registering and looking up a name take constant time on average, including names which are
+/// not legacy arguments. Parsable arguments are searched linearly in order they were registered.
 pub struct ArgumentList<'a> {
+    /// Values not attached to any argument, in input order.
     pub dangling_values: Vec<String>,
     pub arguments: Vec<Argument>,
     pub parsable_arguments: Vec<&'a mut (dyn HandleableArgument<'a> + 'a)>,
+    config: ParserConfig,
+    options: ParserOptions,
+    parsed: ParsedArgs,
+    validators: Vec<Box<Validator>>,
+    index: ArgumentIndex,
+    boxed_arguments: Vec<Box<dyn DynArgument>>,
+    scopes: Vec<Scope<'a>>,
+    tokenizer: Box<dyn Tokenizer>,
+    stats: Option<ParseStats>,
+    presets: Vec<(String, Vec<String>)>,
+    help_template: HelpTemplate,
+    after_help: Option<String>,
+    hidden_tags: Vec<String>,
+    value_providers: Vec<Box<dyn ValueProvider>>,
+    migrations: Vec<Migration>,
+    migration_warnings: Vec<MigrationWarning>,
+    value_warnings: Vec<String>,
+    spec_version: Option<String>,
+    language: Option<String>,
+    terminal: Vec<ArgumentIdentification>,
+    /// Whether `-h` and `--help` in place of an option request built-in help (parse_for_exit).
+    detect_help: bool,
+    help_requested: bool,
+    /// Reads environment variables consulted while parsing (e.g. POSIXLY_CORRECT). Replaced by
+    /// tests, which must not share process environment.
+    env_var: fn(&str) -> Option<OsString>,
 }
 
 impl<'a> ArgumentList<'a> {
@@ -33,13 +138,63 @@ impl<'a> ArgumentList<'a> {
             dangling_values: Vec::new(),
             arguments: Vec::new(),
             parsable_arguments: Vec::new(),
+            config: ParserConfig::default(),
+            options: ParserOptions::default(),
+            parsed: ParsedArgs::default(),
+            validators: Vec::new(),
+            index: ArgumentIndex::default(),
+            boxed_arguments: Vec::new(),
+            scopes: Vec::new(),
+            tokenizer: Box::new(DefaultTokenizer),
+            stats: None,
+            presets: Vec::new(),
+            help_template: HelpTemplate::default(),
+            after_help: None,
+            hidden_tags: Vec::new(),
+            value_providers: Vec::new(),
+            migrations: Vec::new(),
+            migration_warnings: Vec::new(),
+            value_warnings: Vec::new(),
+            spec_version: None,
+            language: None,
+            terminal: Vec::new(),
+            detect_help: false,
+            help_requested: false,
+            env_var: |variable| env::var_os(variable),
         }
     }
 
+    /// Replaces options changing how input is parsed.
+    pub fn set_options(&mut self, options: ParserOptions) {
+        self.options = options;
+    }
+
+    /// Returns options used while parsing.
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// Replaces tokenizer deciding which input values are arguments. DefaultTokenizer is used by
+    /// default.
+    pub fn set_tokenizer<T: Tokenizer + 'static>(&mut self, tokenizer: T) {
+        self.tokenizer = Box::new(tokenizer);
+    }
+
+    /// Replaces configuration used by this list.
+    pub fn set_config(&mut self, config: ParserConfig) {
+        self.config = config;
+    }
+
+    /// Returns configuration used by this list.
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
     /**
     Append argument to the end of the list.
     */
     pub fn append_arg(&mut self, argument: Argument) {
+        self.index.insert(self.arguments.len(), &argument);
         self.arguments.push(argument);
     }
 
@@ -54,100 +209,247 @@ impl<'a> ArgumentList<'a> {
     Search arguments by short name.
     */
     pub fn search_by_short_name(&self, name: char) -> Option<&Argument> {
-        for x in &self.arguments {
-            match x.short() {
-                Some(symbol) => {
-                    if symbol == &name {
-                        return Some(x);
-                    }
-                }
-                None => (),
-            };
-        }
-        Option::None
+        let position = self.index.find_short(&self.arguments, name)?;
+        self.arguments.get(position)
     }
 
     /**
     Search arguments by short name.
     */
     pub fn search_by_short_name_mut(&mut self, name: char) -> Option<&mut Argument> {
-        for x in &mut self.arguments {
-            match x.short() {
-                Some(symbol) => {
-                    if symbol == &name {
-                        return Some(x);
-                    }
-                }
-                None => (),
-            };
+        let position = self.index.find_short(&self.arguments, name)?;
+        self.arguments.get_mut(position)
+    }
+
+    /// Search arguments by name typed by the user (`-p`, `--path`) or given without prefix. Name
+    /// without prefix is looked up as long name first and, when it is a single character, as
+    /// short name.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+    /// for name in ["p", "-p", "path", "--path"] {
+    ///     assert!(args_list.search(name).is_some());
+    /// }
+    /// assert!(args_list.search("-path").is_none());
+    /// ```
+    pub fn search(&self, name: &str) -> Option<&Argument> {
+        if let Some(long) = name.strip_prefix("--") {
+            return self.search_by_long_name(long);
         }
-        Option::None
+        let short_only = name.starts_with('-');
+        let name = name.strip_prefix('-').unwrap_or(name);
+        let by_long = if short_only {
+            None
+        } else {
+            self.search_by_long_name(name)
+        };
+        let mut chars = name.chars();
+        by_long.or_else(|| match (chars.next(), chars.next()) {
+            (Some(c), None) => self.search_by_short_name(c),
+            _ => None,
+        })
     }
 
     fn handle_parsable_short_name(
         &mut self,
+        word: &str,
         name: char,
-        input_iter: &mut Peekable<&mut std::slice::Iter<'_, String>>,
-    ) -> Result<bool, String> {
-        for x in &mut self.parsable_arguments {
-            if x.is_by_short(name) {
-                x.handle(input_iter)?;
-                return Result::Ok(true);
-            }
-        }
-        return Result::Ok(false);
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<Option<ArgumentIdentification>, ParseError> {
+        self.handle_parsable(word, |x| x.is_by_short(name), input_iter)
     }
 
     fn handle_parsable_long_name(
         &mut self,
+        word: &str,
         name: &str,
-        input_iter: &mut Peekable<&mut std::slice::Iter<'_, String>>,
-    ) -> Result<bool, String> {
-        for x in &mut self.parsable_arguments {
-            if x.is_by_long(name) {
-                x.handle(input_iter)?;
-                return Result::Ok(true);
-            }
-        }
-        return Result::Ok(false);
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<Option<ArgumentIdentification>, ParseError> {
+        self.handle_parsable(word, |x| x.is_by_long(name), input_iter)
     }
 
+    /**
+    Search arguments by long name. Name can be given with `--` prefix.
+    */
     pub fn search_by_long_name(&self, name: &str) -> Option<&Argument> {
-        for x in &self.arguments {
-            match x.long() {
-                Some(ref long_name) => {
-                    if long_name == name {
-                        return Option::Some(x);
-                    }
-                }
-                None => (),
-            }
-        }
-        Option::None
+        let name = name.strip_prefix("--").unwrap_or(name);
+        let position = self.index.find_long(&self.arguments, name)?;
+        self.arguments.get(position)
     }
 
     /**
-    Search arguments by long name.
+    Search arguments by long name. Name can be given with `--` prefix.
     */
     pub fn search_by_long_name_mut(&mut self, name: &str) -> Option<&mut Argument> {
-        for x in &mut self.arguments {
-            match x.long() {
-                Some(ref long_name) => {
-                    if long_name == name {
-                        return Option::Some(x);
+        let name = name.strip_prefix("--").unwrap_or(name);
+        let position = self.index.find_long(&self.arguments, name)?;
+        self.arguments.get_mut(position)
+    }
+
+    /// Handles group of short options. Every option except the last one must not take any value.
+    fn handle_bundle(
+        &mut self,
+        word: &str,
+        options: &[char],
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<Vec<ArgumentIdentification>, ParseError> {
+        let mut handled = Vec::new();
+        let empty: Vec<String> = Vec::new();
+        for (i, option) in options.iter().enumerate() {
+            let is_last = i + 1 == options.len();
+            let unknown = || ParseError::UnknownBundledArgument {
+                bundle: String::from(word),
+                option: *option,
+            };
+            if let Some(argument) = self.search_by_short_name_mut(*option) {
+                if !is_last && argument.arg_type() != &ArgType::Flag {
+                    return Err(ParseError::illegal_bundle(word, *option));
+                }
+                let had_input = input_iter.peek().is_some();
+                argument.add_value_from(input_iter).map_err(|message| {
+                    ParseError::handler(&ArgSpec::of_legacy(argument), had_input, None, message)
+                })?;
+                handled.push(argument.identification());
+            } else if is_last {
+                match self.handle_parsable_short_name(word, *option, input_iter)? {
+                    Some(identification) => handled.push(identification),
+                    None => return Err(unknown()),
+                }
+            } else {
+                // Parsable arguments which fail without any input are considered to take a value.
+                match self.handle_parsable_short_name(word, *option, &mut InputCursor::new(&empty))
+                {
+                    Result::Ok(Some(identification)) => handled.push(identification),
+                    Result::Ok(None) => return Err(unknown()),
+                    Result::Err(_) => return Err(ParseError::illegal_bundle(word, *option)),
+                }
+            }
+        }
+        Ok(handled)
+    }
+
+    /// Handles argument identified by short name.
+    fn handle_short_name(
+        &mut self,
+        word: &str,
+        name: char,
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<ArgumentIdentification, ParseError> {
+        match self.search_by_short_name_mut(name) {
+            Some(argument) => {
+                let had_input = input_iter.peek().is_some();
+                argument.add_value_from(input_iter).map_err(|message| {
+                    ParseError::handler(&ArgSpec::of_legacy(argument), had_input, None, message)
+                })?;
+                Ok(argument.identification())
+            }
+            None => match self.handle_parsable_short_name(word, name, input_iter)? {
+                Some(identification) => Ok(identification),
+                None => self.handle_scoped_short_name(word, name, input_iter),
+            },
+        }
+    }
+
+    /// Handles argument identified by long name.
+    fn handle_long_name(
+        &mut self,
+        word: &str,
+        name: &str,
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<ArgumentIdentification, ParseError> {
+        match self.search_by_long_name_mut(name) {
+            Some(argument) => {
+                let had_input = input_iter.peek().is_some();
+                argument.add_value_from(input_iter).map_err(|message| {
+                    ParseError::handler(&ArgSpec::of_legacy(argument), had_input, None, message)
+                })?;
+                Ok(argument.identification())
+            }
+            None => match self.handle_parsable_long_name(word, name, input_iter)? {
+                Some(identification) => Ok(identification),
+                None => self.handle_scoped_long_name(word, name, input_iter),
+            },
+        }
+    }
+
+    /// Handles argument given together with its value (`--name=value`). Argument has to consume
+    /// exactly that value.
+    fn handle_long_name_with_value(
+        &mut self,
+        prefix: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<ArgumentIdentification, ParseError> {
+        let word = format!("{}{}", prefix, name);
+        let inline = [String::from(value)];
+        let mut inline_input = InputCursor::new_inline(&inline);
+        let identification = self.handle_long_name(&word, name, &mut inline_input)?;
+        if inline_input.peek().is_some() {
+            return Err(ParseError::UnexpectedValue {
+                argument: word,
+                value: String::from(value),
+            });
+        }
+        Ok(identification)
+    }
+
+    /// Classifies input value with configured tokenizer. Numeric-looking long options (`--123`,
+    /// `--10..20`) are values unless the number is a registered long name, so negative ranges
+    /// and similar values can be passed without `--` separator.
+    fn tokenize<'w>(&self, word: &'w str) -> Token<'w> {
+        match self.tokenizer.tokenize(word, &self.options) {
+            Token::Value => match word.strip_prefix("--") {
+                Some(long) if long.starts_with(|c: char| c.is_ascii_digit()) => {
+                    let (name, value) = match long.split_once('=') {
+                        Some((name, value)) => (name, Some(value)),
+                        None => (long, None),
+                    };
+                    if self.is_known_long(name) {
+                        Token::Long {
+                            prefix: "--",
+                            name,
+                            value,
+                        }
+                    } else {
+                        Token::Value
                     }
                 }
-                None => (),
+                _ => Token::Value,
+            },
+            token => token,
+        }
+    }
+
+    /// Saves value not attached to any argument. Lone `-` is handled as selected with
+    /// ParserOptions::lone_dash.
+    fn handle_dangling_value(&mut self, word: &str, index: usize) -> Result<(), ParseError> {
+        match (word, self.options.lone_dash) {
+            ("-", LoneDash::Stdin) => self.parsed.push_stdin(index),
+            ("-", LoneDash::Error) => return Err(ParseError::LoneDash(index)),
+            _ => {
+                self.append_dangling_value(word);
+                self.parsed.push_positional(word, index);
             }
         }
-        Option::None
+        Ok(())
     }
 
-    /// Returns vector of all generated dangling values (values not attached to any argument)
+    /// Returns vector of all generated dangling values (values not attached to any argument) in
+    /// order they were given.
     pub fn get_dangling_values(&self) -> &Vec<String> {
         &self.dangling_values
     }
 
+    /// Returns counters collected during the last parse. Available only when enabled with
+    /// ParserOptions::collect_stats and the whole input was processed.
+    pub fn stats(&self) -> Option<&ParseStats> {
+        self.stats.as_ref()
+    }
+
     /// Function that does all the parsing. You need to feed user input as an argument. Handles both
     /// legacy type arguments and parsable value arguments. When used with mixed type arguments, parsable
     /// arguments cannot be accessed before all borrows to ArgumentList are released or it gets dropped.
@@ -173,106 +475,2135 @@ impl<'a> ArgumentList<'a> {
     /// // Then access parsable value arguments since last reference was used.
     /// argument_str.first_value();
     /// ```
-    pub fn parse_args(&mut self, input: Vec<String>) -> Result<(), String> {
-        let mut iter = input.iter();
-        let mut input_iter = iter.borrow_mut().peekable();
+    ///
+    /// With ErrorMode::Aggregate parsing continues after errors and all of them are returned in
+    /// ParseError::Multiple.
+    ///
+    /// With `tracing` feature every parse is traced in `parse_args` span, with events for each
+    /// classified input value, matched argument and handler outcome. Values of secret arguments
+    /// are never traced.
+    pub fn parse_args(&mut self, input: Vec<String>) -> Result<(), ParseError> {
+        self.parse_args_outcome(input).map(|_| ())
+    }
+
+    /// Parses input like parse_args, but tells whether parsing was cut short by a terminal
+    /// argument (see set_terminal).
+    pub fn parse_args_outcome(&mut self, input: Vec<String>) -> Result<ParseOutcome, ParseError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("parse_args", values = input.len()).entered();
+        let first_item = self.parsed.iter_in_order().len();
+        let result = self
+            .prepare_input(input)
+            .and_then(|input| self.parse_prepared(&input));
+        self.report_parse(first_item, result.as_ref().err());
+        result
+    }
+
+    /// Parses input like parse_args in ErrorMode::Aggregate, but never fails. Values which could
+    /// not be parsed are skipped and their errors are returned, so programs can run with defaults
+    /// and report warnings instead of refusing to start. When input cannot be parsed at all (e.g.
+    /// unknown preset), only defaults are applied.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut argument_port = ParsableValueArgument::new_integer(ArgumentIdentification::Long(
+    ///     String::from("port"),
+    /// ))
+    /// .set_default(8080);
+    /// let mut argument_host = ParsableValueArgument::new_string(ArgumentIdentification::Long(
+    ///     String::from("host"),
+    /// ));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut argument_port);
+    /// args_list.register_parsable(&mut argument_host);
+    /// let input = vec!["--port", "eighty", "--host", "localhost"];
+    /// let partial = args_list.parse_args_best_effort(input.iter().map(|s| String::from(*s)).collect());
+    /// assert!(!partial.is_complete());
+    /// assert_eq!(partial.errors_of("port").len(), 1);
+    /// assert!(partial.errors_of("host").is_empty());
+    /// drop(args_list);
+    /// assert_eq!(argument_port.first_value(), Some(&8080));
+    /// assert_eq!(argument_host.first_value().unwrap(), "localhost");
+    /// ```
+    pub fn parse_args_best_effort(&mut self, input: Vec<String>) -> PartialParse {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("parse_args_best_effort", values = input.len()).entered();
+        let error_mode = self.options.error_mode;
+        self.options.error_mode = ErrorMode::Aggregate;
+        let first_item = self.parsed.iter_in_order().len();
+        let mut errors = Vec::new();
+        let input = self.prepare_input(input).unwrap_or_else(|error| {
+            errors.push(error);
+            Vec::new()
+        });
+        if let Err(error) = self.parse_prepared(&input) {
+            errors.extend(error.errors().into_iter().cloned());
+        }
+        self.options.error_mode = error_mode;
+        let error = ParseError::Multiple(errors.clone());
+        self.report_parse(first_item, Some(&error).filter(|_| !errors.is_empty()));
+        PartialParse::new(errors)
+    }
+
+    /// Passes summary of items parsed since first_item to ParserConfig::on_parse_complete.
+    fn report_parse(&self, first_item: usize, error: Option<&ParseError>) {
+        if let Some(on_parse_complete) = self.config.on_parse_complete {
+            let items = self.parsed.iter_in_order().skip(first_item);
+            let mut report = ParseReport::new(items, error);
+            report.warnings = self
+                .migration_warnings
+                .iter()
+                .map(|warning| format!("{}", warning))
+                .chain(self.value_warnings.iter().cloned())
+                .collect();
+            on_parse_complete(&report);
+        }
+    }
+
+    /// Applies preprocessors, presets, limits and handling of blank values to input.
+    fn prepare_input(&mut self, input: Vec<String>) -> Result<Vec<String>, ParseError> {
+        self.value_warnings.clear();
+        self.migration_warnings.clear();
+        let input = self
+            .options
+            .preprocessors
+            .iter()
+            .fold(input, |input, preprocess| preprocess(input));
+        self.check_input_limits(&input)?;
+        self.handle_blank_tokens(input)
+    }
+
+    /// Parses input returned by prepare_input.
+    fn parse_prepared(&mut self, input: &[String]) -> Result<ParseOutcome, ParseError> {
+        let fail_fast = self.options.error_mode == ErrorMode::FailFast;
+        let mut errors = Vec::new();
+        let mut stats = ParseStats {
+            tokens: input.len(),
+            ..ParseStats::default()
+        };
+        self.stats = None;
+        // Arguments could be added to or removed from the public list directly.
+        self.index.refresh(&self.arguments);
+        let first_occurrence = self.parsed.occurrences().count();
+        for spec in self.iter_specs() {
+            if let Some(default) = spec.default_value() {
+                self.parsed
+                    .set_default(source::key(spec.identification()), default);
+            }
+        }
+        let terminated = if self.is_flag_only() {
+            self.parse_flag_tokens(input, &mut errors, &mut stats)
+        } else {
+            self.parse_tokens(input, &mut errors, &mut stats)
+        };
+        let scopes = self.activate_scopes(first_occurrence);
+        let secrets: Vec<String> = self
+            .active_parsables()
+            .iter()
+            .filter(|x| x.is_secret())
+            .map(|x| source::key(x.identification()))
+            .collect();
+        for key in secrets {
+            self.parsed.set_secret(key);
+        }
+        // Warnings are collected even when parsing failed, so they never leak into next parse.
+        let mut value_warnings = Vec::new();
+        for x in self.active_parsables_mut() {
+            for warning in x.take_warnings() {
+                value_warnings.push(format!("{}: {}", x.identification(), warning));
+            }
+        }
+        self.value_warnings = value_warnings;
+        let terminated = terminated?;
+        if let Err(error) = scopes {
+            if fail_fast {
+                return Err(error);
+            }
+            errors.push(error);
+        }
+
+        if self.options.collect_stats {
+            self.stats = Some(stats);
+        }
+
+        if let Some(identification) = terminated {
+            if !errors.is_empty() {
+                return Err(ParseError::Multiple(errors));
+            }
+            return Ok(ParseOutcome::Terminated(identification));
+        }
+
+        if let Err(error) = self.check_list_limit() {
+            if fail_fast {
+                return Err(error);
+            }
+            errors.push(error);
+        }
+
+        let resolved = self.resolve_secrets(&mut errors)?;
+        for x in self.active_parsables() {
+            let hidden = x.tags().iter().any(|tag| self.hidden_tags.contains(tag));
+            if x.is_required()
+                && !hidden
+                && !resolved.contains(x.identification())
+                && !self
+                    .parsed
+                    .occurrences()
+                    .any(|o| o.identification().overlaps(x.identification()))
+            {
+                let error = ParseError::MissingArgument(format!("{}", x.identification()));
+                if fail_fast {
+                    return Err(error);
+                }
+                errors.push(error);
+            }
+        }
+
+        for x in self.active_parsables_mut() {
+            if let Result::Err(message) = x.finalize() {
+                let error = ParseError::InvalidValues {
+                    argument: format!("{}", x.identification()),
+                    message,
+                };
+                if fail_fast {
+                    return Err(error);
+                }
+                errors.push(error);
+            }
+        }
+
+        for validator in &self.validators {
+            if let Err(error) = validator(&self.parsed) {
+                if fail_fast {
+                    return Err(error);
+                }
+                errors.push(error);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(ParseError::Multiple(errors));
+        }
+        // return arguments list with filled parsed values
+        Ok(ParseOutcome::Complete)
+    }
+
+    /// Handles every input value. Errors are returned immediately in ErrorMode::FailFast and
+    /// collected in errors otherwise. Returns terminal argument which stopped parsing.
+    fn parse_tokens(
+        &mut self,
+        input: &[String],
+        errors: &mut Vec<ParseError>,
+        stats: &mut ParseStats,
+    ) -> Result<Option<ArgumentIdentification>, ParseError> {
+        let first_occurrence = self.parsed.occurrences().count();
+        self.parse_token_list(input, None, first_occurrence, errors, stats)
+    }
+
+    /// Version of parse_tokens handling values of given preset, or user input when None.
+    /// first_occurrence is the first occurrence recorded by the current parse.
+    fn parse_token_list(
+        &mut self,
+        input: &[String],
+        preset: Option<&PresetUse>,
+        first_occurrence: usize,
+        errors: &mut Vec<ParseError>,
+        stats: &mut ParseStats,
+    ) -> Result<Option<ArgumentIdentification>, ParseError> {
+        let fail_fast = self.options.error_mode == ErrorMode::FailFast;
+        let strict_ordering = self.is_strict_ordering();
+        let mut input_iter = InputCursor::new(input);
+        let mut only_positionals = false;
+        // Whether `--` ending options was given in compliance mode.
+        let mut delimited = false;
+        // Values after `--` are never migrated and never request built-in help, even when `--`
+        // is kept as a value.
+        let mut dashes_given = false;
         while let Some(word) = input_iter.next() {
-            // Check if word is a short argument, long argument or dangling value
-            let word_length = word.chars().count();
-            if word_length == 2 {
-                if word.chars().nth(0).expect("first letter") == '-'
-                    && word
-                        .chars()
-                        .nth(1)
-                        .expect(&format!("{}", word_length))
-                        .is_alphabetic()
-                {
-                    // Add value to argument identified by short name
-                    match self.search_by_short_name_mut(word.chars().nth(1).unwrap()) {
-                        Some(argument) => {
-                            argument.add_value(&mut input_iter)?;
-                        }
-                        None => {
-                            if !self.handle_parsable_short_name(
-                                word.chars().nth(1).unwrap(),
-                                &mut input_iter,
-                            )? {
-                                return Err(format!(
-                                    "Could not find argument identified by {}.",
-                                    word
-                                ));
-                            }
-                        }
-                    };
-                } else {
-                    // Add as dangling value
-                    self.append_dangling_value(word);
+            let values_start = input_iter.consumed();
+            let index = preset.map_or(values_start - 1, |preset| preset.index);
+            if only_positionals {
+                // Dangling value is stored both in dangling_values and in parsed.
+                stats.allocations += 2;
+                let result = match self.check_operand(word, &mut delimited) {
+                    Ok(true) => self.handle_dangling_value(word, index),
+                    Ok(false) => Ok(()),
+                    Err(error) => Err(error),
+                };
+                match result {
+                    Ok(()) => (),
+                    Err(error) if fail_fast => return Err(error),
+                    Err(error) => errors.push(error),
                 }
-            } else if word_length > 2 {
-                if word.chars().nth(0).unwrap() == '-'
-                    && word.chars().nth(1).unwrap() == '-'
-                    && word.chars().nth(2).unwrap().is_alphabetic()
-                {
-                    // Add value to argument identified by long name
-                    match self.search_by_long_name_mut(&word[2..word.len()]) {
-                        Some(argument) => {
-                            argument.add_value(&mut input_iter)?;
-                        }
-                        Option::None => {
-                            if !self
-                                .handle_parsable_long_name(&word[2..word.len()], &mut input_iter)?
-                            {
-                                return Err(format!(
-                                    "Could not find argument identified by {}.",
-                                    word
-                                ));
-                            }
-                        }
-                    };
+                continue;
+            }
+            // Values consumed by a failing handler are parsed again.
+            let checkpoint = input_iter.checkpoint();
+            // Value given after `=` instead of as a separate input value.
+            let mut inline_value = None;
+            let token = self.tokenize(word);
+            #[cfg(feature = "tracing")]
+            {
+                let (word, token) = self.redact_token(word, &token);
+                tracing::trace!(index = index, word = %word, ?token, "classified input value");
+            }
+            let migration = if dashes_given {
+                Ok(None)
+            } else {
+                self.migrate_token(word, &token)
+            };
+            let migrated;
+            let (word, token) = match migration {
+                Ok(Some(replacement)) => {
+                    migrated = replacement;
+                    (migrated.as_str(), self.tokenize(&migrated))
+                }
+                Ok(None) => (word.as_str(), token),
+                Err(error) if fail_fast => return Err(error),
+                Err(error) => {
+                    errors.push(error);
+                    continue;
+                }
+            };
+            if !dashes_given && self.is_help_token(&token) {
+                self.help_requested = true;
+                return Ok(Some(Self::help_identification()));
+            }
+            dashes_given |= word == "--";
+            if let Token::Long {
+                name: "preset",
+                value,
+                ..
+            } = token
+            {
+                if self.uses_presets() {
+                    let result = self
+                        .preset_values(value, &mut input_iter, preset, index)
+                        .and_then(|(values, preset)| {
+                            self.parse_token_list(
+                                &values,
+                                Some(&preset),
+                                first_occurrence,
+                                errors,
+                                stats,
+                            )
+                        });
+                    match result {
+                        Ok(None) => (),
+                        Ok(Some(identification)) => return Ok(Some(identification)),
+                        Err(error) if fail_fast => return Err(error),
+                        Err(error) => errors.push(error),
+                    }
+                    continue;
+                }
+            }
+            if !self.presets.is_empty() {
+                if let Err(error) = self.check_preset_conflict(&token, preset, first_occurrence) {
+                    if fail_fast {
+                        return Err(error);
+                    }
+                    errors.push(error);
+                    continue;
+                }
+            }
+            if let Err(error) = self.check_compliance(word, &token) {
+                if fail_fast {
+                    return Err(error);
+                }
+                errors.push(error);
+                continue;
+            }
+            stats.lookups += match &token {
+                Token::Short(_) | Token::Long { .. } => 1,
+                Token::Bundle(options) => options.len(),
+                Token::Value => 0,
+            };
+            let result = match token {
+                Token::Short(name) => self
+                    .handle_short_name(word, name, &mut input_iter)
+                    .map(|identification| vec![identification]),
+                Token::Long {
+                    prefix,
+                    name,
+                    value: Some(value),
+                } => {
+                    inline_value = Some(String::from(value));
+                    self.handle_long_name_with_value(prefix, name, value)
+                        .map(|identification| vec![identification])
+                }
+                Token::Long {
+                    name, value: None, ..
+                } => self
+                    .handle_long_name(word, name, &mut input_iter)
+                    .map(|identification| vec![identification]),
+                Token::Bundle(options) => self.handle_bundle(word, &options, &mut input_iter),
+                Token::Value => {
+                    stats.allocations += 2;
+                    match self.check_operand(word, &mut delimited) {
+                        Ok(true) => self.handle_dangling_value(word, index).map(|()| Vec::new()),
+                        Ok(false) => Ok(Vec::new()),
+                        Err(error) => Err(error),
+                    }
+                }
+            };
+            #[cfg(feature = "tracing")]
+            if let Err(error) = &result {
+                tracing::debug!(index = index, %error, "input value rejected");
+            }
+            let handled = match result {
+                Ok(handled) => handled,
+                Err(error) if fail_fast => return Err(error),
+                Err(error) => {
+                    // Continue right after the failed argument to report every problem at once.
+                    input_iter.rollback(checkpoint);
+                    errors.push(error);
+                    continue;
+                }
+            };
+            if handled.is_empty() && (strict_ordering || delimited) {
+                only_positionals = true;
+            }
+            // Only the last handled argument could consume values.
+            let values_end = input_iter.consumed();
+            let count = handled.len();
+            for (i, identification) in handled.into_iter().enumerate() {
+                let is_last = i + 1 == count;
+                let values = if is_last {
+                    match &inline_value {
+                        Some(value) => vec![value.clone()],
+                        None => input[values_start..values_end].to_vec(),
+                    }
+                } else {
+                    Vec::new()
+                };
+                // Values, their list and the form the argument was given in.
+                stats.allocations += values.len() + 2;
+                #[cfg(feature = "tracing")]
+                if self.is_secret_argument(&identification) {
+                    tracing::trace!(argument = %identification, "matched argument");
                 } else {
-                    // Add as dangling value
-                    self.append_dangling_value(word);
+                    tracing::trace!(argument = %identification, ?values, "matched argument");
+                }
+                let terminal = self.terminal.iter().any(|t| t.overlaps(&identification));
+                let mut occurrence =
+                    ParsedOccurrence::new(identification.clone(), values, index).with_form(word);
+                if is_last && inline_value.is_some() {
+                    occurrence = occurrence.with_inline_values();
+                } else if is_last {
+                    occurrence = occurrence.with_value_range(values_start..values_end);
+                }
+                if let Some(preset) = preset {
+                    occurrence = occurrence.with_preset(&preset.name);
+                }
+                self.parsed.push_occurrence(occurrence);
+                if terminal {
+                    return Ok(Some(identification));
                 }
-            } else {
-                // Add as dangling value
-                self.append_dangling_value(word);
             }
         }
+        Ok(None)
+    }
 
-        // return arguments list with filled parsed values
+    /// Rejects input with too many values or too long values before anything is parsed.
+    fn check_input_limits(&self, input: &[String]) -> Result<(), ParseError> {
+        let limits = self.options.limits;
+        if input.len() > limits.max_tokens {
+            return Err(ParseError::LimitExceeded {
+                limit: Limit::Tokens,
+                maximum: limits.max_tokens,
+            });
+        }
+        if input
+            .iter()
+            .any(|word| word.len() > limits.max_value_length)
+        {
+            return Err(ParseError::LimitExceeded {
+                limit: Limit::ValueLength,
+                maximum: limits.max_value_length,
+            });
+        }
         Ok(())
     }
 
-    /**
-     * Registers argument mutable borrow to be used while parsing.
-     */
-    pub fn register_parsable(&mut self, arg: &'a mut impl HandleableArgument<'a>) {
-        self.parsable_arguments.push(arg);
+    /// Applies BlankTokens option to empty and whitespace-only input values.
+    fn handle_blank_tokens(&self, input: Vec<String>) -> Result<Vec<String>, ParseError> {
+        let is_blank = |word: &String| word.trim().is_empty();
+        match self.options.blank_tokens {
+            BlankTokens::Value => Ok(input),
+            BlankTokens::Skip => Ok(input.into_iter().filter(|w| !is_blank(w)).collect()),
+            BlankTokens::Error => match input.iter().position(is_blank) {
+                Some(index) => Err(ParseError::BlankValue(index)),
+                None => Ok(input),
+            },
+        }
     }
-}
 
-/**
-Helper function to transform arguments given by user from Args to vector of String.
-*/
-pub fn args_to_string_vector(args: env::Args) -> Vec<String> {
-    let mut arguments = Vec::new();
+    /// Checks if any argument consumed more values than allowed across all its occurrences.
+    fn check_list_limit(&self) -> Result<(), ParseError> {
+        let maximum = self.options.limits.max_list_items;
+        if maximum == usize::MAX {
+            return Ok(());
+        }
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for occurrence in self.parsed.occurrences() {
+            let count = counts
+                .entry(format!("{}", occurrence.identification()))
+                .or_insert(0);
+            *count += occurrence.values().len();
+            if *count > maximum {
+                return Err(ParseError::LimitExceeded {
+                    limit: Limit::ListItems,
+                    maximum,
+                });
+            }
+        }
+        Ok(())
+    }
 
-    for x in args {
-        arguments.push(String::from(x));
+    /// Removes all arguments (legacy and parsable) sharing any name with given identification.
+    /// Returns true when anything was removed.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(Some('d'), Some("debug"), ArgType::Flag).unwrap());
+    /// assert!(args_list.remove('d'));
+    /// assert!(args_list.search_by_long_name("debug").is_none());
+    /// ```
+    pub fn remove<I: Into<ArgumentIdentification>>(&mut self, identification: I) -> bool {
+        let identification = identification.into();
+        let count =
+            self.arguments.len() + self.parsable_arguments.len() + self.boxed_arguments.len();
+        self.arguments
+            .retain(|x| !x.identification().overlaps(&identification));
+        self.index.rebuild(&self.arguments);
+        self.parsable_arguments
+            .retain(|x| !x.identification().overlaps(&identification));
+        self.boxed_arguments
+            .retain(|x| !x.identification().overlaps(&identification));
+        count != self.arguments.len() + self.parsable_arguments.len() + self.boxed_arguments.len()
     }
-    arguments
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::argument::{
-        legacy_argument::{ArgResult, ArgType},
-        parsable_argument::ParsableValueArgument,
-    };
+    /// Replaces legacy argument sharing any name with given identification. Returns replaced
+    /// argument or None when there was nothing to replace, in which case new argument is appended.
+    pub fn replace<I: Into<ArgumentIdentification>>(
+        &mut self,
+        identification: I,
+        argument: Argument,
+    ) -> Option<Argument> {
+        let identification = identification.into();
+        match self
+            .arguments
+            .iter()
+            .position(|x| x.identification().overlaps(&identification))
+        {
+            Some(i) => {
+                let replaced = std::mem::replace(&mut self.arguments[i], argument);
+                self.index.rebuild(&self.arguments);
+                Some(replaced)
+            }
+            None => {
+                self.append_arg(argument);
+                None
+            }
+        }
+    }
+
+    /// Replaces registered parsable argument sharing any name with given identification. Returns
+    /// replaced argument borrow or None when there was nothing to replace, in which case new
+    /// argument is registered.
+    pub fn replace_parsable<I: Into<ArgumentIdentification>>(
+        &mut self,
+        identification: I,
+        arg: &'a mut impl HandleableArgument<'a>,
+    ) -> Option<&'a mut (dyn HandleableArgument<'a> + 'a)> {
+        let identification = identification.into();
+        match self
+            .parsable_arguments
+            .iter()
+            .position(|x| x.identification().overlaps(&identification))
+        {
+            Some(i) => Some(std::mem::replace(&mut self.parsable_arguments[i], arg)),
+            None => {
+                self.register_parsable(arg);
+                None
+            }
+        }
+    }
+
+    /// Iterates over descriptions of all registered arguments, legacy ones first.
+    pub fn iter_specs(&self) -> std::vec::IntoIter<ArgSpec> {
+        let legacy = self.arguments.iter().map(ArgSpec::of_legacy);
+        let parsable = self.parsables().map(ArgSpec::of_parsable);
+        legacy.chain(parsable).collect::<Vec<ArgSpec>>().into_iter()
+    }
+
+    /// Consumes the list returning owned parse results and legacy arguments with their values.
+    /// Results can outlive the list and be sent to other threads. Registered parsable arguments
+    /// are released and can be read directly.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+    /// args_list
+    ///     .parse_args(vec![String::from("-p"), String::from("/file")])
+    ///     .unwrap();
+    /// let (parsed, arguments) = args_list.into_results();
+    /// let handle = std::thread::spawn(move || parsed.value_of("path").map(String::from));
+    /// assert_eq!(handle.join().unwrap(), Some(String::from("/file")));
+    /// assert_eq!(arguments.len(), 1);
+    /// ```
+    pub fn into_results(self) -> (ParsedArgs, Vec<Argument>) {
+        (self.parsed, self.arguments)
+    }
+
+    /// Returns raw view of everything parsed so far.
+    pub fn parsed(&self) -> &ParsedArgs {
+        &self.parsed
+    }
+
+    /// Warnings about values accepted in the last parse, e.g. numbers saturated by arguments
+    /// with allow_saturating. Written to ParserConfig::error_output by parse_or_exit.
+    pub fn value_warnings(&self) -> &Vec<String> {
+        &self.value_warnings
+    }
+
+    /// Counts how many times argument with given short name was given (e.g. 3 for `-v -v -v`),
+    /// whether it is legacy or parsable argument.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{legacy_argument::*, parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable_boxed(Box::new(ParsableValueArgument::new_flag(
+    ///     ArgumentIdentification::Short('v'),
+    /// )));
+    /// args_list.register_parsable_boxed(Box::new(
+    ///     ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("jobs")))
+    ///         .set_default(4),
+    /// ));
+    /// args_list.append_arg(Argument::new(Some('I'), Some("include"), ArgType::ValueList).unwrap());
+    /// let input = vec!["-v", "-v", "-I", "a", "-I", "b"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// assert_eq!(args_list.flag_count('v'), 2);
+    /// assert_eq!(args_list.value_of("jobs"), Some("4"));
+    /// assert_eq!(args_list.values_of("include"), vec!["a", "b"]);
+    /// ```
+    pub fn flag_count(&self, name: char) -> usize {
+        self.parsed.occurrences_of(&name.to_string())
+    }
+
+    /// Returns the last value given to argument named by long name or single character short
+    /// name, or its default value when it was not given. Names can be given with prefix
+    /// (`--path`, `-p`).
+    pub fn value_of(&self, name: &str) -> Option<&str> {
+        self.values_of(name).last().copied()
+    }
 
-    use super::{argument::ArgumentIdentification, *};
+    /// Returns all values given to argument named by long name or single character short name,
+    /// or its default value when it was not given.
+    pub fn values_of(&self, name: &str) -> Vec<&str> {
+        if self.parsed.is_present(name) {
+            return self.parsed.values_of(name);
+        }
+        self.iter_specs()
+            .find(|spec| spec.identification().is_named(name))
+            .and_then(|spec| self.parsed.default_of(&source::key(spec.identification())))
+            .into_iter()
+            .collect()
+    }
+
+    /// Marks argument as terminal, like `--help` or `--print-schema`. When it is given, input after
+    /// it is not parsed, required arguments, finalizers and validators are not checked and
+    /// parse_args_outcome returns ParseOutcome::Terminated. Errors found before it are still
+    /// returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{legacy_argument::*, parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    ///     parsed::ParseOutcome,
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(None, Some("print-schema"), ArgType::Flag).unwrap());
+    /// args_list.register_parsable_boxed(Box::new(
+    ///     ParsableValueArgument::new_string(ArgumentIdentification::Short('o')).set_required(true),
+    /// ));
+    /// args_list.set_terminal("print-schema");
+    /// let input = vec!["--print-schema", "-o"];
+    /// assert_eq!(
+    ///     args_list.parse_args_outcome(input.iter().map(|s| String::from(*s)).collect()),
+    ///     Ok(ParseOutcome::Terminated(ArgumentIdentification::Long(String::from("print-schema"))))
+    /// );
+    /// ```
+    pub fn set_terminal<I: Into<ArgumentIdentification>>(&mut self, identification: I) {
+        self.terminal.push(identification.into());
+    }
+
+    /// Adds validator invoked with complete parse result after all arguments were handled. Allows
+    /// checking invariants spanning multiple arguments.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    ///     error::ParseError,
+    /// };
+    ///
+    /// let mut start = ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("start")));
+    /// let mut end = ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("end")));
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable(&mut start);
+    /// args_list.register_parsable(&mut end);
+    /// args_list.add_validator(|parsed| {
+    ///     let start: i64 = parsed.value_of("start").unwrap_or("0").parse().unwrap();
+    ///     let end: i64 = parsed.value_of("end").unwrap_or("0").parse().unwrap();
+    ///     if start >= end {
+    ///         return Err(ParseError::Validation {
+    ///             arguments: vec![String::from("--start"), String::from("--end")],
+    ///             message: String::from("--start must be lower than --end"),
+    ///         });
+    ///     }
+    ///     Ok(())
+    /// });
+    /// let input = vec!["--start", "5", "--end", "2"];
+    /// let result = args_list.parse_args(input.iter().map(|s| String::from(*s)).collect());
+    /// assert!(result.is_err());
+    /// ```
+    pub fn add_validator<F>(&mut self, validator: F)
+    where
+        F: Fn(&ParsedArgs) -> Result<(), ParseError> + 'static,
+    {
+        self.validators.push(Box::new(validator));
+    }
+
+    /// Parses input like parse_args and then awaits async handlers of registered arguments (see
+    /// AsyncParsableValueArgument). Does not depend on any particular executor. Available with
+    /// `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn parse_args_async(&mut self, input: Vec<String>) -> Result<(), ParseError> {
+        self.parse_args(input)?;
+        for x in self.active_parsables_mut() {
+            let spec = ArgSpec::of_parsable(&*x);
+            if let Some(future) = x.resolve_async() {
+                if let Err(message) = future.await {
+                    return Err(ParseError::handler(&spec, true, None, message));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Identification under which built-in help stops parsing.
+    fn help_identification() -> ArgumentIdentification {
+        ArgumentIdentification::Both('h', String::from("help"))
+    }
+
+    /// Check if token given in place of an option requests built-in help: `-h` (also inside a
+    /// bundle) or `--help` which are not claimed by any registered argument or are kept by
+    /// built-in help due to ReservedNamePolicy::Coexist. Always false outside parse_for_exit.
+    pub(crate) fn is_help_token(&self, token: &Token<'_>) -> bool {
+        if !self.detect_help {
+            return false;
+        }
+        let coexist = self.config.reserved_names == ReservedNamePolicy::Coexist;
+        let is_help_short = |name: &char| *name == 'h' && (coexist || !self.is_known_short('h'));
+        match token {
+            Token::Short(name) => is_help_short(name),
+            Token::Bundle(options) => options.iter().any(is_help_short),
+            Token::Long {
+                prefix: "--",
+                name: "help",
+                value: None,
+            } => coexist || !self.is_known_long("help"),
+            _ => false,
+        }
+    }
+
+    fn parse_for_exit(&mut self, input: Vec<String>) -> Result<(), ExitReason> {
+        if self.config.generate_command {
+            match self.generate_artifact(&input) {
+                Some(Ok(artifact)) => return Err(ExitReason::Artifact(artifact)),
+                Some(Err(err)) => return Err(ExitReason::UsageError(err)),
+                None => (),
+            }
+        }
+        if self.config.reserved_names == ReservedNamePolicy::Error {
+            let report = self.lint_spec();
+            let reserved = report.issues().iter().find_map(|issue| match issue {
+                lint::SpecIssue::ReservedName(name)
+                    if lint::RESERVED_NAMES.contains(&name.as_str()) =>
+                {
+                    Some(name)
+                }
+                _ => None,
+            });
+            if let Some(name) = reserved {
+                return Err(ExitReason::Misconfigured(format!(
+                    "Argument {} uses name reserved for help.",
+                    name
+                )));
+            }
+        }
+        self.detect_help = true;
+        self.help_requested = false;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.parse_args(input)));
+        self.detect_help = false;
+        match result {
+            // Errors found before help was requested are not reported.
+            Result::Ok(_) if self.help_requested => Result::Err(ExitReason::Help),
+            Result::Ok(Result::Ok(())) => Result::Ok(()),
+            Result::Ok(Result::Err(err)) => Result::Err(ExitReason::UsageError(err)),
+            Result::Err(_) => Result::Err(ExitReason::InternalError),
+        }
+    }
+
+    /// Parses input like parse_args, but terminates the process instead of returning errors.
+    /// Prints help when `-h` or `--help` was given in place of an option before `--` (unless
+    /// registered by the user, see ParserConfig::reserved_names). When
+    /// ParserConfig::generate_command is enabled, `__generate completions <shell>` and
+    /// `__generate man` print completion script or manual page instead. Exit codes
+    /// for each case are taken from ParserConfig::exit_codes. Help and errors are written to
+    /// writers from ParserConfig.
+    pub fn parse_or_exit(&mut self, input: Vec<String>) {
+        if let Some(code) = self.parse_and_report(input) {
+            process::exit(code);
+        }
+    }
+
+    /// Parses input and writes help or error message. Returns exit code when process should end.
+    fn parse_and_report(&mut self, input: Vec<String>) -> Option<i32> {
+        let exit_codes = self.config.exit_codes;
+        // Nothing better can be done when message cannot be written, process ends anyway.
+        let result = self.parse_for_exit(input);
+        for warning in &self.migration_warnings {
+            let _ = writeln!(self.config.error_output, "Warning: {}", warning);
+        }
+        for warning in &self.value_warnings {
+            let _ = writeln!(self.config.error_output, "Warning: {}", warning);
+        }
+        match result {
+            Result::Ok(()) => None,
+            Result::Err(ExitReason::Help) => {
+                let help = self.help_message();
+                let _ = self.config.output.write_all(help.as_bytes());
+                let _ = self.config.output.flush();
+                Some(exit_codes.help_shown)
+            }
+            Result::Err(ExitReason::Artifact(artifact)) => {
+                let _ = self.config.output.write_all(artifact.as_bytes());
+                let _ = self.config.output.flush();
+                Some(exit_codes.help_shown)
+            }
+            Result::Err(ExitReason::UsageError(err)) => {
+                let _ = writeln!(self.config.error_output, "{}", err);
+                let _ = self.config.error_output.flush();
+                Some(exit_codes.usage_error)
+            }
+            Result::Err(ExitReason::InternalError) => Some(exit_codes.internal_error),
+            Result::Err(ExitReason::Misconfigured(message)) => {
+                let _ = writeln!(self.config.error_output, "{}", message);
+                let _ = self.config.error_output.flush();
+                Some(exit_codes.internal_error)
+            }
+        }
+    }
+
+    /**
+     * Registers argument mutable borrow to be used while parsing.
+     */
+    pub fn register_parsable(&mut self, arg: &'a mut impl HandleableArgument<'a>) {
+        self.parsable_arguments.push(arg);
+    }
+
+    /// Registers argument owned by the list. Unlike register_parsable, does not borrow the
+    /// argument, so arguments can be created dynamically (e.g. by plugins). Values can be read
+    /// with get_parsable.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.register_parsable_boxed(Box::new(ParsableValueArgument::new_integer(
+    ///     ArgumentIdentification::Short('n'),
+    /// )));
+    /// args_list
+    ///     .parse_args(vec![String::from("-n"), String::from("5")])
+    ///     .unwrap();
+    /// let argument_int = args_list
+    ///     .get_parsable::<ParsableValueArgument<i64>, _>('n')
+    ///     .unwrap();
+    /// assert_eq!(argument_int.first_value(), Some(&5));
+    /// ```
+    pub fn register_parsable_boxed(&mut self, arg: Box<dyn DynArgument>) {
+        self.boxed_arguments.push(arg);
+    }
+
+    /// Returns argument registered with register_parsable_boxed sharing any name with given
+    /// identification. None when there is no such argument or it is not of type T.
+    pub fn get_parsable<T: DynArgument, I: Into<ArgumentIdentification>>(
+        &self,
+        identification: I,
+    ) -> Option<&T> {
+        let identification = identification.into();
+        let arg = self
+            .boxed_arguments
+            .iter()
+            .find(|x| x.identification().overlaps(&identification))?;
+        let any: &dyn Any = &**arg;
+        any.downcast_ref()
+    }
+
+    /// Mutable version of get_parsable.
+    pub fn get_parsable_mut<T: DynArgument, I: Into<ArgumentIdentification>>(
+        &mut self,
+        identification: I,
+    ) -> Option<&mut T> {
+        let identification = identification.into();
+        let arg = self
+            .boxed_arguments
+            .iter_mut()
+            .find(|x| x.identification().overlaps(&identification))?;
+        let any: &mut dyn Any = &mut **arg;
+        any.downcast_mut()
+    }
+
+    /// All registered parsable arguments, borrowed ones first.
+    fn parsables(&self) -> impl Iterator<Item = &dyn HandleableArgument<'a>> + '_ {
+        let borrowed = self
+            .parsable_arguments
+            .iter()
+            .map(|x| &**x as &dyn HandleableArgument<'a>);
+        let boxed = self
+            .boxed_arguments
+            .iter()
+            .map(|x| &**x as &dyn HandleableArgument<'a>);
+        borrowed.chain(boxed)
+    }
+
+    /// Handles first parsable argument matching `is_named`. Its handler gets ParseContext with
+    /// arguments parsed so far.
+    fn handle_parsable<F>(
+        &mut self,
+        word: &str,
+        is_named: F,
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<Option<ArgumentIdentification>, ParseError>
+    where
+        F: Fn(&dyn HandleableArgument<'a>) -> bool,
+    {
+        // Fields are borrowed separately, so handlers can read parsed state while being mutated.
+        let context = ParseContext::new(&self.parsed, &self.options, word);
+        let borrowed = self
+            .parsable_arguments
+            .iter_mut()
+            .map(|x| &mut **x as &mut dyn HandleableArgument<'a>);
+        let boxed = self
+            .boxed_arguments
+            .iter_mut()
+            .map(|x| &mut **x as &mut dyn HandleableArgument<'a>);
+        for x in borrowed.chain(boxed) {
+            if is_named(&*x) {
+                let had_input = input_iter.peek().is_some();
+                let mut cursor = input_iter.with_context(context);
+                let result = x.handle(&mut cursor);
+                let consumed = cursor.consumed();
+                // Values of secret arguments are never included in errors.
+                let rejected = cursor
+                    .rejected()
+                    .filter(|_| !x.is_secret())
+                    .map(String::from);
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    argument = %x.identification(),
+                    consumed,
+                    error = result.as_ref().err(),
+                    "argument handler finished"
+                );
+                input_iter.take(consumed);
+                result.map_err(|message| {
+                    ParseError::handler(
+                        &ArgSpec::of_parsable(&*x),
+                        had_input,
+                        rejected.as_deref(),
+                        message,
+                    )
+                })?;
+                return Result::Ok(Some(x.identification().clone()));
+            }
+        }
+        Result::Ok(None)
+    }
+}
+
+impl<'a> Default for ArgumentList<'a> {
+    fn default() -> Self {
+        ArgumentList::new()
+    }
+}
+
+/**
+Helper function to transform arguments given by user from Args to vector of String.
+*/
+pub fn args_to_string_vector(args: env::Args) -> Vec<String> {
+    let mut arguments = Vec::new();
+
+    for x in args {
+        arguments.push(x);
+    }
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::argument::{
+        legacy_argument::{ArgResult, ArgType},
+        parsable_argument::ParsableValueArgument,
+    };
+
+    use super::{
+        argument::{parsable_argument::ListOptions, ArgumentIdentification},
+        options::{LoneDash, ParserLimits, Prefix},
+        parsed::{ParsedArgs, ParsedItem},
+        *,
+    };
+
+    #[test]
+    fn parse_for_exit_detects_help() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        assert_eq!(
+            args_list.parse_for_exit(vec![String::from("-d"), String::from("--help")]),
+            Err(ExitReason::Help)
+        );
+        args_list.append_arg(Argument::new_short('h', ArgType::Flag));
+        assert_eq!(args_list.parse_for_exit(vec![String::from("-h")]), Ok(()));
+        // Only values given in place of an option and before `--` request help.
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true).permute(true));
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        args_list.append_arg(Argument::new_long("message", ArgType::Value));
+        let input = ["--message", "--help", "--", "-h"];
+        assert_eq!(
+            args_list.parse_for_exit(input.iter().map(|s| String::from(*s)).collect()),
+            Ok(())
+        );
+        assert_eq!(args_list.get_dangling_values(), &vec!["-h"]);
+        assert_eq!(
+            args_list.parse_for_exit(vec![String::from("-dh")]),
+            Err(ExitReason::Help)
+        );
+    }
+
+    #[test]
+    fn reserved_name_policy_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(Some('h'), Some("host"), ArgType::Value).unwrap());
+        args_list.set_config(ParserConfig {
+            reserved_names: ReservedNamePolicy::Coexist,
+            ..ParserConfig::default()
+        });
+        let input = vec![String::from("--host"), String::from("a")];
+        assert_eq!(args_list.parse_for_exit(input.clone()), Ok(()));
+        let mut value_list = ArgumentList::new();
+        value_list.append_arg(Argument::new(Some('h'), Some("host"), ArgType::Value).unwrap());
+        value_list.set_config(ParserConfig {
+            reserved_names: ReservedNamePolicy::Coexist,
+            ..ParserConfig::default()
+        });
+        // Value of an argument never requests help.
+        assert_eq!(
+            value_list.parse_for_exit(vec![String::from("--host"), String::from("-h")]),
+            Ok(())
+        );
+        assert_eq!(
+            args_list.parse_for_exit(vec![String::from("-h")]),
+            Err(ExitReason::Help)
+        );
+        args_list.set_config(ParserConfig {
+            reserved_names: ReservedNamePolicy::Error,
+            ..ParserConfig::default()
+        });
+        assert_eq!(
+            args_list.parse_for_exit(input),
+            Err(ExitReason::Misconfigured(String::from(
+                "Argument -h uses name reserved for help."
+            )))
+        );
+    }
+
+    /// Writer appending to buffer which stays accessible after config takes ownership.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            std::cell::RefCell::borrow_mut(&self.0).write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn parse_and_report_writes_to_configured_writers() {
+        let output = SharedBuffer::default();
+        let error_output = SharedBuffer::default();
+        let mut args_list = ArgumentList::new();
+        args_list.set_config(ParserConfig {
+            output: Box::new(output.clone()),
+            error_output: Box::new(error_output.clone()),
+            ..ParserConfig::default()
+        });
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        assert_eq!(
+            args_list.parse_and_report(vec![String::from("-h")]),
+            Some(0)
+        );
+        assert_eq!(output.contents(), "Options:\n  -d\n");
+        assert_eq!(
+            args_list.parse_and_report(vec![String::from("-x")]),
+            Some(2)
+        );
+        assert_eq!(
+            error_output.contents(),
+            "Could not find argument identified by -x.\n"
+        );
+        assert_eq!(args_list.parse_and_report(vec![String::from("-d")]), None);
+    }
+
+    #[test]
+    fn parse_for_exit_generates_artifacts_when_enabled() {
+        let input = vec![
+            String::from("__generate"),
+            String::from("completions"),
+            String::from("fish"),
+            String::from("tool"),
+        ];
+        let mut args_list = ArgumentList::new();
+        // Without the option these are just dangling values.
+        assert_eq!(args_list.parse_for_exit(input.clone()), Ok(()));
+        args_list.set_config(ParserConfig {
+            generate_command: true,
+            ..ParserConfig::default()
+        });
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        assert_eq!(
+            args_list.parse_for_exit(input),
+            Err(ExitReason::Artifact(String::from(
+                "complete -c tool -s d\n"
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_for_exit_detects_errors() {
+        let mut args_list = ArgumentList::new();
+        assert_eq!(
+            args_list.parse_for_exit(vec![String::from("-x")]),
+            Err(ExitReason::UsageError(ParseError::UnknownArgument(
+                String::from("-x")
+            )))
+        );
+        let mut argument_panic =
+            ParsableValueArgument::<i64>::new(ArgumentIdentification::Short('p'), |_, _| {
+                panic!("handler failure")
+            });
+        args_list.register_parsable(&mut argument_panic);
+        assert_eq!(
+            args_list.parse_for_exit(vec![String::from("-p")]),
+            Err(ExitReason::InternalError)
+        );
+    }
+
+    #[test]
+    fn parse_bundle_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true));
+        let mut argument_int =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        args_list.register_parsable(&mut argument_int);
+        args_list
+            .parse_args(vec![String::from("-vdn"), String::from("5")])
+            .unwrap();
+        assert!(args_list
+            .search_by_short_name('v')
+            .unwrap()
+            .get_flag()
+            .unwrap());
+        assert!(args_list
+            .search_by_short_name('d')
+            .unwrap()
+            .get_flag()
+            .unwrap());
+        assert_eq!(argument_int.first_value().unwrap(), &5);
+    }
+
+    #[test]
+    fn parse_bundle_disabled_by_default() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.parse_args(vec![String::from("-vv")]).unwrap();
+        assert_eq!(args_list.get_dangling_values(), &vec![String::from("-vv")]);
+    }
+
+    #[test]
+    fn parse_bundle_fails_value_not_last() {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true));
+        let mut argument_int =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.append_arg(Argument::new_short('o', ArgType::Value));
+        args_list.register_parsable(&mut argument_int);
+        assert_eq!(
+            args_list.parse_args(vec![String::from("-vov"), String::from("out")]),
+            Err(ParseError::IllegalBundle {
+                bundle: String::from("-vov"),
+                option: 'o',
+                suggestion: String::from("-vv -o <value>"),
+            })
+        );
+        assert_eq!(
+            args_list.parse_args(vec![String::from("-nv"), String::from("5")]),
+            Err(ParseError::IllegalBundle {
+                bundle: String::from("-nv"),
+                option: 'n',
+                suggestion: String::from("-v -n <value>"),
+            })
+        );
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        assert_eq!(
+            args_list.parse_args(vec![String::from("-vx")]),
+            Err(ParseError::UnknownBundledArgument {
+                bundle: String::from("-vx"),
+                option: 'x',
+            })
+        );
+    }
+
+    #[test]
+    fn parse_runs_finalizers() {
+        let mut args_list = ArgumentList::new();
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("tag")))
+                .set_finalizer(|values| {
+                    let mut unique = values.clone();
+                    unique.dedup();
+                    if unique.len() != values.len() {
+                        return Result::Err(String::from("tags must be unique"));
+                    }
+                    Result::Ok(())
+                });
+        args_list.register_parsable(&mut argument_str);
+        assert_eq!(
+            args_list.parse_args(vec![
+                String::from("--tag"),
+                String::from("a"),
+                String::from("--tag"),
+                String::from("a"),
+            ]),
+            Err(ParseError::InvalidValues {
+                argument: String::from("--tag"),
+                message: String::from("tags must be unique"),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_records_parsed_args() {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true));
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("hello")));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.append_arg(Argument::new_short('o', ArgType::Value));
+        args_list.register_parsable(&mut argument_str);
+        args_list
+            .parse_args(vec![
+                String::from("-vo"),
+                String::from("out"),
+                String::from("file"),
+                String::from("--hello"),
+                String::from("world"),
+            ])
+            .unwrap();
+        let parsed = args_list.parsed();
+        assert!(parsed.is_present("v"));
+        assert!(parsed.values_of("v").is_empty());
+        assert_eq!(parsed.value_of("o"), Some("out"));
+        assert_eq!(parsed.value_of("hello"), Some("world"));
+        assert_eq!(parsed.positionals(), vec!["file"]);
+    }
+
+    #[test]
+    fn parse_runs_validators() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_long("verbose", ArgType::Flag));
+        args_list.append_arg(Argument::new_long("quiet", ArgType::Flag));
+        args_list.add_validator(|parsed| {
+            if parsed.is_present("verbose") && parsed.is_present("quiet") {
+                return Err(ParseError::Validation {
+                    arguments: vec![String::from("--verbose"), String::from("--quiet")],
+                    message: String::from("cannot be used together"),
+                });
+            }
+            Ok(())
+        });
+        assert_eq!(
+            args_list.parse_args(vec![String::from("--verbose"), String::from("--quiet")]),
+            Err(ParseError::Validation {
+                arguments: vec![String::from("--verbose"), String::from("--quiet")],
+                message: String::from("cannot be used together"),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_options_after_positionals_disabled() {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().options_after_positionals(false));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list
+            .parse_args(vec![
+                String::from("-v"),
+                String::from("file"),
+                String::from("-v"),
+                String::from("--unknown"),
+            ])
+            .unwrap();
+        assert!(args_list
+            .search_by_short_name('v')
+            .unwrap()
+            .get_flag()
+            .unwrap());
+        assert_eq!(
+            args_list.get_dangling_values(),
+            &vec![
+                String::from("file"),
+                String::from("-v"),
+                String::from("--unknown")
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_options_after_positionals_enabled() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list
+            .parse_args(vec![String::from("file"), String::from("-v")])
+            .unwrap();
+        assert!(args_list
+            .search_by_short_name('v')
+            .unwrap()
+            .get_flag()
+            .unwrap());
+        assert_eq!(args_list.get_dangling_values(), &vec![String::from("file")]);
+    }
+
+    #[test]
+    fn remove_works() {
+        let mut args_list = ArgumentList::new();
+        let mut argument_str = ParsableValueArgument::new_string(ArgumentIdentification::Both(
+            'p',
+            String::from("path"),
+        ));
+        args_list.append_arg(Argument::new_short('d', ArgType::Flag));
+        args_list.append_arg(Argument::new_long("path", ArgType::Value));
+        args_list.register_parsable(&mut argument_str);
+        assert!(args_list.remove("path"));
+        assert!(args_list.arguments().len() == 1);
+        assert!(args_list.parsable_arguments.is_empty());
+        assert!(!args_list.remove('x'));
+        assert!(args_list.remove('d'));
+        assert!(args_list.arguments().is_empty());
+    }
+
+    #[test]
+    fn replace_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(Some('d'), Some("debug"), ArgType::Flag).unwrap());
+        let replaced = args_list
+            .replace('d', Argument::new_short('d', ArgType::Value))
+            .unwrap();
+        assert_eq!(replaced.long(), &Some(String::from("debug")));
+        assert_eq!(args_list.arguments().len(), 1);
+        assert_eq!(
+            args_list.search_by_short_name('d').unwrap().arg_type(),
+            &ArgType::Value
+        );
+        assert!(args_list
+            .replace("level", Argument::new_long("level", ArgType::Value))
+            .is_none());
+        assert_eq!(args_list.arguments().len(), 2);
+    }
+
+    #[test]
+    fn replace_parsable_works() {
+        let mut argument_default =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'));
+        let mut argument_override =
+            ParsableValueArgument::new_integer_sum(ArgumentIdentification::Short('n'));
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_default);
+        assert!(args_list
+            .replace_parsable('n', &mut argument_override)
+            .is_some());
+        args_list
+            .parse_args(vec![
+                String::from("-n"),
+                String::from("1"),
+                String::from("-n"),
+                String::from("2"),
+            ])
+            .unwrap();
+        assert!(argument_default.values().is_empty());
+        assert_eq!(argument_override.values(), &vec![3]);
+    }
+
+    #[test]
+    fn results_follow_input_order() {
+        let input: Vec<String> = [
+            "z", "-e", "b", "-l", "3,1", "a", "-e", "a", "y", "-l", "2", "x",
+        ]
+        .iter()
+        .map(|s| String::from(*s))
+        .collect();
+        // Repeated parsing of the same input must give identical results.
+        let mut previous: Option<ParsedArgs> = None;
+        for _ in 0..3 {
+            let mut argument_list = ParsableValueArgument::new_integer_list(
+                ArgumentIdentification::Short('l'),
+                ListOptions::new().delimiter(','),
+            );
+            let mut args_list = ArgumentList::new();
+            args_list.append_arg(Argument::new_short('e', ArgType::ValueList));
+            args_list.register_parsable(&mut argument_list);
+            args_list.parse_args(input.clone()).unwrap();
+            assert_eq!(args_list.get_dangling_values(), &vec!["z", "a", "y", "x"]);
+            assert_eq!(args_list.parsed().positionals(), vec!["z", "a", "y", "x"]);
+            assert_eq!(
+                args_list
+                    .search_by_short_name('e')
+                    .unwrap()
+                    .get_values()
+                    .unwrap(),
+                &vec![String::from("b"), String::from("a")]
+            );
+            assert_eq!(args_list.parsed().values_of("e"), vec!["b", "a"]);
+            assert_eq!(args_list.parsed().values_of("l"), vec!["3,1", "2"]);
+            let indexes: Vec<usize> = args_list
+                .parsed()
+                .iter_in_order()
+                .map(ParsedItem::index)
+                .collect();
+            assert_eq!(indexes, vec![0, 1, 3, 5, 6, 8, 9, 11]);
+            let parsed = args_list.parsed().clone();
+            drop(args_list);
+            assert_eq!(argument_list.values(), &vec![3, 1, 2]);
+            if let Some(previous) = previous {
+                assert_eq!(parsed, previous);
+            }
+            previous = Some(parsed);
+        }
+    }
+
+    #[test]
+    fn parse_collects_stats() {
+        let input = vec![String::from("-vp"), String::from("a"), String::from("b")];
+        let new_list = |options: ParserOptions| {
+            let mut args_list = ArgumentList::new();
+            args_list.set_options(options);
+            args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+            args_list.append_arg(Argument::new_short('p', ArgType::Value));
+            args_list
+        };
+        let mut args_list = new_list(ParserOptions::new().short_bundling(true));
+        args_list.parse_args(input.clone()).unwrap();
+        assert_eq!(args_list.stats(), None);
+        let mut args_list = new_list(
+            ParserOptions::new()
+                .short_bundling(true)
+                .collect_stats(true),
+        );
+        args_list.parse_args(input).unwrap();
+        assert_eq!(
+            args_list.stats(),
+            Some(&stats::ParseStats {
+                tokens: 3,
+                lookups: 2,
+                allocations: 7,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_reports_completion() {
+        static REPORTS: std::sync::Mutex<Vec<stats::ParseReport>> =
+            std::sync::Mutex::new(Vec::new());
+        let mut args_list = ArgumentList::new();
+        args_list.set_config(ParserConfig {
+            on_parse_complete: Some(|report| REPORTS.lock().unwrap().push(report.clone())),
+            ..ParserConfig::default()
+        });
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_u8(ArgumentIdentification::Short('l')).allow_saturating(),
+        ));
+        args_list.add_migration(Migration::renamed("--level", "-l", ""));
+        args_list
+            .parse_args(vec![
+                String::from("-v"),
+                String::from("file"),
+                String::from("--level"),
+                String::from("256"),
+            ])
+            .unwrap();
+        assert!(args_list.parse_args(vec![String::from("-x")]).is_err());
+        assert_eq!(
+            *REPORTS.lock().unwrap(),
+            vec![
+                stats::ParseReport {
+                    arguments: vec![String::from("-v"), String::from("-l")],
+                    positionals: 1,
+                    errors: Vec::new(),
+                    warnings: vec![
+                        String::from("--level is deprecated, use -l instead."),
+                        String::from("-l: Value was saturated to 255.")
+                    ],
+                },
+                stats::ParseReport {
+                    arguments: Vec::new(),
+                    positionals: 0,
+                    errors: vec!["unknown_argument"],
+                    warnings: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn iter_specs_works() {
+        let mut argument_int =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                .set_description("Number")
+                .set_required(true)
+                .set_default(3);
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_long("path", ArgType::Value));
+        args_list.register_parsable(&mut argument_int);
+        let specs: Vec<ArgSpec> = args_list.iter_specs().collect();
+        assert_eq!(specs.len(), 2);
+        assert_eq!(
+            specs[0].identification(),
+            &ArgumentIdentification::Long(String::from("path"))
+        );
+        assert_eq!(specs[0].arg_type(), &ArgType::Value);
+        assert!(!specs[0].is_required());
+        assert_eq!(
+            specs[1].identification(),
+            &ArgumentIdentification::Short('n')
+        );
+        assert_eq!(specs[1].description(), Some("Number"));
+        assert_eq!(specs[1].default_value(), Some("3"));
+        assert!(specs[1].is_required());
+    }
+
+    #[test]
+    fn value_errors_include_usage() {
+        let mut argument_level =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("level")))
+                .set_description("Verbosity")
+                .set_default(1);
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_level);
+        assert_eq!(
+            args_list.parse_args(vec![String::from("--level")]),
+            Err(ParseError::MissingValue {
+                argument: String::from("--level"),
+                usage: String::from("--level <INT>  Verbosity (default: 1)"),
+            })
+        );
+        let error = args_list
+            .parse_args(vec![String::from("--level"), String::from("high")])
+            .unwrap_err();
+        assert!(matches!(error, ParseError::InvalidValue { .. }));
+        assert!(format!("{}", error).ends_with("\nUsage: --level <INT>  Verbosity (default: 1)"));
+    }
+
+    #[test]
+    fn parse_fails_missing_required() {
+        let mut argument_int =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                .set_required(true);
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_int);
+        assert_eq!(
+            args_list.parse_args(Vec::new()),
+            Err(ParseError::MissingArgument(String::from("-n")))
+        );
+        assert!(args_list
+            .parse_args(vec![String::from("-n"), String::from("1")])
+            .is_ok());
+    }
+
+    #[test]
+    fn aggregate_error_mode_collects_all_errors() {
+        let mut argument_int =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                .set_required(true);
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().error_mode(ErrorMode::Aggregate));
+        args_list.register_parsable(&mut argument_int);
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        let input = vec![
+            String::from("-x"),
+            String::from("-v"),
+            String::from("--unknown"),
+        ];
+        assert_eq!(
+            args_list.parse_args(input),
+            Err(ParseError::Multiple(vec![
+                ParseError::UnknownArgument(String::from("-x")),
+                ParseError::UnknownArgument(String::from("--unknown")),
+                ParseError::MissingArgument(String::from("-n")),
+            ]))
+        );
+        assert!(args_list
+            .search_by_short_name('v')
+            .unwrap()
+            .get_flag()
+            .unwrap());
+    }
+
+    #[test]
+    fn failed_handler_returns_consumed_values() {
+        // Takes two integers, but fails only after consuming the second value.
+        let mut argument_pair = ParsableValueArgument::new(
+            ArgumentIdentification::Long(String::from("pair")),
+            |input_iter, values: &mut Vec<i64>| {
+                for _ in 0..2 {
+                    let value = input_iter
+                        .next()
+                        .ok_or_else(|| String::from("No remaining input values."))?;
+                    values.push(
+                        value
+                            .parse()
+                            .map_err(|_| format!("{} is not a number", value))?,
+                    );
+                }
+                Result::Ok(())
+            },
+        );
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().error_mode(ErrorMode::Aggregate));
+        args_list.register_parsable(&mut argument_pair);
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        let input = ["--pair", "1", "-v", "file"];
+        assert_eq!(
+            args_list.parse_args(input.iter().map(|s| String::from(*s)).collect()),
+            Err(ParseError::Multiple(vec![ParseError::InvalidValue {
+                argument: String::from("--pair"),
+                message: String::from("error in --pair: -v is not a number"),
+                usage: String::from("--pair <VALUE>"),
+                value: None,
+                suggestion: None,
+            }]))
+        );
+        assert!(args_list
+            .search_by_short_name('v')
+            .unwrap()
+            .get_flag()
+            .unwrap());
+        assert_eq!(
+            args_list.get_dangling_values(),
+            &vec![String::from("1"), String::from("file")]
+        );
+        drop(args_list);
+        assert!(argument_pair.values().is_empty());
+    }
+
+    #[test]
+    fn terminal_argument_stops_parsing() {
+        let new_list = |options: ParserOptions| {
+            let mut args_list = ArgumentList::new();
+            args_list.set_options(options);
+            args_list.append_arg(Argument::new(Some('h'), Some("help"), ArgType::Flag).unwrap());
+            args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+            args_list.register_parsable_boxed(Box::new(
+                ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                    .set_required(true),
+            ));
+            args_list.set_terminal('h');
+            args_list
+        };
+        let input = |values: &[&str]| values.iter().map(|v| String::from(*v)).collect();
+        let mut args_list = new_list(ParserOptions::new());
+        assert_eq!(
+            args_list.parse_args_outcome(input(&["-v", "--help", "-x", "file"])),
+            Ok(ParseOutcome::Terminated(ArgumentIdentification::Both(
+                'h',
+                String::from("help")
+            )))
+        );
+        assert!(args_list.parsed().is_present("v"));
+        assert!(args_list.parsed().positionals().is_empty());
+        let aggregate = ParserOptions::new().error_mode(ErrorMode::Aggregate);
+        assert_eq!(
+            new_list(aggregate.clone()).parse_args_outcome(input(&["-x", "-h"])),
+            Err(ParseError::Multiple(vec![ParseError::UnknownArgument(
+                String::from("-x")
+            )]))
+        );
+        assert_eq!(
+            new_list(aggregate).parse_args(input(&["-v"])),
+            Err(ParseError::Multiple(vec![ParseError::MissingArgument(
+                String::from("-n")
+            )]))
+        );
+    }
+
+    #[test]
+    fn parse_best_effort_keeps_defaults() {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().error_mode(ErrorMode::FailFast));
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                .set_default(3)
+                .set_required(true),
+        ));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.define_preset("slow", &["-n", "1"]);
+        let partial =
+            args_list.parse_args_best_effort(vec![String::from("--preset"), String::from("fast")]);
+        assert_eq!(
+            partial.errors(),
+            &vec![
+                ParseError::UnknownPreset(String::from("fast")),
+                ParseError::MissingArgument(String::from("-n")),
+            ]
+        );
+        assert_eq!(partial.errors_of("n").len(), 1);
+        assert_eq!(args_list.options.error_mode, ErrorMode::FailFast);
+        let partial = args_list.parse_args_best_effort(vec![String::from("-v")]);
+        assert!(partial.errors_of("v").is_empty());
+        assert!(args_list
+            .search_by_short_name('v')
+            .unwrap()
+            .get_flag()
+            .unwrap());
+    }
+
+    /// Subscriber recording names of spans and fields of events as text.
+    #[cfg(feature = "tracing")]
+    #[derive(Default)]
+    struct CapturingSubscriber {
+        records: std::sync::Mutex<Vec<String>>,
+    }
+
+    /// Fields of an event rendered as text.
+    #[cfg(feature = "tracing")]
+    struct FieldText(String);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for FieldText {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for CapturingSubscriber {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut records = self.records.lock().unwrap();
+            records.push(format!("span {}", span.metadata().name()));
+            tracing::span::Id::from_u64(records.len() as u64)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            let mut record = FieldText(String::from("event"));
+            event.record(&mut record);
+            self.records.lock().unwrap().push(record.0);
+        }
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn parse_entry_points_are_traced() {
+        let subscriber = std::sync::Arc::new(CapturingSubscriber::default());
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            let mut args_list = ArgumentList::new();
+            args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+            args_list.parse_args(vec![String::from("-v")]).unwrap();
+            let mut args_list = ArgumentList::new();
+            args_list.register_parsable_boxed(Box::new(
+                ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from(
+                    "password",
+                )))
+                .set_secret(true),
+            ));
+            args_list.parse_args_best_effort(vec![String::from("--password=hunter2")]);
+            args_list
+                .parse_tree(vec![String::from("--password"), String::from("hunter2")])
+                .unwrap();
+        });
+        let records = subscriber.records.lock().unwrap();
+        assert!(records.iter().all(|record| !record.contains("hunter2")));
+        let spans: Vec<_> = records
+            .iter()
+            .filter(|record| record.starts_with("span"))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                "span parse_args",
+                "span parse_args_best_effort",
+                "span parse_tree"
+            ]
+        );
+        let events = |name: &str| {
+            records
+                .iter()
+                .filter(|record| record.contains(name))
+                .count()
+        };
+        // Flag parse, inline secret value and secret value given separately.
+        assert_eq!(events("classified input value"), 3);
+        assert_eq!(events("matched argument"), 3);
+        assert!(records.contains(&String::from(
+            "event message=classified input value index=0 word=--password=<redacted> \
+             token=Long { prefix: \"--\", name: \"password\", value: Some(\"<redacted>\") }"
+        )));
+    }
+
+    #[test]
+    fn prefixed_names_work() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("jobs")))
+                .set_default(4),
+        ));
+        args_list.register_parsable_boxed(Box::new(ParsableValueArgument::new_integer(
+            ArgumentIdentification::Short('n'),
+        )));
+        let partial = args_list.parse_args_best_effort(vec![
+            String::from("--path=/tmp"),
+            String::from("-n"),
+            String::from("x"),
+        ]);
+        assert_eq!(partial.errors_of("-n").len(), 1);
+        assert!(partial.errors_of("--path").is_empty());
+        assert_eq!(args_list.value_of("-p"), Some("/tmp"));
+        assert_eq!(args_list.value_of("--path"), Some("/tmp"));
+        assert_eq!(args_list.value_of("--jobs"), Some("4"));
+        assert_eq!(args_list.value_of("-path"), None);
+        assert!(args_list.parsed().is_present("--path"));
+        assert_eq!(
+            args_list.search_by_long_name("--path").unwrap().get_value(),
+            Ok("/tmp")
+        );
+    }
+
+    #[test]
+    fn parse_fails_exceeding_limits() {
+        let limits = ParserLimits {
+            max_tokens: 6,
+            max_value_length: 5,
+            max_list_items: 2,
+        };
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().limits(limits));
+        args_list.append_arg(Argument::new_short('l', ArgType::ValueList));
+        assert_eq!(
+            args_list.parse_args(vec![String::from("a"); 7]),
+            Err(ParseError::LimitExceeded {
+                limit: Limit::Tokens,
+                maximum: 6
+            })
+        );
+        assert_eq!(
+            args_list.parse_args(vec![String::from("abcdef")]),
+            Err(ParseError::LimitExceeded {
+                limit: Limit::ValueLength,
+                maximum: 5
+            })
+        );
+        let input = ["-l", "a", "-l", "b", "-l", "c"];
+        assert_eq!(
+            args_list.parse_args(input.iter().map(|s| String::from(*s)).collect()),
+            Err(ParseError::LimitExceeded {
+                limit: Limit::ListItems,
+                maximum: 2
+            })
+        );
+    }
+
+    #[test]
+    fn parse_long_name_with_inline_value() {
+        let mut argument_define =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("define")));
+        let mut argument_name =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("name")));
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_define);
+        args_list.register_parsable(&mut argument_name);
+        args_list
+            .parse_args(vec![
+                String::from("--define=key=value=with=equals"),
+                String::from("--name="),
+            ])
+            .unwrap();
+        assert_eq!(
+            args_list.parsed().value_of("define"),
+            Some("key=value=with=equals")
+        );
+        assert_eq!(args_list.parsed().value_of("name"), Some(""));
+        let forms: Vec<&str> = args_list.parsed().occurrences().map(|o| o.form()).collect();
+        assert_eq!(forms, vec!["--define=key=value=with=equals", "--name="]);
+        assert_eq!(
+            argument_define.first_value().unwrap(),
+            "key=value=with=equals"
+        );
+        assert_eq!(argument_name.first_value().unwrap(), "");
+    }
+
+    #[test]
+    fn parse_fails_inline_value_errors() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(None, Some("verbose"), ArgType::Flag).unwrap());
+        assert_eq!(
+            args_list.parse_args(vec![String::from("--verbose=yes")]),
+            Err(ParseError::UnexpectedValue {
+                argument: String::from("--verbose"),
+                value: String::from("yes")
+            })
+        );
+        assert_eq!(
+            args_list.parse_args(vec![String::from("--unknown=a=b")]),
+            Err(ParseError::UnknownArgument(String::from("--unknown")))
+        );
+    }
+
+    #[test]
+    fn blank_tokens_option_works() {
+        let input = vec![
+            String::from("-p"),
+            String::from(""),
+            String::from("  "),
+            String::from("file"),
+        ];
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('p', ArgType::Value));
+        args_list.parse_args(input.clone()).unwrap();
+        assert_eq!(args_list.parsed().value_of("p"), Some(""));
+        assert_eq!(args_list.get_dangling_values(), &vec!["  ", "file"]);
+
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().blank_tokens(BlankTokens::Skip));
+        args_list.append_arg(Argument::new_short('p', ArgType::Value));
+        args_list.parse_args(input.clone()).unwrap();
+        assert_eq!(args_list.parsed().value_of("p"), Some("file"));
+
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().blank_tokens(BlankTokens::Error));
+        assert_eq!(args_list.parse_args(input), Err(ParseError::BlankValue(1)));
+    }
+
+    #[test]
+    fn numeric_long_values_are_positionals() {
+        let input: Vec<String> = ["--10..20", "--123", "--2=x", "-r", "--5"]
+            .iter()
+            .map(|s| String::from(*s))
+            .collect();
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('r', ArgType::Value));
+        args_list.parse_args(input.clone()).unwrap();
+        assert_eq!(
+            args_list.get_dangling_values(),
+            &vec!["--10..20", "--123", "--2=x"]
+        );
+        assert_eq!(args_list.parsed().value_of("r"), Some("--5"));
+
+        let mut argument_two =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("2")));
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('r', ArgType::Value));
+        args_list.append_arg(Argument::new_long("123", ArgType::Flag));
+        args_list.register_parsable(&mut argument_two);
+        args_list.parse_args(input).unwrap();
+        assert_eq!(args_list.get_dangling_values(), &vec!["--10..20"]);
+        assert!(args_list
+            .search_by_long_name("123")
+            .unwrap()
+            .get_flag()
+            .unwrap());
+        drop(args_list);
+        assert_eq!(argument_two.first_value(), Some(&String::from("x")));
+    }
+
+    #[test]
+    fn lone_dash_option_works() {
+        let input = vec![
+            String::from("-o"),
+            String::from("-"),
+            String::from("-"),
+            String::from("file"),
+        ];
+        let new_list = |lone_dash: LoneDash| {
+            let mut args_list = ArgumentList::new();
+            args_list.set_options(ParserOptions::new().lone_dash(lone_dash));
+            args_list.append_arg(Argument::new_short('o', ArgType::Value));
+            args_list
+        };
+        // Value of an argument is never affected.
+        let mut args_list = new_list(LoneDash::Positional);
+        args_list.parse_args(input.clone()).unwrap();
+        assert_eq!(args_list.parsed().value_of("o"), Some("-"));
+        assert_eq!(args_list.get_dangling_values(), &vec!["-", "file"]);
+        assert!(!args_list.parsed().reads_stdin());
+
+        let mut args_list = new_list(LoneDash::Stdin);
+        args_list.parse_args(input.clone()).unwrap();
+        assert_eq!(args_list.parsed().value_of("o"), Some("-"));
+        assert_eq!(args_list.get_dangling_values(), &vec!["file"]);
+        assert!(args_list.parsed().reads_stdin());
+        assert_eq!(
+            args_list
+                .parsed()
+                .iter_in_order()
+                .skip(1)
+                .collect::<Vec<_>>(),
+            vec![
+                &ParsedItem::Stdin { index: 2 },
+                &ParsedItem::Positional {
+                    value: String::from("file"),
+                    index: 3
+                }
+            ]
+        );
+
+        let mut args_list = new_list(LoneDash::Error);
+        assert_eq!(args_list.parse_args(input), Err(ParseError::LoneDash(2)));
+        // Flag-only lists are parsed separately.
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().lone_dash(LoneDash::Error));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        assert_eq!(
+            args_list.parse_args(vec![String::from("-v"), String::from("-")]),
+            Err(ParseError::LoneDash(1))
+        );
+    }
+
+    #[test]
+    fn single_dash_long_prefix_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().long_prefix(Prefix::SingleDash));
+        args_list.append_arg(Argument::new(None, Some("name"), ArgType::ValueList).unwrap());
+        args_list.append_arg(Argument::new(None, Some("version"), ArgType::Flag).unwrap());
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        let input = ["-name", "*.rs", "-version", "-v", "--name=main.rs"];
+        args_list
+            .parse_args(input.iter().map(|s| String::from(*s)).collect())
+            .unwrap();
+        assert_eq!(
+            args_list.parsed().values_of("name"),
+            vec!["*.rs", "main.rs"]
+        );
+        assert!(args_list.parsed().is_present("version"));
+        assert!(args_list.parsed().is_present("v"));
+        assert_eq!(
+            args_list.parse_args(vec![String::from("-type=f")]),
+            Err(ParseError::UnknownArgument(String::from("-type")))
+        );
+    }
+
+    #[test]
+    fn boxed_parsable_works() {
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("path")));
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_str);
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('n'))
+                .set_required(true),
+        ));
+        assert_eq!(
+            args_list.parse_args(Vec::new()),
+            Err(ParseError::MissingArgument(String::from("-n")))
+        );
+        let input = ["-n", "3", "--path", "/tmp"];
+        args_list
+            .parse_args(input.iter().map(|s| String::from(*s)).collect())
+            .unwrap();
+        assert_eq!(
+            args_list
+                .get_parsable::<ParsableValueArgument<i64>, _>('n')
+                .unwrap()
+                .values(),
+            &vec![3]
+        );
+        assert!(args_list
+            .get_parsable::<ParsableValueArgument<String>, _>('n')
+            .is_none());
+        assert_eq!(args_list.iter_specs().count(), 2);
+        assert!(args_list.remove('n'));
+        assert!(args_list
+            .get_parsable::<ParsableValueArgument<i64>, _>('n')
+            .is_none());
+        drop(args_list);
+        assert_eq!(argument_str.first_value().unwrap(), "/tmp");
+    }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn parse_works() {
         let args = vec![
             String::from("-d"),
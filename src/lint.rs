@@ -0,0 +1,172 @@
+use std::fmt;
+
+use crate::{argument::ArgumentIdentification, ArgumentList};
+
+/// Names handled by parse_or_exit unless claimed by a registered argument.
+pub(crate) const RESERVED_NAMES: [&str; 2] = ["-h", "--help"];
+
+/// Mistake in definitions of arguments found by ArgumentList::lint_spec.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpecIssue {
+    /// Name (e.g. `--path`) is used by more than one argument. Only the first one is ever
+    /// matched.
+    DuplicateName(String),
+    /// Required argument has a default value, which is never used.
+    RequiredWithDefault(String),
+    /// Argument has an empty long name, which cannot be given in input.
+    EmptyLongName(String),
+    /// Argument uses a name reserved for built-in handling (e.g. `--help`), disabling it.
+    ReservedName(String),
+}
+
+impl fmt::Display for SpecIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecIssue::DuplicateName(name) => {
+                write!(f, "{} is used by more than one argument.", name)
+            }
+            SpecIssue::RequiredWithDefault(argument) => {
+                write!(f, "{} is required, but has a default value.", argument)
+            }
+            SpecIssue::EmptyLongName(argument) => write!(f, "{} has empty long name.", argument),
+            SpecIssue::ReservedName(name) => {
+                write!(f, "{} is reserved for built-in handling.", name)
+            }
+        }
+    }
+}
+
+/// Issues found in definitions of arguments. Displayed as one issue per line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpecReport {
+    issues: Vec<SpecIssue>,
+}
+
+impl SpecReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn issues(&self) -> &Vec<SpecIssue> {
+        &self.issues
+    }
+}
+
+impl fmt::Display for SpecReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    ///
+    /// Checks definitions of all registered arguments for duplicate names, required arguments
+    /// with default values, empty long names and names reserved for built-in help (and for
+    /// `--preset` when presets are defined). Meant to be run in tests, as checks are not done
+    /// while parsing.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, lint::SpecIssue};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+    /// args_list.append_arg(Argument::new(Some('p'), Some("port"), ArgType::Value).unwrap());
+    /// let report = args_list.lint_spec();
+    /// assert_eq!(report.issues(), &vec![SpecIssue::DuplicateName(String::from("-p"))]);
+    /// assert_eq!(format!("{}", report), "-p is used by more than one argument.\n");
+    /// ```
+    pub fn lint_spec(&self) -> SpecReport {
+        let specs: Vec<_> = self.iter_specs().collect();
+        let mut issues = Vec::new();
+        let mut names = Vec::new();
+        for spec in &specs {
+            let identification = spec.identification();
+            let argument = format!("{}", identification);
+            if identification.long() == Some("") {
+                issues.push(SpecIssue::EmptyLongName(argument.clone()));
+            }
+            if spec.is_required() && spec.default_value().is_some() {
+                issues.push(SpecIssue::RequiredWithDefault(argument));
+            }
+            let short = identification.short().map(ArgumentIdentification::Short);
+            let long = identification
+                .long()
+                .filter(|long| !long.is_empty())
+                .map(|long| ArgumentIdentification::Long(String::from(long)));
+            for name in short.into_iter().chain(long) {
+                let name = format!("{}", name);
+                let is_preset = name == "--preset" && !self.presets.is_empty();
+                if RESERVED_NAMES.contains(&name.as_str()) || is_preset {
+                    issues.push(SpecIssue::ReservedName(name.clone()));
+                }
+                if names.contains(&name) {
+                    let issue = SpecIssue::DuplicateName(name);
+                    if !issues.contains(&issue) {
+                        issues.push(issue);
+                    }
+                } else {
+                    names.push(name);
+                }
+            }
+        }
+        SpecReport { issues }
+    }
+
+    /// Panics with report of lint_spec when definitions of arguments contain any issues.
+    /// Returns the list, so it can be chained after registering arguments.
+    pub fn assert_valid(&self) -> &Self {
+        let report = self.lint_spec();
+        assert!(report.is_valid(), "Invalid arguments:\n{}", report);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SpecIssue;
+    use crate::{
+        argument::{
+            legacy_argument::{ArgType, Argument},
+            parsable_argument::ParsableValueArgument,
+            ArgumentIdentification,
+        },
+        ArgumentList,
+    };
+
+    #[test]
+    fn lint_spec_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_long("", ArgType::Flag));
+        args_list.append_arg(Argument::new(Some('h'), Some("host"), ArgType::Value).unwrap());
+        args_list.append_arg(Argument::new_long("host", ArgType::Value));
+        args_list.append_arg(Argument::new_long("host", ArgType::Flag));
+        args_list.register_parsable_boxed(Box::new(
+            ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("n")))
+                .set_required(true)
+                .set_default(1),
+        ));
+        assert_eq!(
+            args_list.lint_spec().issues(),
+            &vec![
+                SpecIssue::EmptyLongName(String::from("--")),
+                SpecIssue::ReservedName(String::from("-h")),
+                SpecIssue::DuplicateName(String::from("--host")),
+                SpecIssue::RequiredWithDefault(String::from("--n")),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid arguments:\n--help is reserved for built-in handling.")]
+    fn assert_valid_panics() {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+        args_list.assert_valid();
+        args_list.append_arg(Argument::new_long("help", ArgType::Flag));
+        args_list.assert_valid();
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{help::trim_description, ArgumentList};
+
+/// Escapes text for roff, so dashes and backslashes are printed literally and lines starting with
+/// `.` or `'` are not read as requests.
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('-', "\\-");
+    escaped
+        .split('\n')
+        .map(|line| {
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{}", line)
+            } else {
+                String::from(line)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Generates manual page in roff format listing all registered arguments, including arguments
+    /// of attached scopes with their guards. Examples of arguments are listed in EXAMPLES section
+    /// and text set with set_after_help in NOTES.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(Some('d'), Some("debug"), ArgType::Flag).unwrap());
+    /// assert!(args_list.generate_man_page("myapp").contains("\\fB\\-d\\fR, \\fB\\-\\-debug\\fR"));
+    /// ```
+    pub fn generate_man_page(&self, bin_name: &str) -> String {
+        let mut page = format!(
+            ".TH {} 1\n.SH NAME\n{}\n.SH SYNOPSIS\n\\fB{}\\fR [OPTIONS]\n.SH OPTIONS\n",
+            roff_escape(&bin_name.to_uppercase()),
+            roff_escape(bin_name),
+            roff_escape(bin_name)
+        );
+        self.write_man_options(&mut page, &self.hidden_tags, self.language.as_deref(), None);
+        let examples: Vec<String> = self
+            .visible_specs()
+            .flat_map(|spec| spec.examples().clone())
+            .collect();
+        if !examples.is_empty() {
+            page.push_str(".SH EXAMPLES\n");
+            for example in examples {
+                page.push_str(&format!(
+                    ".PP\n\\fB{}\\fR {}\n",
+                    roff_escape(bin_name),
+                    roff_escape(&example)
+                ));
+            }
+        }
+        if let Some(after_help) = &self.after_help {
+            page.push_str(&format!(
+                ".SH NOTES\n{}\n",
+                roff_escape(after_help.trim_end())
+            ));
+        }
+        page
+    }
+
+    /// Writes OPTIONS entries of visible arguments, followed by arguments of attached scopes.
+    /// Tags and language are taken from the top-level list, like in help_message.
+    fn write_man_options(
+        &self,
+        page: &mut String,
+        hidden_tags: &[String],
+        language: Option<&str>,
+        requirement: Option<&str>,
+    ) {
+        for spec in self
+            .iter_specs()
+            .filter(|spec| !spec.has_any_tag(hidden_tags))
+        {
+            let mut names = Vec::new();
+            if let Some(short) = spec.identification().short() {
+                names.push(format!("\\fB\\-{}\\fR", short));
+            }
+            if let Some(long) = spec.identification().long() {
+                names.push(format!("\\fB\\-\\-{}\\fR", roff_escape(long)));
+            }
+            page.push_str(&format!(".TP\n{}\n", names.join(", ")));
+            let mut description = spec
+                .description_for(language)
+                .map(trim_description)
+                .unwrap_or_default();
+            if let Some(requirement) = requirement {
+                if !description.is_empty() {
+                    description.push(' ');
+                }
+                description.push_str(requirement);
+            }
+            if !description.is_empty() {
+                // Paragraphs are kept indented under the names.
+                let paragraphs: Vec<String> = description.split("\n\n").map(roff_escape).collect();
+                page.push_str(&format!("{}\n", paragraphs.join("\n.IP\n")));
+            }
+        }
+        for scope in &self.scopes {
+            let requirement = format!("(requires {})", scope.guard);
+            scope
+                .list
+                .write_man_options(page, hidden_tags, language, Some(&requirement));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        argument::{
+            legacy_argument::{ArgType, Argument},
+            parsable_argument::ParsableValueArgument,
+            ArgumentIdentification,
+        },
+        ArgumentList,
+    };
+
+    #[test]
+    fn generate_man_page_works() {
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("path")))
+                .set_description("Path to a file-like object");
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_str);
+        assert_eq!(
+            args_list.generate_man_page("my-app"),
+            ".TH MY\\-APP 1\n.SH NAME\nmy\\-app\n.SH SYNOPSIS\n\\fBmy\\-app\\fR [OPTIONS]\n.SH OPTIONS\n.TP\n\\fB\\-\\-path\\fR\nPath to a file\\-like object\n"
+        );
+    }
+
+    #[test]
+    fn generate_man_page_keeps_paragraphs() {
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Short('p'))
+                .set_description("\n    Input file.\n    Must exist.\n\n    Use - for stdin.\n");
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_str);
+        assert!(args_list
+            .generate_man_page("app")
+            .ends_with(".TP\n\\fB\\-p\\fR\nInput file.\nMust exist.\n.IP\nUse \\- for stdin.\n"));
+    }
+
+    #[test]
+    fn generate_man_page_escapes_control_lines() {
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Short('p'))
+                .set_description("Skips files:\n.git and\n'quoted' names.");
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_str);
+        assert!(args_list
+            .generate_man_page("app")
+            .ends_with("Skips files:\n\\&.git and\n\\&'quoted' names.\n"));
+    }
+
+    #[test]
+    fn generate_man_page_lists_scoped_arguments() {
+        let mut argument_threads = ParsableValueArgument::new_integer(
+            ArgumentIdentification::Long(String::from("threads")),
+        )
+        .set_description("Worker count.");
+        let mut advanced = ArgumentList::new();
+        advanced.register_parsable(&mut argument_threads);
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_long("advanced", ArgType::Flag));
+        args_list.add_scope("advanced", advanced);
+        assert!(args_list.generate_man_page("app").ends_with(
+            ".TP\n\\fB\\-\\-advanced\\fR\n.TP\n\\fB\\-\\-threads\\fR\nWorker count. (requires \\-\\-advanced)\n"
+        ));
+    }
+
+    #[test]
+    fn generate_man_page_lists_examples() {
+        let mut argument_str =
+            ParsableValueArgument::new_string(ArgumentIdentification::Short('p'))
+                .add_example("-p ./config.toml");
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_str);
+        args_list.set_after_help("See also: app-config(5)");
+        assert!(args_list.generate_man_page("app").ends_with(
+            ".SH EXAMPLES\n.PP\n\\fBapp\\fR \\-p ./config.toml\n.SH NOTES\nSee also: app\\-config(5)\n"
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+
+use crate::{error::ParseError, token::Token, ArgumentList};
+
+/// Change of argument between versions of the program, registered with
+/// ArgumentList::add_migration. Names are compared with names of options as typed, including
+/// prefix (e.g. `--colour` or `-c`). Values of arguments and values after `--` are never migrated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Migration {
+    /// Argument was renamed. Old name is replaced with the new one while parsing and a warning
+    /// is recorded.
+    Renamed {
+        from: String,
+        to: String,
+        note: String,
+    },
+    /// Argument was removed. Using it fails with ParseError::RemovedArgument.
+    Removed { name: String, note: String },
+}
+
+impl Migration {
+    pub fn renamed(from: &str, to: &str, note: &str) -> Migration {
+        Migration::Renamed {
+            from: String::from(from),
+            to: String::from(to),
+            note: String::from(note),
+        }
+    }
+
+    pub fn removed(name: &str, note: &str) -> Migration {
+        Migration::Removed {
+            name: String::from(name),
+            note: String::from(note),
+        }
+    }
+}
+
+/// Old name of renamed argument found in input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationWarning {
+    pub from: String,
+    pub to: String,
+    pub note: String,
+}
+
+impl fmt::Display for MigrationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is deprecated, use {} instead.", self.from, self.to)?;
+        if !self.note.is_empty() {
+            write!(f, " {}", self.note)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Registers renamed or removed argument. Warnings about renamed arguments used in the last
+    /// parse are returned by migration_warnings, passed to ParserConfig::on_parse_complete and
+    /// written to ParserConfig::error_output by parse_or_exit.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList, argument::legacy_argument::*, error::ParseError, migrations::Migration,
+    /// };
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(None, Some("color"), ArgType::Value).unwrap());
+    /// args_list.add_migration(Migration::renamed("--colour", "--color", ""));
+    /// args_list.add_migration(Migration::removed("--fancy", "Use --color=always instead."));
+    /// args_list
+    ///     .parse_args(vec![String::from("--colour=never")])
+    ///     .unwrap();
+    /// assert_eq!(args_list.parsed().value_of("color"), Some("never"));
+    /// assert_eq!(
+    ///     format!("{}", args_list.migration_warnings()[0]),
+    ///     "--colour is deprecated, use --color instead."
+    /// );
+    /// assert_eq!(
+    ///     args_list.parse_args(vec![String::from("--fancy")]),
+    ///     Err(ParseError::RemovedArgument {
+    ///         argument: String::from("--fancy"),
+    ///         note: String::from("Use --color=always instead."),
+    ///     })
+    /// );
+    /// ```
+    pub fn add_migration(&mut self, migration: Migration) {
+        self.migrations.push(migration);
+    }
+
+    /// Warnings about renamed arguments used in the last parse.
+    pub fn migration_warnings(&self) -> &Vec<MigrationWarning> {
+        &self.migration_warnings
+    }
+
+    /// Returns input value naming an option with old name of renamed argument replaced with the
+    /// new one, keeping value attached with `=`. Old names in a group of short options can be
+    /// replaced only with other short names. Fails when removed argument is used.
+    pub(crate) fn migrate_token(
+        &mut self,
+        word: &str,
+        token: &Token<'_>,
+    ) -> Result<Option<String>, ParseError> {
+        match token {
+            Token::Short(_) => self.migrate_name(word),
+            Token::Long {
+                prefix,
+                name,
+                value,
+            } => {
+                let migrated = self.migrate_name(&format!("{}{}", prefix, name))?;
+                Ok(migrated.map(|to| match value {
+                    Some(value) => format!("{}={}", to, value),
+                    None => to,
+                }))
+            }
+            Token::Bundle(options) => {
+                let mut bundle = String::from(&word[..1]);
+                let mut renamed = false;
+                for option in options {
+                    let name = format!("-{}", option);
+                    match self.migrate_name(&name)? {
+                        Some(to) => match to.strip_prefix('-') {
+                            Some(short) if short.chars().count() == 1 => {
+                                bundle.push_str(short);
+                                renamed = true;
+                            }
+                            _ => {
+                                return Err(ParseError::RemovedArgument {
+                                    argument: name,
+                                    note: format!("Use {} instead.", to),
+                                })
+                            }
+                        },
+                        None => bundle.push(*option),
+                    }
+                }
+                Ok(Some(bundle).filter(|_| renamed))
+            }
+            Token::Value => Ok(None),
+        }
+    }
+
+    /// Finds migration of argument given with name, recording warning when it was renamed.
+    /// Returns the new name.
+    fn migrate_name(&mut self, name: &str) -> Result<Option<String>, ParseError> {
+        let migration = self.migrations.iter().find(|migration| match migration {
+            Migration::Renamed { from, .. } => from == name,
+            Migration::Removed { name: removed, .. } => removed == name,
+        });
+        match migration {
+            Some(Migration::Renamed { from, to, note }) => {
+                self.migration_warnings.push(MigrationWarning {
+                    from: from.clone(),
+                    to: to.clone(),
+                    note: note.clone(),
+                });
+                Ok(Some(to.clone()))
+            }
+            Some(Migration::Removed { name, note }) => Err(ParseError::RemovedArgument {
+                argument: name.clone(),
+                note: note.clone(),
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Migration, MigrationWarning};
+    use crate::{
+        argument::legacy_argument::{ArgType, Argument},
+        error::ParseError,
+        options::ParserOptions,
+        ArgumentList,
+    };
+
+    fn new_list() -> ArgumentList<'static> {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true).permute(true));
+        args_list.append_arg(Argument::new_long("output", ArgType::ValueList));
+        args_list.append_arg(Argument::new_short('q', ArgType::Flag));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.add_migration(Migration::renamed(
+            "-o",
+            "--output",
+            "Short form was dropped.",
+        ));
+        args_list.add_migration(Migration::renamed("-x", "-v", ""));
+        args_list
+    }
+
+    #[test]
+    fn migrations_apply_to_option_names() {
+        let input = |values: &[&str]| values.iter().map(|v| String::from(*v)).collect();
+        let mut args_list = new_list();
+        args_list
+            .parse_args(input(&["-o", "-o", "-qx", "--output=-o", "--", "-o"]))
+            .unwrap();
+        // Values of arguments and values after `--` keep old names.
+        assert_eq!(args_list.values_of("output"), vec!["-o", "-o"]);
+        assert_eq!(args_list.get_dangling_values(), &vec!["-o"]);
+        assert_eq!(args_list.flag_count('v'), 1);
+        let warning = MigrationWarning {
+            from: String::from("-o"),
+            to: String::from("--output"),
+            note: String::from("Short form was dropped."),
+        };
+        assert_eq!(args_list.migration_warnings()[0], warning);
+        assert_eq!(args_list.migration_warnings().len(), 2);
+        assert_eq!(
+            format!("{}", args_list.migration_warnings()[0]),
+            "-o is deprecated, use --output instead. Short form was dropped."
+        );
+        // Long name cannot be given in a group of short options.
+        let mut args_list = new_list();
+        assert_eq!(
+            args_list.parse_args(input(&["-qo", "file"])),
+            Err(ParseError::RemovedArgument {
+                argument: String::from("-o"),
+                note: String::from("Use --output instead."),
+            })
+        );
+        let mut args_list = new_list();
+        args_list.parse_args(input(&["file"])).unwrap();
+        assert!(args_list.migration_warnings().is_empty());
+    }
+}
@@ -0,0 +1,321 @@
+/// Decides what parse_args does after encountering an error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorMode {
+    /// Stop at the first error and return it.
+    FailFast,
+    /// Keep parsing after errors and return all of them in ParseError::Multiple, so user can fix
+    /// the whole command line at once.
+    Aggregate,
+}
+
+/// Prefix introducing long options.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prefix {
+    /// Long options are written as `--name`.
+    DoubleDash,
+    /// Long options can also be written as `-name`, like in `find` or `java`. Two character
+    /// values (`-v`) are still recognized as short options and short option bundling is not
+    /// possible.
+    SingleDash,
+}
+
+/// Decides how empty and whitespace-only input values are treated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlankTokens {
+    /// Treat them like any other value. They are never recognized as arguments, so they are
+    /// consumed as values of preceding argument or become dangling values.
+    Value,
+    /// Remove them from input before parsing.
+    Skip,
+    /// Fail parsing with ParseError::BlankValue.
+    Error,
+}
+
+/// Decides meaning of a lone `-` which is not consumed as a value of preceding argument.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoneDash {
+    /// Treat it as a dangling value.
+    Positional,
+    /// Treat it as the conventional name of standard input. It is not stored among dangling
+    /// values and ParsedArgs::reads_stdin returns true. Its position is kept by
+    /// ParsedArgs::iter_in_order as ParsedItem::Stdin.
+    Stdin,
+    /// Fail parsing with ParseError::LoneDash.
+    Error,
+}
+
+/// Conformance to POSIX Utility Syntax Guidelines enforced while parsing. Input breaking them
+/// fails with ParseError::PosixViolation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compliance {
+    /// No additional rules.
+    None,
+    /// Options must precede operands, `-W` is reserved and long options are not allowed. The first
+    /// `--` which is not a value of an option ends options and is not stored as dangling value.
+    Posix,
+    /// Same as Compliance::Posix, but long options are allowed like in GNU tools.
+    Gnu,
+}
+
+///
+/// Limits of input accepted by parse_args. Protects services passing untrusted strings to the
+/// parser from excessive memory and time usage. Input is not limited by default, services opt in
+/// with ParserLimits::recommended or their own limits.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, options::{ParserLimits, ParserOptions}};
+///
+/// let mut args_list = ArgumentList::new();
+/// assert!(args_list.parse_args(vec![String::from("value"); 20_000]).is_ok());
+/// let mut args_list = ArgumentList::new();
+/// args_list.set_options(ParserOptions::new().limits(ParserLimits {
+///     max_tokens: 100,
+///     ..ParserLimits::recommended()
+/// }));
+/// assert!(args_list.parse_args(vec![String::from("value"); 101]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParserLimits {
+    /// Maximum number of input values.
+    pub max_tokens: usize,
+    /// Maximum length of a single input value in bytes.
+    pub max_value_length: usize,
+    /// Maximum number of values consumed by a single argument across all occurrences.
+    pub max_list_items: usize,
+}
+
+impl ParserLimits {
+    /// Limits which accept any input. Same as ParserLimits::default().
+    pub fn unlimited() -> ParserLimits {
+        ParserLimits {
+            max_tokens: usize::MAX,
+            max_value_length: usize::MAX,
+            max_list_items: usize::MAX,
+        }
+    }
+
+    /// Limits high enough for any command typed by a human.
+    pub fn recommended() -> ParserLimits {
+        ParserLimits {
+            max_tokens: 10_000,
+            // Same as the limit of a single argument on Linux (MAX_ARG_STRLEN).
+            max_value_length: 131_072,
+            max_list_items: 10_000,
+        }
+    }
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        ParserLimits::unlimited()
+    }
+}
+
+///
+/// Options changing how user input is interpreted by parse_args.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, options::ParserOptions};
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.set_options(ParserOptions::new().short_bundling(true));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    pub(crate) short_bundling: bool,
+    pub(crate) options_after_positionals: bool,
+    pub(crate) error_mode: ErrorMode,
+    pub(crate) limits: ParserLimits,
+    pub(crate) blank_tokens: BlankTokens,
+    pub(crate) long_prefix: Prefix,
+    pub(crate) preprocessors: Vec<fn(Vec<String>) -> Vec<String>>,
+    pub(crate) collect_stats: bool,
+    pub(crate) lone_dash: LoneDash,
+    pub(crate) compliance: Compliance,
+    pub(crate) permute: bool,
+    pub(crate) plus_options: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            short_bundling: false,
+            options_after_positionals: true,
+            error_mode: ErrorMode::FailFast,
+            limits: ParserLimits::default(),
+            blank_tokens: BlankTokens::Value,
+            long_prefix: Prefix::DoubleDash,
+            preprocessors: Vec::new(),
+            collect_stats: false,
+            lone_dash: LoneDash::Positional,
+            compliance: Compliance::None,
+            permute: false,
+            plus_options: false,
+        }
+    }
+}
+
+impl ParserOptions {
+    /// Creates options matching default parser behaviour.
+    pub fn new() -> ParserOptions {
+        ParserOptions::default()
+    }
+
+    /// When enabled, multiple short options can be grouped in a single token (`-vdo` is the same
+    /// as `-v -d -o`). Only the last option of a group can take a value. Disabled by default.
+    pub fn short_bundling(mut self, enabled: bool) -> ParserOptions {
+        self.short_bundling = enabled;
+        self
+    }
+
+    /// When disabled, the first dangling value ends parsing of arguments and every following input
+    /// value is treated as dangling value, like in many POSIX tools. Enabled by default, which
+    /// allows arguments anywhere in the input.
+    pub fn options_after_positionals(mut self, enabled: bool) -> ParserOptions {
+        self.options_after_positionals = enabled;
+        self
+    }
+
+    /// Enables GNU getopt style permutation: options can appear anywhere, operands are collected
+    /// in order and the first `--` which is not a value of an option ends options (it is not
+    /// stored as dangling value). When POSIXLY_CORRECT environment variable is set at parse time,
+    /// the first operand ends options instead, like in POSIX tools. Permits options after
+    /// operands in Compliance::Gnu and has no effect in Compliance::Posix. Disabled by default.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, options::ParserOptions};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.set_options(ParserOptions::new().permute(true));
+    /// args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+    /// let input = vec!["a", "-v", "b", "--", "-v"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// if std::env::var_os("POSIXLY_CORRECT").is_none() {
+    ///     assert_eq!(args_list.get_dangling_values(), &vec!["a", "b", "-v"]);
+    /// }
+    /// ```
+    pub fn permute(mut self, enabled: bool) -> ParserOptions {
+        self.permute = enabled;
+        self
+    }
+
+    /// Recognizes `+x` (and `+xy` with short_bundling) as short options, so flags created with
+    /// ParsableValueArgument::new_polarity can be enabled with `+x` and disabled with `-x`, like
+    /// options of shell `set`. Other arguments given with `+` behave as if given with `-`.
+    /// Disabled by default, so `+` values are dangling values.
+    pub fn plus_options(mut self, enabled: bool) -> ParserOptions {
+        self.plus_options = enabled;
+        self
+    }
+
+    /// Selects whether parsing stops at the first error. ErrorMode::FailFast by default.
+    pub fn error_mode(mut self, mode: ErrorMode) -> ParserOptions {
+        self.error_mode = mode;
+        self
+    }
+
+    /// Replaces limits of accepted input. Input is not limited by default.
+    pub fn limits(mut self, limits: ParserLimits) -> ParserOptions {
+        self.limits = limits;
+        self
+    }
+
+    /// Selects how empty and whitespace-only input values are treated. BlankTokens::Value by
+    /// default. Indexes of parsed items refer to input after blank values were skipped.
+    pub fn blank_tokens(mut self, blank_tokens: BlankTokens) -> ParserOptions {
+        self.blank_tokens = blank_tokens;
+        self
+    }
+
+    /// Selects prefix of long options. Prefix::DoubleDash by default.
+    pub fn long_prefix(mut self, prefix: Prefix) -> ParserOptions {
+        self.long_prefix = prefix;
+        self
+    }
+
+    /// Selects meaning of a lone `-`. LoneDash::Positional by default.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, options::{LoneDash, ParserOptions}};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.set_options(ParserOptions::new().lone_dash(LoneDash::Stdin));
+    /// args_list
+    ///     .parse_args(vec![String::from("file"), String::from("-")])
+    ///     .unwrap();
+    /// assert!(args_list.parsed().reads_stdin());
+    /// assert_eq!(args_list.get_dangling_values(), &vec![String::from("file")]);
+    /// ```
+    pub fn lone_dash(mut self, lone_dash: LoneDash) -> ParserOptions {
+        self.lone_dash = lone_dash;
+        self
+    }
+
+    /// Selects POSIX rules enforced while parsing. Compliance::None by default. Every mode other
+    /// than Compliance::None implies disabled options_after_positionals, unless options are
+    /// permuted in Compliance::Gnu.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList, argument::legacy_argument::*, options::{Compliance, ParserOptions},
+    /// };
+    ///
+    /// let new_list = || {
+    ///     let mut args_list = ArgumentList::new();
+    ///     args_list.set_options(ParserOptions::new().compliance(Compliance::Posix));
+    ///     args_list.append_arg(Argument::new(Some('v'), Some("verbose"), ArgType::Flag).unwrap());
+    ///     args_list
+    /// };
+    /// let input = |values: &[&str]| values.iter().map(|v| String::from(*v)).collect();
+    /// assert!(new_list().parse_args(input(&["-v", "file", "--", "-v"])).is_ok());
+    /// assert!(new_list().parse_args(input(&["file", "-v"])).is_err());
+    /// assert!(new_list().parse_args(input(&["--verbose"])).is_err());
+    /// ```
+    pub fn compliance(mut self, compliance: Compliance) -> ParserOptions {
+        self.compliance = compliance;
+        self
+    }
+
+    /// When enabled, parse_args counts work it does and makes it available through
+    /// ArgumentList::stats. Disabled by default.
+    pub fn collect_stats(mut self, enabled: bool) -> ParserOptions {
+        self.collect_stats = enabled;
+        self
+    }
+
+    /// Adds function transforming whole input before it is parsed, e.g. expanding aliases or
+    /// reading arguments from `@file`. Functions run in order they were added. Limits are checked
+    /// on transformed input.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, options::ParserOptions};
+    ///
+    /// fn expand_aliases(input: Vec<String>) -> Vec<String> {
+    ///     input
+    ///         .into_iter()
+    ///         .flat_map(|word| match word.as_str() {
+    ///             "-q" => vec![String::from("--verbosity"), String::from("0")],
+    ///             _ => vec![word],
+    ///         })
+    ///         .collect()
+    /// }
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.set_options(ParserOptions::new().preprocess(expand_aliases));
+    /// args_list.append_arg(Argument::new(None, Some("verbosity"), ArgType::Value).unwrap());
+    /// args_list.parse_args(vec![String::from("-q")]).unwrap();
+    /// assert_eq!(args_list.parsed().value_of("verbosity"), Some("0"));
+    /// ```
+    pub fn preprocess(mut self, preprocessor: fn(Vec<String>) -> Vec<String>) -> ParserOptions {
+        self.preprocessors.push(preprocessor);
+        self
+    }
+}
@@ -0,0 +1,1004 @@
+use crate::{
+    argument::ArgumentIdentification,
+    completion::single_quoted,
+    source::{self, ConfigDiff, Source, SourceStack},
+};
+use std::{collections::BTreeMap, fmt, ops::Range, process::Command};
+
+/// Single occurrence of an argument in user input together with raw values it consumed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedOccurrence {
+    identification: ArgumentIdentification,
+    values: Vec<String>,
+    /// Whether values were given in the same input value as the name.
+    inline: bool,
+    index: usize,
+    /// Indexes of separate input values holding values.
+    value_range: Range<usize>,
+    form: String,
+    preset: Option<String>,
+    source: Source,
+}
+
+impl ParsedOccurrence {
+    pub(crate) fn new(
+        identification: ArgumentIdentification,
+        values: Vec<String>,
+        index: usize,
+    ) -> Self {
+        let value_range = index + 1..index + 1 + values.len();
+        ParsedOccurrence {
+            identification,
+            values,
+            inline: false,
+            index,
+            value_range,
+            form: String::new(),
+            preset: None,
+            source: Source::Cli,
+        }
+    }
+
+    /// Marks values as given inline (`--path=a`), in the same input value as the name.
+    pub(crate) fn with_inline_values(mut self) -> Self {
+        self.inline = true;
+        self.value_range = self.index + 1..self.index + 1;
+        self
+    }
+
+    /// Sets indexes of input values consumed by the argument.
+    pub(crate) fn with_value_range(mut self, value_range: Range<usize>) -> Self {
+        self.value_range = value_range;
+        self
+    }
+
+    /// Sets input value which named the argument.
+    pub(crate) fn with_form(mut self, form: &str) -> Self {
+        self.form = String::from(form);
+        self
+    }
+
+    /// Marks occurrence as given by preset with given name.
+    pub(crate) fn with_preset(mut self, preset: &str) -> Self {
+        self.preset = Some(String::from(preset));
+        self.value_range = self.index + 1..self.index + 1;
+        self
+    }
+
+    /// Index of input value which named the argument. For arguments given by a preset, index of
+    /// `--preset` using it.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Input value which named the argument exactly as typed by the user (e.g. `-p`, `--path=a`
+    /// or `-vp` when bundled).
+    pub fn form(&self) -> &str {
+        &self.form
+    }
+
+    /// Name of preset which gave the argument (outermost one for nested presets). None when the
+    /// argument was given explicitly.
+    pub fn preset(&self) -> Option<&str> {
+        self.preset.as_deref()
+    }
+
+    /// Source layer which gave the argument. Occurrences parsed from input, including ones given
+    /// by presets, come from Source::Cli.
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    /// Identification of argument which handled this occurrence.
+    pub fn identification(&self) -> &ArgumentIdentification {
+        &self.identification
+    }
+
+    /// Raw input values consumed by the argument.
+    pub fn values(&self) -> &Vec<String> {
+        &self.values
+    }
+
+    /// Index of input value containing value at position `i` of values. Values given inline
+    /// (`--path=a`) or by a preset share index with the name.
+    pub fn value_index(&self, i: usize) -> usize {
+        if self.value_range.is_empty() {
+            self.index
+        } else {
+            self.value_range.start + i
+        }
+    }
+
+    /// Indexes of input values consumed by the argument after the one naming it. Empty when
+    /// values were given inline (`--path=a`) or by a preset.
+    pub fn value_range(&self) -> Range<usize> {
+        self.value_range.clone()
+    }
+
+    /// Check if occurrence belongs to argument identified by long name or single character short name.
+    /// Names can be given with prefix (`--path`, `-p`).
+    pub fn is_named(&self, name: &str) -> bool {
+        self.identification.is_named(name)
+    }
+}
+
+/// Successful result of ArgumentList::parse_args_outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseOutcome {
+    /// Whole input was parsed and validated.
+    Complete,
+    /// Terminal argument was given. Input after it was not parsed and arguments were not validated.
+    Terminated(ArgumentIdentification),
+}
+
+/// Value of an argument together with index of input value containing it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexedValue<'p> {
+    /// Identification of argument which consumed the value.
+    pub identification: &'p ArgumentIdentification,
+    pub index: usize,
+    pub value: &'p str,
+}
+
+/// Element of user input, either an argument occurrence or a value not attached to any argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedItem {
+    Argument(ParsedOccurrence),
+    Positional {
+        value: String,
+        index: usize,
+    },
+    /// Lone `-` naming standard input, given while parsing with LoneDash::Stdin. It is not one of
+    /// the positional values, but keeps its place among them.
+    Stdin {
+        index: usize,
+    },
+}
+
+impl ParsedItem {
+    /// Index of input value at which this item starts.
+    pub fn index(&self) -> usize {
+        match self {
+            ParsedItem::Argument(occurrence) => occurrence.index(),
+            ParsedItem::Positional { index, .. } | ParsedItem::Stdin { index } => *index,
+        }
+    }
+}
+
+///
+/// Raw view of parse results shared by legacy and parsable arguments. Contains every occurrence of
+/// an argument (with values it consumed as typed by the user) and every dangling value. Arguments
+/// are looked up by long name or by short name given as a single character string.
+///
+/// Everything is kept in order it was given by the user, so iteration results and returned
+/// lists are deterministic and match input order.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+/// args_list
+///     .parse_args(vec![String::from("-p"), String::from("/file")])
+///     .unwrap();
+/// assert_eq!(args_list.parsed().value_of("path"), Some("/file"));
+/// assert_eq!(args_list.parsed().value_of("p"), Some("/file"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedArgs {
+    items: Vec<ParsedItem>,
+    defaults: BTreeMap<String, String>,
+    /// Keys (see source::key) of secret arguments, whose values are never displayed or serialized.
+    secrets: Vec<String>,
+    /// Name and results of selected subcommand.
+    subcommand: Option<Box<(String, ParsedArgs)>>,
+}
+
+impl ParsedArgs {
+    pub(crate) fn push_occurrence(&mut self, occurrence: ParsedOccurrence) {
+        self.items.push(ParsedItem::Argument(occurrence));
+    }
+
+    pub(crate) fn set_default(&mut self, name: String, default: &str) {
+        self.defaults.insert(name, String::from(default));
+    }
+
+    pub(crate) fn set_secret(&mut self, key: String) {
+        if !self.secrets.contains(&key) {
+            self.secrets.push(key);
+        }
+    }
+
+    /// Check if argument stored under name returned by source::key is secret.
+    fn is_secret_key(&self, key: &str) -> bool {
+        self.secrets.iter().any(|secret| secret == key)
+    }
+
+    /// Default value of argument stored under name returned by source::key.
+    pub(crate) fn default_of(&self, key: &str) -> Option<&str> {
+        self.defaults.get(key).map(String::as_str)
+    }
+
+    /// Removes positional values starting from the first one, together with standard input
+    /// given after it. Used when they were given to a subcommand.
+    pub(crate) fn truncate_positionals(&mut self) {
+        let first = self.items.iter().find_map(|item| match item {
+            ParsedItem::Positional { index, .. } => Some(*index),
+            _ => None,
+        });
+        if let Some(first) = first {
+            self.items.retain(|item| match item {
+                ParsedItem::Argument(_) => true,
+                ParsedItem::Positional { index, .. } | ParsedItem::Stdin { index } => {
+                    *index < first
+                }
+            });
+        }
+    }
+
+    pub(crate) fn push_stdin(&mut self, index: usize) {
+        self.items.push(ParsedItem::Stdin { index });
+    }
+
+    pub(crate) fn set_subcommand(&mut self, name: &str, parsed: ParsedArgs) {
+        self.subcommand = Some(Box::new((String::from(name), parsed)));
+    }
+
+    pub(crate) fn push_positional(&mut self, value: &str, index: usize) {
+        self.items.push(ParsedItem::Positional {
+            value: String::from(value),
+            index,
+        });
+    }
+
+    /// Iterates over argument occurrences and positional values interleaved in order they were
+    /// given by the user. Needed when relative order matters (e.g. `-e expr file -e expr2`).
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, parsed::ParsedItem};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new_short('e', ArgType::ValueList));
+    /// let input = vec!["-e", "expr", "file", "-e", "expr2"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// let indexes: Vec<usize> = args_list.parsed().iter_in_order().map(ParsedItem::index).collect();
+    /// assert_eq!(indexes, vec![0, 2, 3]);
+    /// ```
+    pub fn iter_in_order(&self) -> std::slice::Iter<'_, ParsedItem> {
+        self.items.iter()
+    }
+
+    /// Name and results of subcommand selected with ArgumentList::parse_subcommand. Arguments of
+    /// subcommand are kept separately, so subcommands can use the same names as the main command.
+    pub fn subcommand(&self) -> Option<(&str, &ParsedArgs)> {
+        self.subcommand
+            .as_deref()
+            .map(|(name, parsed)| (name.as_str(), parsed))
+    }
+
+    /// Check if lone `-` was given while parsing with LoneDash::Stdin. Its position among
+    /// positional values is kept by iter_in_order as ParsedItem::Stdin.
+    pub fn reads_stdin(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| matches!(item, ParsedItem::Stdin { .. }))
+    }
+
+    /// All argument occurrences in order they were given.
+    pub fn occurrences(&self) -> impl Iterator<Item = &ParsedOccurrence> {
+        self.items.iter().filter_map(|item| match item {
+            ParsedItem::Argument(occurrence) => Some(occurrence),
+            ParsedItem::Positional { .. } | ParsedItem::Stdin { .. } => None,
+        })
+    }
+
+    /// All values not attached to any argument, in input order.
+    pub fn positionals(&self) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                ParsedItem::Argument(_) | ParsedItem::Stdin { .. } => None,
+                ParsedItem::Positional { value, .. } => Some(value.as_str()),
+            })
+            .collect()
+    }
+
+    /// Check if argument was given at least once.
+    pub fn is_present(&self, name: &str) -> bool {
+        self.occurrences().any(|o| o.is_named(name))
+    }
+
+    /// Count how many times argument was given.
+    pub fn occurrences_of(&self, name: &str) -> usize {
+        self.occurrences().filter(|o| o.is_named(name)).count()
+    }
+
+    /// Returns the last value given to argument.
+    pub fn value_of(&self, name: &str) -> Option<&str> {
+        self.values_of(name).last().copied()
+    }
+
+    /// Returns all values given to argument across all occurrences, in input order.
+    pub fn values_of(&self, name: &str) -> Vec<&str> {
+        self.occurrences()
+            .filter(|o| o.is_named(name))
+            .flat_map(|o| o.values.iter().map(|v| v.as_str()))
+            .collect()
+    }
+
+    /// Returns values of all arguments named in `names` with their input indexes, in input order.
+    /// Keeps relative order of values given to different arguments, which matters for
+    /// compiler-like programs (e.g. `-I a -L x -I b`).
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new_short('I', ArgType::ValueList));
+    /// args_list.append_arg(Argument::new(Some('L'), Some("lib"), ArgType::ValueList).unwrap());
+    /// let input = vec!["-I", "a", "--lib=x", "-I", "b"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// let values: Vec<(usize, &str)> = args_list
+    ///     .parsed()
+    ///     .indexed_values_of(&["I", "L"])
+    ///     .iter()
+    ///     .map(|v| (v.index, v.value))
+    ///     .collect();
+    /// assert_eq!(values, vec![(1, "a"), (2, "x"), (4, "b")]);
+    /// ```
+    pub fn indexed_values_of(&self, names: &[&str]) -> Vec<IndexedValue<'_>> {
+        self.occurrences()
+            .filter(|o| names.iter().any(|name| o.is_named(name)))
+            .flat_map(|o| {
+                o.values
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, value)| IndexedValue {
+                        identification: &o.identification,
+                        index: o.value_index(i),
+                        value,
+                    })
+            })
+            .collect()
+    }
+
+    /// Appends occurrences of arguments named in `names` and all positional values to arguments
+    /// of command, in input order. Arguments are written by long name when they have one
+    /// (`--name`), otherwise by short name, followed by values they consumed, so bundles are
+    /// split. Values given inline are kept in the same argument (`--name=value`). Defaults are
+    /// not forwarded.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::process::Command;
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(Some('i'), Some("ignore-case"), ArgType::Flag).unwrap());
+    /// args_list.append_arg(Argument::new(None, Some("color"), ArgType::Value).unwrap());
+    /// args_list.append_arg(Argument::new_short('x', ArgType::Flag));
+    /// let input = vec!["-i", "-x", "--color=never", "pattern"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// let command = args_list
+    ///     .parsed()
+    ///     .forward_to(Command::new("grep"), &["i", "color"]);
+    /// let forwarded: Vec<_> = command.get_args().collect();
+    /// assert_eq!(forwarded, vec!["--ignore-case", "--color=never", "pattern"]);
+    /// ```
+    pub fn forward_to(&self, mut command: Command, names: &[&str]) -> Command {
+        for item in &self.items {
+            match item {
+                ParsedItem::Argument(occurrence) => {
+                    if !names.iter().any(|name| occurrence.is_named(name)) {
+                        continue;
+                    }
+                    let identification = occurrence.identification();
+                    let name = match (identification.long(), identification.short()) {
+                        (Some(long), _) => format!("--{}", long),
+                        (None, Some(short)) => format!("-{}", short),
+                        (None, None) => continue,
+                    };
+                    match occurrence.values.first() {
+                        Some(value) if occurrence.inline => {
+                            command.arg(format!("{}={}", name, value));
+                        }
+                        _ => {
+                            command.arg(name);
+                            command.args(&occurrence.values);
+                        }
+                    }
+                }
+                ParsedItem::Positional { value, .. } => {
+                    command.arg(value);
+                }
+                ParsedItem::Stdin { .. } => {
+                    command.arg("-");
+                }
+            }
+        }
+        command
+    }
+}
+
+/// Writes values quoted and separated by commas, preceded by ` = `. Nothing is written for no values.
+pub(crate) fn write_values(f: &mut fmt::Formatter<'_>, values: &[String]) -> fmt::Result {
+    for (i, value) in values.iter().enumerate() {
+        let separator = if i == 0 { " = " } else { ", " };
+        write!(f, "{}{:?}", separator, value)?;
+    }
+    Ok(())
+}
+
+/// Writes values like write_values, but with values of secret arguments replaced by
+/// `<redacted>`.
+fn write_values_redacted(
+    f: &mut fmt::Formatter<'_>,
+    values: &[String],
+    secret: bool,
+) -> fmt::Result {
+    if secret && !values.is_empty() {
+        write!(f, " = <redacted>")
+    } else {
+        write_values(f, values)
+    }
+}
+
+///
+/// Lists every given argument with all its values and the source they came from, arguments with
+/// default values which were not given and positional values, one per line (e.g.
+/// `-p, --path = "/file" (from command line)`). Values of secret arguments are shown as
+/// `<redacted>`. Meant for `--show-args` style debugging output.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+/// args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+/// let input = vec!["-v", "--path", "/file", "file"];
+/// args_list
+///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+///     .unwrap();
+/// assert_eq!(
+///     format!("{}", args_list.parsed()),
+///     "-v (from command line)\n\
+///      -p, --path = \"/file\" (from command line)\n\
+///      \"file\" (positional)\n"
+/// );
+/// ```
+impl fmt::Display for ParsedArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut listed: Vec<(&ArgumentIdentification, Source)> = Vec::new();
+        for occurrence in self.occurrences() {
+            let identification = occurrence.identification();
+            let source = occurrence.source();
+            if listed.contains(&(identification, source)) {
+                continue;
+            }
+            listed.push((identification, source));
+            write!(f, "{}", identification)?;
+            let values: Vec<String> = self
+                .occurrences()
+                .filter(|o| o.identification() == identification && o.source() == source)
+                .flat_map(|o| o.values.iter().cloned())
+                .collect();
+            let secret = self.is_secret_key(&source::key(identification));
+            write_values_redacted(f, &values, secret)?;
+            writeln!(f, " (from {})", source)?;
+        }
+        for (name, default) in &self.defaults {
+            if self.is_present(name) {
+                continue;
+            }
+            let prefix = if name.chars().count() == 1 { "-" } else { "--" };
+            write!(f, "{}{}", prefix, name)?;
+            write_values_redacted(f, std::slice::from_ref(default), self.is_secret_key(name))?;
+            writeln!(f, " (from {})", Source::Default)?;
+        }
+        for item in &self.items {
+            match item {
+                ParsedItem::Positional { value, .. } => writeln!(f, "{:?} (positional)", value)?,
+                ParsedItem::Stdin { .. } => writeln!(f, "- (standard input)")?,
+                ParsedItem::Argument(_) => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Value of nested configuration built from parsed arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    /// Argument given without any value.
+    Flag(bool),
+    /// Argument given a single value.
+    Value(String),
+    /// Argument given multiple values.
+    List(Vec<String>),
+    /// Group of keys sharing the same prefix.
+    Map(BTreeMap<String, ConfigValue>),
+}
+
+impl ConfigValue {
+    fn from_values(values: Vec<&str>) -> ConfigValue {
+        match values.len() {
+            0 => ConfigValue::Flag(true),
+            1 => ConfigValue::Value(String::from(values[0])),
+            _ => ConfigValue::List(values.into_iter().map(String::from).collect()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ConfigValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ConfigValue::Flag(flag) => serializer.serialize_bool(*flag),
+            ConfigValue::Value(value) => serializer.serialize_str(value),
+            ConfigValue::List(values) => serializer.collect_seq(values),
+            ConfigValue::Map(map) => serializer.collect_map(map),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParsedItem {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        match self {
+            ParsedItem::Argument(occurrence) => {
+                let mut item = serializer.serialize_struct("ParsedItem", 6)?;
+                item.serialize_field("kind", "argument")?;
+                item.serialize_field("identification", &format!("{}", occurrence.identification))?;
+                item.serialize_field("form", &occurrence.form)?;
+                item.serialize_field("index", &occurrence.index)?;
+                item.serialize_field("values", &occurrence.values)?;
+                item.serialize_field("source", &occurrence.source)?;
+                item.end()
+            }
+            ParsedItem::Positional { value, index } => {
+                let mut item = serializer.serialize_struct("ParsedItem", 4)?;
+                item.serialize_field("kind", "positional")?;
+                item.serialize_field("value", value)?;
+                item.serialize_field("index", index)?;
+                item.serialize_field("source", &Source::Cli)?;
+                item.end()
+            }
+            ParsedItem::Stdin { index } => {
+                let mut item = serializer.serialize_struct("ParsedItem", 3)?;
+                item.serialize_field("kind", "stdin")?;
+                item.serialize_field("index", index)?;
+                item.serialize_field("source", &Source::Cli)?;
+                item.end()
+            }
+        }
+    }
+}
+
+/// Serializes every parsed item with its provenance: index of input value, form typed by the
+/// user and source layer.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParsedArgs {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Subcommand<'p>(&'p str, &'p ParsedArgs);
+
+        impl<'p> serde::Serialize for Subcommand<'p> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut subcommand = serializer.serialize_struct("Subcommand", 2)?;
+                subcommand.serialize_field("name", self.0)?;
+                subcommand.serialize_field("parsed", self.1)?;
+                subcommand.end()
+            }
+        }
+
+        let mut parsed = serializer.serialize_struct("ParsedArgs", 3)?;
+        parsed.serialize_field("items", &self.items)?;
+        parsed.serialize_field("defaults", &self.defaults)?;
+        match self.subcommand() {
+            Some((name, subcommand)) => {
+                parsed.serialize_field("subcommand", &Subcommand(name, subcommand))?
+            }
+            None => parsed.skip_field("subcommand")?,
+        }
+        parsed.end()
+    }
+}
+
+impl ParsedArgs {
+    /// Lists arguments given in command line with values different from their defaults. Useful
+    /// for `--show-config` style commands. See SourceStack::diff_defaults to include other sources.
+    pub fn diff_defaults(&self) -> Vec<ConfigDiff> {
+        let mut stack = SourceStack::new();
+        stack.merge_from(Source::Default, self.defaults.clone());
+        stack.merge_parsed(self);
+        stack.diff_defaults()
+    }
+
+    /// Builds nested configuration from arguments with long names split on separator
+    /// (`--db.host` and `--db.port` become keys `host` and `port` of map `db`). Arguments without
+    /// long name are skipped. When a name is also a prefix of other names, nested keys win.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, parsed::ConfigValue};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(None, Some("db.host"), ArgType::Value).unwrap());
+    /// args_list.append_arg(Argument::new(None, Some("db.port"), ArgType::Value).unwrap());
+    /// let input = vec!["--db.host", "localhost", "--db.port", "5432"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// let config = args_list.parsed().to_nested_map('.');
+    /// match &config["db"] {
+    ///     ConfigValue::Map(db) => {
+    ///         assert_eq!(db["host"], ConfigValue::Value(String::from("localhost")));
+    ///     }
+    ///     _ => panic!("db should be a map"),
+    /// }
+    /// ```
+    pub fn to_nested_map(&self, separator: char) -> BTreeMap<String, ConfigValue> {
+        let mut names: Vec<&str> = Vec::new();
+        for occurrence in self.occurrences() {
+            if let Some(name) = occurrence.identification().long() {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        // Shorter names first so that nested keys replace values of their prefixes.
+        names.sort_by_key(|name| name.matches(separator).count());
+        let mut root = BTreeMap::new();
+        for name in names {
+            let value = ConfigValue::from_values(self.values_of(name));
+            let mut keys: Vec<&str> = name.split(separator).collect();
+            let last = keys.pop().unwrap_or(name);
+            let mut map = &mut root;
+            for key in keys {
+                let entry = map
+                    .entry(String::from(key))
+                    .or_insert_with(|| ConfigValue::Map(BTreeMap::new()));
+                if !matches!(entry, ConfigValue::Map(_)) {
+                    *entry = ConfigValue::Map(BTreeMap::new());
+                }
+                map = match entry {
+                    ConfigValue::Map(map) => map,
+                    _ => unreachable!(),
+                };
+            }
+            map.entry(String::from(last)).or_insert(value);
+        }
+        root
+    }
+
+    ///
+    /// Formats arguments as shell variable assignments, one per line, which wrapper scripts can
+    /// `eval`. Variables are named after arguments the same way as in SourceStack::merge_env
+    /// (`path` becomes `MYAPP_PATH` for prefix `MYAPP_`). Arguments given without values are set
+    /// to `1`, values of arguments given multiple times are separated by newlines and defaults
+    /// are exported for arguments not given. Values are quoted for POSIX shells. Characters not
+    /// allowed in shell variable names are replaced with `_` and names starting with a digit are
+    /// prefixed with `_`. Positional values are not exported.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+    ///
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+    /// args_list.append_arg(Argument::new(Some('d'), Some("dry-run"), ArgType::Flag).unwrap());
+    /// args_list.append_arg(Argument::new_long("name", ArgType::Value));
+    /// let input = vec!["-p", "/file", "--dry-run", "--name", "it's"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     args_list.parsed().to_env_exports("MYAPP_"),
+    ///     "MYAPP_PATH=/file\nMYAPP_DRY_RUN=1\nMYAPP_NAME='it'\\''s'\n"
+    /// );
+    /// ```
+    pub fn to_env_exports(&self, prefix: &str) -> String {
+        let mut exported: Vec<String> = Vec::new();
+        let mut exports = String::new();
+        for occurrence in self.occurrences() {
+            let name = source::key(occurrence.identification());
+            if exported.contains(&name) {
+                continue;
+            }
+            let values = self.values_of(&name);
+            let value = if values.is_empty() {
+                String::from("1")
+            } else {
+                values.join("\n")
+            };
+            exports.push_str(&export_line(prefix, &name, &value));
+            exported.push(name);
+        }
+        for (name, default) in &self.defaults {
+            if !exported.contains(name) {
+                exports.push_str(&export_line(prefix, name, default));
+            }
+        }
+        exports
+    }
+}
+
+/// Quotes value for POSIX shells. Values made only of characters safe in shell words are not
+/// quoted.
+pub(crate) fn shell_word(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-:,+@%".contains(c));
+    if safe {
+        String::from(value)
+    } else {
+        single_quoted(value)
+    }
+}
+
+/// Single `NAME=value` line of ParsedArgs::to_env_exports.
+fn export_line(prefix: &str, name: &str, value: &str) -> String {
+    format!(
+        "{}={}\n",
+        shell_variable(&source::env_variable(prefix, name)),
+        shell_word(value)
+    )
+}
+
+/// Turns variable name into a valid shell identifier (`[A-Za-z_][A-Za-z0-9_]*`). Other
+/// characters are replaced with `_` and names not starting with a letter or `_` are prefixed
+/// with `_`.
+fn shell_variable(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.chars().next() {
+        Some(c) if !c.is_ascii_digit() => name,
+        _ => format!("_{}", name),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConfigValue, ParsedArgs, ParsedItem, ParsedOccurrence};
+    use crate::{
+        argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+        ArgumentList,
+    };
+
+    fn parsed() -> ParsedArgs {
+        let mut parsed = ParsedArgs::default();
+        parsed.push_occurrence(ParsedOccurrence::new(
+            ArgumentIdentification::Both('p', String::from("path")),
+            vec![String::from("/a")],
+            0,
+        ));
+        parsed.push_occurrence(ParsedOccurrence::new(
+            ArgumentIdentification::Short('v'),
+            Vec::new(),
+            2,
+        ));
+        parsed.push_positional("file", 3);
+        parsed.push_occurrence(ParsedOccurrence::new(
+            ArgumentIdentification::Both('p', String::from("path")),
+            vec![String::from("/b")],
+            4,
+        ));
+        parsed
+    }
+
+    #[test]
+    fn lookup_works() {
+        let parsed = parsed();
+        assert!(parsed.is_present("v"));
+        assert!(parsed.is_present("path"));
+        assert!(!parsed.is_present("x"));
+        assert_eq!(parsed.occurrences_of("p"), 2);
+        assert_eq!(parsed.value_of("path"), Some("/b"));
+        assert_eq!(parsed.values_of("p"), vec!["/a", "/b"]);
+        assert_eq!(parsed.value_of("v"), None);
+        assert_eq!(parsed.positionals(), vec!["file"]);
+    }
+
+    #[test]
+    fn iter_in_order_works() {
+        let parsed = parsed();
+        let items: Vec<&ParsedItem> = parsed.iter_in_order().collect();
+        assert_eq!(items.len(), 4);
+        assert_eq!(
+            items[2],
+            &ParsedItem::Positional {
+                value: String::from("file"),
+                index: 3
+            }
+        );
+        assert_eq!(
+            parsed
+                .iter_in_order()
+                .map(ParsedItem::index)
+                .collect::<Vec<usize>>(),
+            vec![0, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn indexed_values_of_works() {
+        let mut parsed = parsed();
+        parsed.push_occurrence(
+            ParsedOccurrence::new(
+                ArgumentIdentification::Short('v'),
+                vec![String::from("x")],
+                6,
+            )
+            .with_inline_values(),
+        );
+        let indexes: Vec<(usize, &str)> = parsed
+            .indexed_values_of(&["path", "v"])
+            .iter()
+            .map(|v| (v.index, v.value))
+            .collect();
+        assert_eq!(indexes, vec![(1, "/a"), (5, "/b"), (6, "x")]);
+        assert!(parsed.indexed_values_of(&["x"]).is_empty());
+    }
+
+    #[test]
+    fn display_works() {
+        let mut parsed = parsed();
+        parsed.set_default(String::from("jobs"), "4");
+        parsed.set_default(String::from("path"), "/default");
+        assert_eq!(
+            format!("{}", parsed),
+            "-p, --path = \"/a\", \"/b\" (from command line)\n\
+             -v (from command line)\n\
+             --jobs = \"4\" (from default)\n\
+             \"file\" (positional)\n"
+        );
+        assert_eq!(format!("{}", ParsedArgs::default()), "");
+    }
+
+    #[test]
+    fn display_redacts_secrets() {
+        let mut argument_token =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("token")))
+                .set_secret(true);
+        let mut argument_key =
+            ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("key")))
+                .set_secret(true)
+                .set_default(String::from("none"));
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_token);
+        args_list.register_parsable(&mut argument_key);
+        args_list
+            .parse_args(vec![String::from("--token=hunter2")])
+            .unwrap();
+        assert_eq!(
+            format!("{}", args_list.parsed()),
+            "--token = <redacted> (from command line)\n\
+             --key = <redacted> (from default)\n"
+        );
+    }
+
+    #[test]
+    fn forward_to_works() {
+        let mut parsed = parsed();
+        parsed.push_occurrence(
+            ParsedOccurrence::new(
+                ArgumentIdentification::Long(String::from("color")),
+                vec![String::from("never")],
+                6,
+            )
+            .with_inline_values(),
+        );
+        let command = parsed.forward_to(std::process::Command::new("tool"), &["p", "x", "color"]);
+        let forwarded: Vec<_> = command.get_args().collect();
+        assert_eq!(
+            forwarded,
+            vec!["--path", "/a", "file", "--path", "/b", "--color=never"]
+        );
+    }
+
+    #[test]
+    fn to_env_exports_works() {
+        let mut parsed = parsed();
+        parsed.set_default(String::from("path"), "/default");
+        parsed.set_default(String::from("db.host"), "");
+        assert_eq!(
+            parsed.to_env_exports("APP_"),
+            "APP_PATH='/a\n/b'\nAPP_V=1\nAPP_DB_HOST=''\n"
+        );
+        let mut parsed = ParsedArgs::default();
+        parsed.set_default(String::from("2fa"), "on");
+        parsed.set_default(String::from("x$(reboot)"), "1");
+        parsed.set_default(String::from("zażółć"), "1");
+        assert_eq!(
+            parsed.to_env_exports(""),
+            "_2FA=on\nX__REBOOT_=1\nZA____=1\n"
+        );
+    }
+
+    #[test]
+    fn to_nested_map_works() {
+        let mut parsed = ParsedArgs::default();
+        parsed.push_occurrence(ParsedOccurrence::new(
+            ArgumentIdentification::Long(String::from("db.host")),
+            vec![String::from("localhost")],
+            0,
+        ));
+        parsed.push_occurrence(ParsedOccurrence::new(
+            ArgumentIdentification::Long(String::from("db.replicas")),
+            vec![String::from("a"), String::from("b")],
+            2,
+        ));
+        parsed.push_occurrence(ParsedOccurrence::new(
+            ArgumentIdentification::Long(String::from("verbose")),
+            Vec::new(),
+            5,
+        ));
+        let config = parsed.to_nested_map('.');
+        assert_eq!(config["verbose"], ConfigValue::Flag(true));
+        match &config["db"] {
+            ConfigValue::Map(db) => {
+                assert_eq!(db["host"], ConfigValue::Value(String::from("localhost")));
+                assert_eq!(
+                    db["replicas"],
+                    ConfigValue::List(vec![String::from("a"), String::from("b")])
+                );
+            }
+            other => panic!("unexpected value {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parsed_args_serialize_provenance() {
+        let mut parsed = ParsedArgs::default();
+        parsed.push_occurrence(
+            ParsedOccurrence::new(
+                ArgumentIdentification::Both('p', String::from("path")),
+                vec![String::from("/a")],
+                0,
+            )
+            .with_form("--path=/a"),
+        );
+        parsed.push_positional("file", 1);
+        assert_eq!(
+            serde_json::to_string(&parsed).unwrap(),
+            concat!(
+                r#"{"items":[{"kind":"argument","identification":"-p, --path","form":"--path=/a","#,
+                r#""index":0,"values":["/a"],"source":"command line"},"#,
+                r#"{"kind":"positional","value":"file","index":1,"source":"command line"}],"#,
+                r#""defaults":{}}"#
+            )
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn nested_map_serializes() {
+        let mut parsed = ParsedArgs::default();
+        parsed.push_occurrence(ParsedOccurrence::new(
+            ArgumentIdentification::Long(String::from("db.port")),
+            vec![String::from("5432")],
+            0,
+        ));
+        assert_eq!(
+            serde_json::to_string(&parsed.to_nested_map('.')).unwrap(),
+            r#"{"db":{"port":"5432"}}"#
+        );
+    }
+}
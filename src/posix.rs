@@ -0,0 +1,154 @@
+use crate::{
+    error::{ParseError, PosixRule},
+    options::Compliance,
+    token::Token,
+    ArgumentList,
+};
+
+impl<'a> ArgumentList<'a> {
+    /// Checks value naming arguments against rules selected by ParserOptions::compliance.
+    pub(crate) fn check_compliance(&self, word: &str, token: &Token) -> Result<(), ParseError> {
+        let rule = match (self.options.compliance, token) {
+            (Compliance::None, _) => return Ok(()),
+            (Compliance::Posix, Token::Long { .. }) => PosixRule::LongOption,
+            (_, Token::Short('W')) => PosixRule::ReservedOption,
+            (_, Token::Bundle(options)) if options.contains(&'W') => PosixRule::ReservedOption,
+            _ => return Ok(()),
+        };
+        Err(ParseError::PosixViolation {
+            value: String::from(word),
+            rule,
+        })
+    }
+
+    /// Check if options are permuted (see ParserOptions::permute). Reads environment.
+    fn permutes(&self) -> bool {
+        self.options.permute
+            && self.options.compliance != Compliance::Posix
+            && (self.env_var)("POSIXLY_CORRECT").is_none()
+    }
+
+    /// Check if the first operand ends options. Reads environment.
+    pub(crate) fn is_strict_ordering(&self) -> bool {
+        if !self.options.options_after_positionals {
+            return true;
+        }
+        match self.options.compliance {
+            Compliance::None => self.options.permute && !self.permutes(),
+            Compliance::Posix | Compliance::Gnu => !self.permutes(),
+        }
+    }
+
+    /// Checks value given as operand. Returns false for the `--` delimiting operands, which is
+    /// not stored. `delimited` tells whether the delimiter was already given.
+    pub(crate) fn check_operand(
+        &self,
+        word: &str,
+        delimited: &mut bool,
+    ) -> Result<bool, ParseError> {
+        if *delimited {
+            return Ok(true);
+        }
+        let has_delimiter = self.options.compliance != Compliance::None || self.options.permute;
+        if has_delimiter && word == "--" {
+            *delimited = true;
+            return Ok(false);
+        }
+        if self.options.compliance == Compliance::None {
+            return Ok(true);
+        }
+        match self.tokenize(word) {
+            Token::Value => Ok(true),
+            _ => Err(ParseError::PosixViolation {
+                value: String::from(word),
+                rule: PosixRule::OptionAfterOperand,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::OsString;
+
+    use crate::{
+        argument::legacy_argument::{ArgType, Argument},
+        error::{ParseError, PosixRule},
+        options::{Compliance, ErrorMode, ParserOptions},
+        ArgumentList,
+    };
+
+    #[test]
+    fn compliance_works() {
+        let new_list = |compliance| {
+            let mut args_list = ArgumentList::new();
+            args_list.set_options(
+                ParserOptions::new()
+                    .compliance(compliance)
+                    .short_bundling(true)
+                    .error_mode(ErrorMode::Aggregate),
+            );
+            args_list.append_arg(Argument::new(Some('v'), Some("verbose"), ArgType::Flag).unwrap());
+            args_list.append_arg(Argument::new_short('W', ArgType::Flag));
+            args_list.append_arg(Argument::new_short('x', ArgType::Flag));
+            args_list
+        };
+        let input = ["--verbose", "-xW", "a", "-x", "--", "-v"];
+        let violation = |value: &str, rule| ParseError::PosixViolation {
+            value: String::from(value),
+            rule,
+        };
+        let mut args_list = new_list(Compliance::Posix);
+        assert_eq!(
+            args_list.parse_args(input.iter().map(|s| String::from(*s)).collect()),
+            Err(ParseError::Multiple(vec![
+                violation("--verbose", PosixRule::LongOption),
+                violation("-xW", PosixRule::ReservedOption),
+                violation("-x", PosixRule::OptionAfterOperand),
+            ]))
+        );
+        assert_eq!(args_list.get_dangling_values(), &vec!["a", "-v"]);
+        let mut args_list = new_list(Compliance::Gnu);
+        assert_eq!(
+            args_list.parse_args(input[..3].iter().map(|s| String::from(*s)).collect()),
+            Err(ParseError::Multiple(vec![violation(
+                "-xW",
+                PosixRule::ReservedOption
+            )]))
+        );
+        let mut args_list = new_list(Compliance::None);
+        assert!(args_list
+            .parse_args(input[..3].iter().map(|s| String::from(*s)).collect())
+            .is_ok());
+    }
+
+    #[test]
+    fn permute_works() {
+        let parse = |compliance, posixly_correct| {
+            let mut args_list = ArgumentList::new();
+            args_list.set_options(ParserOptions::new().compliance(compliance).permute(true));
+            args_list.append_arg(Argument::new_short('v', ArgType::ValueList));
+            if posixly_correct {
+                args_list.env_var = |_| Some(OsString::from("1"));
+            } else {
+                args_list.env_var = |_| None;
+            }
+            let input = ["a", "-v", "1", "b", "--", "-v"];
+            let result = args_list.parse_args(input.iter().map(|s| String::from(*s)).collect());
+            (result, args_list.dangling_values)
+        };
+        assert_eq!(
+            parse(Compliance::None, false),
+            (
+                Ok(()),
+                vec![String::from("a"), String::from("b"), String::from("-v")]
+            )
+        );
+        assert_eq!(parse(Compliance::Gnu, false).1, vec!["a", "b", "-v"]);
+        let (result, dangling_values) = parse(Compliance::None, true);
+        assert!(result.is_ok());
+        assert_eq!(dangling_values, vec!["a", "-v", "1", "b", "-v"]);
+        assert!(parse(Compliance::Gnu, true).0.is_err());
+        assert!(parse(Compliance::Posix, false).0.is_err());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    argument::ArgumentIdentification,
+    cursor::InputCursor,
+    error::{Limit, ParseError},
+    token::Token,
+    ArgumentList,
+};
+
+/// Maximum number of presets expanded one inside another. Cycles between presets stop here.
+const MAX_PRESET_DEPTH: usize = 8;
+
+/// Preset which parsed input values come from.
+#[derive(Debug, Clone)]
+pub(crate) struct PresetUse {
+    /// Name of the outermost preset, which arguments of nested presets are attributed to.
+    pub(crate) name: String,
+    pub(crate) depth: usize,
+    /// Index of `--preset` in user input.
+    pub(crate) index: usize,
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Defines named list of input values which is parsed in place of `--preset name` (or
+    /// `--preset=name`) given as an option. Presets can use other presets, up to 8 levels deep.
+    /// Giving explicitly an argument which is also set by used preset fails with
+    /// ParseError::PresetConflict. Defining preset with the same name again replaces it. Presets
+    /// are not used when an argument named `--preset` is registered, which lint_spec reports.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, error::ParseError};
+    ///
+    /// let new_list = || {
+    ///     let mut args_list = ArgumentList::new();
+    ///     args_list.append_arg(Argument::new(Some('j'), Some("jobs"), ArgType::Value).unwrap());
+    ///     args_list.append_arg(Argument::new(None, Some("no-checks"), ArgType::Flag).unwrap());
+    ///     args_list.define_preset("fast", &["--jobs", "8", "--no-checks"]);
+    ///     args_list
+    /// };
+    /// let mut args_list = new_list();
+    /// args_list
+    ///     .parse_args(vec![String::from("--preset"), String::from("fast")])
+    ///     .unwrap();
+    /// assert_eq!(args_list.parsed().value_of("jobs"), Some("8"));
+    /// let mut args_list = new_list();
+    /// assert_eq!(
+    ///     args_list.parse_args(vec![
+    ///         String::from("--preset=fast"),
+    ///         String::from("-j"),
+    ///         String::from("2"),
+    ///     ]),
+    ///     Err(ParseError::PresetConflict {
+    ///         preset: String::from("fast"),
+    ///         argument: String::from("-j, --jobs"),
+    ///     })
+    /// );
+    /// ```
+    pub fn define_preset(&mut self, name: &str, values: &[&str]) {
+        let values = values.iter().map(|v| String::from(*v)).collect();
+        match self.presets.iter_mut().find(|(n, _)| n == name) {
+            Some(preset) => preset.1 = values,
+            None => self.presets.push((String::from(name), values)),
+        }
+    }
+
+    /// Check if `--preset` names a preset. Argument registered with such name takes precedence
+    /// (see lint_spec).
+    pub(crate) fn uses_presets(&self) -> bool {
+        !self.presets.is_empty() && !self.is_known_long("preset")
+    }
+
+    /// Returns values of preset named by `--preset name` (or `--preset=name`) given at index,
+    /// which are parsed in place of it. `preset` is the preset which `--preset` comes from.
+    pub(crate) fn preset_values(
+        &self,
+        value: Option<&str>,
+        input_iter: &mut InputCursor<'_>,
+        preset: Option<&PresetUse>,
+        index: usize,
+    ) -> Result<(Vec<String>, PresetUse), ParseError> {
+        let name = match value {
+            Some(value) => value,
+            None => input_iter.next().ok_or_else(|| ParseError::MissingValue {
+                argument: String::from("--preset"),
+                usage: String::from("--preset <NAME>"),
+            })?,
+        };
+        let depth = preset.map_or(1, |preset| preset.depth + 1);
+        if depth > MAX_PRESET_DEPTH {
+            return Err(ParseError::LimitExceeded {
+                limit: Limit::PresetDepth,
+                maximum: MAX_PRESET_DEPTH,
+            });
+        }
+        let (_, values) = self
+            .presets
+            .iter()
+            .find(|(n, _)| n == name)
+            .ok_or_else(|| ParseError::UnknownPreset(String::from(name)))?;
+        let preset = match preset {
+            Some(outer) => PresetUse {
+                depth,
+                ..outer.clone()
+            },
+            None => PresetUse {
+                name: String::from(name),
+                depth,
+                index,
+            },
+        };
+        Ok((values.clone(), preset))
+    }
+
+    /// Check if argument named by token was given explicitly and by a preset. `preset` is the
+    /// preset which token comes from and first_occurrence is the first occurrence of the current
+    /// parse.
+    pub(crate) fn check_preset_conflict(
+        &self,
+        token: &Token<'_>,
+        preset: Option<&PresetUse>,
+        first_occurrence: usize,
+    ) -> Result<(), ParseError> {
+        let names = match token {
+            Token::Short(name) => vec![ArgumentIdentification::Short(*name)],
+            Token::Long { name, .. } => vec![ArgumentIdentification::Long(String::from(*name))],
+            Token::Bundle(options) => options
+                .iter()
+                .map(|name| ArgumentIdentification::Short(*name))
+                .collect(),
+            Token::Value => return Ok(()),
+        };
+        let conflict = self
+            .parsed
+            .occurrences()
+            .skip(first_occurrence)
+            .filter(|o| o.preset().is_some() != preset.is_some())
+            .find(|o| names.iter().any(|name| o.identification().overlaps(name)));
+        match conflict {
+            Some(occurrence) => Err(ParseError::PresetConflict {
+                preset: preset
+                    .map(|preset| preset.name.as_str())
+                    .or(occurrence.preset())
+                    .map(String::from)
+                    .unwrap_or_default(),
+                argument: format!("{}", occurrence.identification()),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        argument::legacy_argument::{ArgType, Argument},
+        error::{Limit, ParseError},
+        lint::SpecIssue,
+        options::ErrorMode,
+        ArgumentList,
+    };
+
+    fn new_list() -> ArgumentList<'static> {
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(Some('j'), Some("jobs"), ArgType::Value).unwrap());
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.append_arg(Argument::new_long("message", ArgType::Value));
+        args_list.define_preset("fast", &["-j", "8"]);
+        args_list.define_preset("loud-fast", &["--preset", "fast", "-v"]);
+        args_list.define_preset("loop", &["--preset=loop"]);
+        args_list
+    }
+
+    #[test]
+    fn presets_work() {
+        let input = |values: &[&str]| values.iter().map(|v| String::from(*v)).collect();
+        let mut args_list = new_list();
+        args_list
+            .parse_args(input(&[
+                "a",
+                "--preset=loud-fast",
+                "--message",
+                "--preset",
+                "b",
+            ]))
+            .unwrap();
+        assert_eq!(args_list.parsed().value_of("jobs"), Some("8"));
+        assert_eq!(args_list.flag_count('v'), 1);
+        // Values of arguments are never expanded.
+        assert_eq!(args_list.parsed().value_of("message"), Some("--preset"));
+        let occurrence = args_list.parsed().occurrences().next().unwrap();
+        assert_eq!(occurrence.preset(), Some("loud-fast"));
+        assert_eq!(occurrence.index(), 1);
+        assert_eq!(args_list.get_dangling_values(), &vec!["a", "b"]);
+        assert_eq!(
+            new_list().parse_args(input(&["--preset", "loud-fast", "--jobs", "1"])),
+            Err(ParseError::PresetConflict {
+                preset: String::from("loud-fast"),
+                argument: String::from("-j, --jobs"),
+            })
+        );
+        assert_eq!(
+            new_list().parse_args(input(&["--preset", "slow"])),
+            Err(ParseError::UnknownPreset(String::from("slow")))
+        );
+        assert_eq!(
+            new_list().parse_args(input(&["--preset", "loop"])),
+            Err(ParseError::LimitExceeded {
+                limit: Limit::PresetDepth,
+                maximum: 8,
+            })
+        );
+        let mut args_list = new_list();
+        args_list.set_options(args_list.options().clone().error_mode(ErrorMode::Aggregate));
+        assert_eq!(
+            args_list.parse_args(input(&["--preset", "slow", "-v"])),
+            Err(ParseError::Multiple(vec![ParseError::UnknownPreset(
+                String::from("slow")
+            )]))
+        );
+        assert_eq!(args_list.flag_count('v'), 1);
+    }
+
+    #[test]
+    fn registered_preset_argument_is_reported() {
+        let mut args_list = new_list();
+        args_list.append_arg(Argument::new_long("preset", ArgType::Value));
+        assert_eq!(
+            args_list.lint_spec().issues(),
+            &vec![SpecIssue::ReservedName(String::from("--preset"))]
+        );
+        // Registered argument is not shadowed by presets.
+        args_list
+            .parse_args(vec![String::from("--preset"), String::from("fast")])
+            .unwrap();
+        assert_eq!(args_list.parsed().value_of("preset"), Some("fast"));
+        assert_eq!(args_list.parsed().value_of("jobs"), None);
+    }
+}
@@ -0,0 +1,320 @@
+#[cfg(feature = "tracing")]
+use std::borrow::Cow;
+use std::{fs, io, path::PathBuf};
+
+#[cfg(feature = "tracing")]
+use crate::token::Token;
+use crate::{
+    argument::{spec::ArgSpec, ArgumentIdentification},
+    cursor::InputCursor,
+    error::ParseError,
+    options::ErrorMode,
+    source, ArgumentList,
+};
+
+///
+/// Source of values for secret arguments (see ParsableValueArgument::set_secret) which were not
+/// given in command line, so tokens and passwords do not have to be typed into shell history.
+/// Arguments are identified by long name, or by short name given as a single character string
+/// when argument has no long name. Closures can be used as providers, e.g. to read a system
+/// keyring.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+/// };
+///
+/// let mut argument_token =
+///     ParsableValueArgument::new_string(ArgumentIdentification::Long(String::from("token")))
+///         .set_required(true)
+///         .set_secret(true);
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_parsable(&mut argument_token);
+/// args_list.add_value_provider(|name: &str| -> Result<Option<String>, String> {
+///     Ok(Some(format!("{}-from-keyring", name)))
+/// });
+/// args_list.parse_args(Vec::new()).unwrap();
+/// drop(args_list);
+/// assert_eq!(argument_token.first_value().map(String::as_str), Some("token-from-keyring"));
+/// ```
+pub trait ValueProvider {
+    /// Returns value of argument with given name, None when provider does not have it.
+    fn provide(&self, name: &str) -> Result<Option<String>, String>;
+}
+
+impl<F> ValueProvider for F
+where
+    F: Fn(&str) -> Result<Option<String>, String>,
+{
+    fn provide(&self, name: &str) -> Result<Option<String>, String> {
+        self(name)
+    }
+}
+
+/// Reads values from environment variables named the same way as in SourceStack::merge_env
+/// (`token` becomes `APP_TOKEN` for prefix `APP_`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvProvider {
+    prefix: String,
+}
+
+impl EnvProvider {
+    pub fn new(prefix: &str) -> EnvProvider {
+        EnvProvider {
+            prefix: String::from(prefix),
+        }
+    }
+}
+
+impl EnvProvider {
+    /// Returns value of argument read with `env_var`, like provide.
+    fn provide_with<F>(&self, name: &str, env_var: F) -> Option<String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        env_var(&source::env_variable(&self.prefix, name))
+    }
+}
+
+impl ValueProvider for EnvProvider {
+    fn provide(&self, name: &str) -> Result<Option<String>, String> {
+        Ok(self.provide_with(name, |variable| std::env::var(variable).ok()))
+    }
+}
+
+/// Reads values from files named after arguments in given directory, like secrets mounted by
+/// container runtimes (e.g. `/run/secrets/token`). Trailing newline is removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileProvider {
+    directory: PathBuf,
+}
+
+impl FileProvider {
+    pub fn new<P: Into<PathBuf>>(directory: P) -> FileProvider {
+        FileProvider {
+            directory: directory.into(),
+        }
+    }
+}
+
+impl ValueProvider for FileProvider {
+    fn provide(&self, name: &str) -> Result<Option<String>, String> {
+        let path = self.directory.join(name);
+        match fs::read_to_string(&path) {
+            Ok(value) => Ok(Some(String::from(value.trim_end_matches(['\n', '\r'])))),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(format!("Could not read {}: {}", path.display(), error)),
+        }
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Adds provider asked for values of secret arguments not given in command line. Providers
+    /// are asked in order they were added and the first value found is handled as if it was
+    /// given after the argument. Resolved values are not recorded in ParsedArgs, so they are
+    /// never displayed or serialized with it.
+    pub fn add_value_provider<P: ValueProvider + 'static>(&mut self, provider: P) {
+        self.value_providers.push(Box::new(provider));
+    }
+
+    /// Check if identification belongs to a secret argument, whose values are never traced or
+    /// displayed.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn is_secret_argument(&self, identification: &ArgumentIdentification) -> bool {
+        self.active_parsables()
+            .iter()
+            .any(|x| x.is_secret() && x.identification().overlaps(identification))
+    }
+
+    /// Input value and its token with value of a secret argument given after `=` replaced, so
+    /// they can be traced.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn redact_token<'w>(
+        &self,
+        word: &'w str,
+        token: &Token<'w>,
+    ) -> (Cow<'w, str>, Token<'w>) {
+        match *token {
+            Token::Long {
+                prefix,
+                name,
+                value: Some(_),
+            } if self.is_secret_argument(&ArgumentIdentification::Long(String::from(name))) => (
+                Cow::Owned(format!("{}{}=<redacted>", prefix, name)),
+                Token::Long {
+                    prefix,
+                    name,
+                    value: Some("<redacted>"),
+                },
+            ),
+            _ => (Cow::Borrowed(word), token.clone()),
+        }
+    }
+
+    /// Handles values of absent secret arguments found by providers. Returns arguments which got
+    /// a value.
+    pub(crate) fn resolve_secrets(
+        &mut self,
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Vec<ArgumentIdentification>, ParseError> {
+        let fail_fast = self.options.error_mode == ErrorMode::FailFast;
+        if self.value_providers.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut found = Vec::new();
+        for x in self.active_parsables() {
+            let identification = x.identification();
+            let given = self
+                .parsed
+                .occurrences()
+                .any(|o| o.identification().overlaps(identification));
+            if !x.is_secret() || given {
+                continue;
+            }
+            let name = source::key(identification);
+            for provider in &self.value_providers {
+                match provider.provide(&name) {
+                    Ok(Some(value)) => {
+                        found.push((identification.clone(), value));
+                        break;
+                    }
+                    Ok(None) => (),
+                    Err(message) => {
+                        let error = ParseError::InvalidValues {
+                            argument: format!("{}", identification),
+                            message,
+                        };
+                        if fail_fast {
+                            return Err(error);
+                        }
+                        errors.push(error);
+                        break;
+                    }
+                }
+            }
+        }
+        let mut resolved = Vec::new();
+        for x in self.active_parsables_mut() {
+            let position = match found
+                .iter()
+                .position(|(identification, _)| identification == x.identification())
+            {
+                Some(position) => position,
+                None => continue,
+            };
+            let (identification, value) = found.remove(position);
+            let input = [value];
+            match x.handle(&mut InputCursor::new(&input)) {
+                Ok(()) => resolved.push(identification),
+                Err(message) => {
+                    // Message already names the argument, like errors of values given in
+                    // command line.
+                    let error =
+                        ParseError::handler(&ArgSpec::of_parsable(&*x), true, None, message);
+                    if fail_fast {
+                        return Err(error);
+                    }
+                    errors.push(error);
+                }
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf, process};
+
+    use super::{EnvProvider, FileProvider, ValueProvider};
+    use crate::{
+        argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+        error::ParseError,
+        ArgumentList,
+    };
+
+    /// Directory unique to a test, removed with its contents when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let directory = std::env::temp_dir().join(format!("{}-{}", name, process::id()));
+            fs::create_dir_all(&directory).unwrap();
+            TempDir(directory)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn env_provider_works() {
+        let provider = EnvProvider::new("PROVIDER_TEST_");
+        let env_var = |variable: &str| {
+            if variable == "PROVIDER_TEST_DB_PASSWORD" {
+                Some(String::from("hunter2"))
+            } else {
+                None
+            }
+        };
+        assert_eq!(
+            provider.provide_with("db-password", env_var),
+            Some(String::from("hunter2"))
+        );
+        assert_eq!(provider.provide_with("api-key", env_var), None);
+    }
+
+    #[test]
+    fn value_providers_work() {
+        let temp_dir = TempDir::new("provider_test_secrets");
+        let directory = &temp_dir.0;
+        fs::write(directory.join("db-password"), "hunter2\n").unwrap();
+        fs::write(directory.join("api-key"), "key\n").unwrap();
+        assert_eq!(FileProvider::new(directory).provide("missing"), Ok(None));
+        assert_eq!(
+            FileProvider::new(directory).provide("api-key"),
+            Ok(Some(String::from("key")))
+        );
+
+        let mut argument_password = ParsableValueArgument::new_string(
+            ArgumentIdentification::Long(String::from("db-password")),
+        )
+        .set_required(true)
+        .set_secret(true);
+        let mut argument_key = ParsableValueArgument::new_string(ArgumentIdentification::Long(
+            String::from("api-key"),
+        ))
+        .set_secret(true);
+        let mut argument_port =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('p')).set_secret(true);
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_password);
+        args_list.register_parsable(&mut argument_key);
+        args_list.register_parsable(&mut argument_port);
+        args_list.add_value_provider(EnvProvider::new("PROVIDER_TEST_"));
+        args_list.add_value_provider(FileProvider::new(directory));
+        args_list.add_value_provider(|name: &str| -> Result<Option<String>, String> {
+            Ok(Some(String::from(name)))
+        });
+        assert_eq!(
+            args_list.parse_args(vec![String::from("--api-key"), String::from("given")]),
+            Err(ParseError::InvalidValue {
+                argument: String::from("-p"),
+                message: String::from("error in -p: Input is not a number"),
+                usage: String::from("-p <INT>"),
+                value: None,
+                suggestion: None,
+            })
+        );
+        assert!(args_list.parsed().values_of("db-password").is_empty());
+        drop(args_list);
+        assert_eq!(argument_password.values(), &vec![String::from("hunter2")]);
+        assert_eq!(argument_key.values(), &vec![String::from("given")]);
+        assert!(argument_port.values().is_empty());
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{
+    argument::{parsable_argument::HandleableArgument, ArgumentIdentification},
+    cursor::InputCursor,
+    error::ParseError,
+    ArgumentList,
+};
+
+/// Child list of arguments which can be used only when guard argument is present in input.
+pub(crate) struct Scope<'a> {
+    pub(crate) guard: ArgumentIdentification,
+    pub(crate) list: ArgumentList<'a>,
+    pub(crate) active: bool,
+}
+
+impl<'a> Scope<'a> {
+    /// Marks scopes (and scopes nested in them) whose guards are among given arguments as active.
+    fn activate(scopes: &mut [Scope<'a>], given: &[(ArgumentIdentification, String)]) {
+        for scope in scopes {
+            scope.active = given.iter().any(|(i, _)| i.overlaps(&scope.guard));
+            Scope::activate(&mut scope.list.scopes, given);
+        }
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Attaches list of arguments which can be used only when guard argument (usually a flag
+    /// registered in this list) is present in input, e.g. `--advanced` unlocking `--threads`.
+    /// Using them without the guard fails with ParseError::LockedArgument. Required arguments of
+    /// the child list are required only when the guard is present.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::{
+    ///     ArgumentList,
+    ///     argument::{legacy_argument::*, parsable_argument::ParsableValueArgument, ArgumentIdentification},
+    ///     error::ParseError,
+    /// };
+    ///
+    /// let mut argument_threads =
+    ///     ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("threads")));
+    /// let mut advanced = ArgumentList::new();
+    /// advanced.register_parsable(&mut argument_threads);
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(None, Some("advanced"), ArgType::Flag).unwrap());
+    /// args_list.add_scope("advanced", advanced);
+    /// assert_eq!(
+    ///     args_list.parse_args(vec![String::from("--threads"), String::from("4")]),
+    ///     Err(ParseError::LockedArgument {
+    ///         argument: String::from("--threads"),
+    ///         guard: String::from("--advanced"),
+    ///     })
+    /// );
+    /// let mut args_list = ArgumentList::new();
+    /// args_list.append_arg(Argument::new(None, Some("advanced"), ArgType::Flag).unwrap());
+    /// let mut advanced = ArgumentList::new();
+    /// advanced.register_parsable(&mut argument_threads);
+    /// args_list.add_scope("advanced", advanced);
+    /// let input = vec!["--threads", "4", "--advanced"];
+    /// args_list
+    ///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+    ///     .unwrap();
+    /// drop(args_list);
+    /// assert_eq!(argument_threads.first_value(), Some(&4));
+    /// ```
+    pub fn add_scope<I: Into<ArgumentIdentification>>(&mut self, guard: I, list: ArgumentList<'a>) {
+        self.scopes.push(Scope {
+            guard: guard.into(),
+            list,
+            active: false,
+        });
+    }
+
+    /// Returns child list attached with given guard. Can be used to read its legacy arguments.
+    pub fn scope<I: Into<ArgumentIdentification>>(&self, guard: I) -> Option<&ArgumentList<'a>> {
+        let guard = guard.into();
+        self.scopes
+            .iter()
+            .find(|s| s.guard.overlaps(&guard))
+            .map(|s| &s.list)
+    }
+
+    /// Marks scopes whose guards were given since first occurrence of the current parse as active.
+    /// Fails when an argument of a scope was given without its guard.
+    pub(crate) fn activate_scopes(&mut self, first_occurrence: usize) -> Result<(), ParseError> {
+        if self.scopes.is_empty() {
+            return Ok(());
+        }
+        let given: Vec<(ArgumentIdentification, String)> = self
+            .parsed
+            .occurrences()
+            .skip(first_occurrence)
+            .map(|o| (o.identification().clone(), String::from(o.form())))
+            .collect();
+        Scope::activate(&mut self.scopes, &given);
+        for (identification, form) in &given {
+            if let Some(guard) = self.locked_guard(identification) {
+                return Err(ParseError::LockedArgument {
+                    argument: form.clone(),
+                    guard: format!("{}", guard),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Guard of inactive scope which argument with given identification belongs to.
+    fn locked_guard(
+        &self,
+        identification: &ArgumentIdentification,
+    ) -> Option<&ArgumentIdentification> {
+        // Arguments of this list are found before arguments of scopes.
+        let is_own = identification.short().is_some_and(|c| {
+            self.search_by_short_name(c).is_some() || self.parsables().any(|x| x.is_by_short(c))
+        }) || identification.long().is_some_and(|name| {
+            self.search_by_long_name(name).is_some() || self.parsables().any(|x| x.is_by_long(name))
+        });
+        if is_own {
+            return None;
+        }
+        let scope = self.scopes.iter().find(|s| {
+            identification
+                .short()
+                .is_some_and(|c| s.list.is_known_short(c))
+                || identification
+                    .long()
+                    .is_some_and(|name| s.list.is_known_long(name))
+        })?;
+        if scope.active {
+            scope.list.locked_guard(identification)
+        } else {
+            Some(&scope.guard)
+        }
+    }
+
+    pub(crate) fn is_known_short(&self, name: char) -> bool {
+        self.search_by_short_name(name).is_some()
+            || self.parsables().any(|x| x.is_by_short(name))
+            || self.scopes.iter().any(|s| s.list.is_known_short(name))
+    }
+
+    pub(crate) fn is_known_long(&self, name: &str) -> bool {
+        self.search_by_long_name(name).is_some()
+            || self.parsables().any(|x| x.is_by_long(name))
+            || self.scopes.iter().any(|s| s.list.is_known_long(name))
+    }
+
+    /// Handles argument identified by short name defined in one of attached scopes. Scopes are
+    /// activated after the whole input was parsed, so guards can be given after the argument.
+    pub(crate) fn handle_scoped_short_name(
+        &mut self,
+        word: &str,
+        name: char,
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<ArgumentIdentification, ParseError> {
+        for scope in &mut self.scopes {
+            if scope.list.is_known_short(name) {
+                return scope.list.handle_short_name(word, name, input_iter);
+            }
+        }
+        Err(ParseError::UnknownArgument(String::from(word)))
+    }
+
+    /// Handles argument identified by long name defined in one of attached scopes. Scopes are
+    /// activated after the whole input was parsed, so guards can be given after the argument.
+    pub(crate) fn handle_scoped_long_name(
+        &mut self,
+        word: &str,
+        name: &str,
+        input_iter: &mut InputCursor<'_>,
+    ) -> Result<ArgumentIdentification, ParseError> {
+        for scope in &mut self.scopes {
+            if scope.list.is_known_long(name) {
+                return scope.list.handle_long_name(word, name, input_iter);
+            }
+        }
+        Err(ParseError::UnknownArgument(String::from(word)))
+    }
+
+    /// Parsable arguments of this list and of all active scopes.
+    pub(crate) fn active_parsables(&self) -> Vec<&dyn HandleableArgument<'a>> {
+        let mut parsables: Vec<_> = self.parsables().collect();
+        for scope in self.scopes.iter().filter(|s| s.active) {
+            parsables.extend(scope.list.active_parsables());
+        }
+        parsables
+    }
+
+    /// Mutable version of active_parsables.
+    pub(crate) fn active_parsables_mut(&mut self) -> Vec<&mut dyn HandleableArgument<'a>> {
+        let mut parsables: Vec<&mut dyn HandleableArgument<'a>> = self
+            .parsable_arguments
+            .iter_mut()
+            .map(|x| &mut **x as &mut dyn HandleableArgument<'a>)
+            .collect();
+        parsables.extend(
+            self.boxed_arguments
+                .iter_mut()
+                .map(|x| &mut **x as &mut dyn HandleableArgument<'a>),
+        );
+        for scope in self.scopes.iter_mut().filter(|s| s.active) {
+            parsables.extend(scope.list.active_parsables_mut());
+        }
+        parsables
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        argument::{
+            legacy_argument::{ArgType, Argument},
+            parsable_argument::ParsableValueArgument,
+            ArgumentIdentification,
+        },
+        error::ParseError,
+        options::ParserOptions,
+        ArgumentList,
+    };
+
+    #[test]
+    fn scoped_required_argument_works() {
+        let mut argument_size = ParsableValueArgument::new_integer(ArgumentIdentification::Long(
+            String::from("chunk-size"),
+        ))
+        .set_required(true)
+        .set_description("Size of a chunk");
+        let mut advanced = ArgumentList::new();
+        advanced.register_parsable(&mut argument_size);
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('a', ArgType::Flag));
+        args_list.add_scope('a', advanced);
+        assert_eq!(
+            args_list.help_message(),
+            "Options:\n  -a\n  --chunk-size <INT>  Size of a chunk (requires -a)\n"
+        );
+        assert!(args_list.parse_args(Vec::new()).is_ok());
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new_short('a', ArgType::Flag));
+        let mut advanced = ArgumentList::new();
+        advanced.register_parsable(&mut argument_size);
+        args_list.add_scope('a', advanced);
+        assert_eq!(
+            args_list.parse_args(vec![String::from("-a")]),
+            Err(ParseError::MissingArgument(String::from("--chunk-size")))
+        );
+    }
+
+    #[test]
+    fn scopes_are_unlocked_by_options_only() {
+        let mut argument_threads =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('t'));
+        let mut advanced = ArgumentList::new();
+        advanced.register_parsable(&mut argument_threads);
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true).permute(true));
+        args_list.append_arg(Argument::new_short('a', ArgType::Flag));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.append_arg(Argument::new_long("name", ArgType::Value));
+        args_list.add_scope('a', advanced);
+        let locked = Err(ParseError::LockedArgument {
+            argument: String::from("-t"),
+            guard: String::from("-a"),
+        });
+        let input = |values: &[&str]| values.iter().map(|v| String::from(*v)).collect();
+        // Guard given as a value or after `--` does not unlock the scope.
+        assert_eq!(
+            args_list.parse_args(input(&["--name", "-a", "-t", "1"])),
+            locked
+        );
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true).permute(true));
+        args_list.append_arg(Argument::new_short('a', ArgType::Flag));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        let mut advanced = ArgumentList::new();
+        advanced.register_parsable(&mut argument_threads);
+        args_list.add_scope('a', advanced);
+        assert_eq!(
+            args_list.parse_args(input(&["-t", "1", "--", "-a"])),
+            locked
+        );
+        let mut argument_threads =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Short('t'));
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true));
+        args_list.append_arg(Argument::new_short('a', ArgType::Flag));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        let mut advanced = ArgumentList::new();
+        advanced.register_parsable(&mut argument_threads);
+        args_list.add_scope('a', advanced);
+        args_list.parse_args(input(&["-t", "2", "-va"])).unwrap();
+        drop(args_list);
+        assert_eq!(argument_threads.first_value(), Some(&2));
+    }
+}
@@ -0,0 +1,211 @@
+use std::{error::Error, fmt};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    argument::legacy_argument::{ArgType, Argument},
+    error::ParseError,
+    parsed::{shell_word, ParsedArgs},
+    source, ArgumentList,
+};
+
+/// Error returned while parsing on behalf of a shell script.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    /// Definition of argument in spec could not be understood. Contains the definition.
+    InvalidSpec(String),
+    /// Input was rejected by the parser.
+    Parse(ParseError),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::InvalidSpec(definition) => {
+                write!(f, "Invalid argument definition '{}'.", definition)
+            }
+            ScriptError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for ScriptError {}
+
+/// Format of parse results printed for the calling script.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptOutput {
+    /// Variable assignments made by ParsedArgs::to_env_exports with given prefix, followed by
+    /// `set --` replacing script arguments with positional values.
+    Env(String),
+    /// JSON object with `arguments` mapping names to `true`, a value or a list of values and
+    /// `positionals` listing positional values.
+    Json,
+}
+
+/// Parses single definition, e.g. `o|output=`.
+fn parse_definition(definition: &str) -> Result<Argument, ScriptError> {
+    let invalid = || ScriptError::InvalidSpec(String::from(definition));
+    let (names, arg_type) = if let Some(names) = definition.strip_suffix('=') {
+        (names, ArgType::Value)
+    } else if let Some(names) = definition.strip_suffix('*') {
+        (names, ArgType::ValueList)
+    } else {
+        (definition, ArgType::Flag)
+    };
+    let is_name = |name: &str| {
+        !name.is_empty()
+            && !name.starts_with('-')
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+    };
+    match names.split_once('|') {
+        Some((short, long)) if short.chars().count() == 1 && is_name(short) && is_name(long) => {
+            Argument::new(short.chars().next(), Some(long), arg_type).map_err(|_| invalid())
+        }
+        Some(_) => Err(invalid()),
+        None if !is_name(names) => Err(invalid()),
+        None if names.chars().count() == 1 => Ok(Argument::new_short(
+            names.chars().next().unwrap_or_default(),
+            arg_type,
+        )),
+        None => Ok(Argument::new_long(names, arg_type)),
+    }
+}
+
+///
+/// Builds argument list from compact spec. Definitions are separated by whitespace and consist of
+/// names followed by optional type marker. Names are a short name, a long name or both separated
+/// by `|`. Arguments without marker are flags, `=` marks arguments taking a single value and `*`
+/// arguments taking a list of values.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::script::parse_spec;
+///
+/// let args_list = parse_spec("v|verbose o|output= I* dry-run").unwrap();
+/// assert_eq!(args_list.iter_specs().count(), 4);
+/// assert!(parse_spec("x|").is_err());
+/// ```
+pub fn parse_spec(spec: &str) -> Result<ArgumentList<'static>, ScriptError> {
+    let mut args_list = ArgumentList::new();
+    for definition in spec.split_whitespace() {
+        args_list.append_arg(parse_definition(definition)?);
+    }
+    Ok(args_list)
+}
+
+/// Formats parse results as JSON object described in ScriptOutput::Json.
+pub fn to_json(parsed: &ParsedArgs) -> String {
+    let mut arguments = Map::new();
+    for occurrence in parsed.occurrences() {
+        let name = source::key(occurrence.identification());
+        if arguments.contains_key(&name) {
+            continue;
+        }
+        let values = parsed.values_of(&name);
+        let value = match values.len() {
+            0 => Value::Bool(true),
+            1 => Value::from(values[0]),
+            _ => Value::from(values),
+        };
+        arguments.insert(name, value);
+    }
+    let mut result = Map::new();
+    result.insert(String::from("arguments"), Value::Object(arguments));
+    result.insert(
+        String::from("positionals"),
+        Value::from(parsed.positionals()),
+    );
+    Value::Object(result).to_string()
+}
+
+///
+/// Entry point of a helper program parsing arguments on behalf of shell scripts. Builds arguments
+/// from spec (see parse_spec), parses input and formats results for the script.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::script::{run, ScriptOutput};
+///
+/// // Called by a script as `eval "$(tap-cli 'v|verbose o|output=' "$@")"`.
+/// let input = vec!["-v", "--output", "out dir", "file"];
+/// let input: Vec<String> = input.iter().map(|s| String::from(*s)).collect();
+/// assert_eq!(
+///     run("v|verbose o|output=", input.clone(), &ScriptOutput::Env(String::from("APP_"))).unwrap(),
+///     "APP_VERBOSE=1\nAPP_OUTPUT='out dir'\nset -- file\n"
+/// );
+/// assert_eq!(
+///     run("v|verbose o|output=", input, &ScriptOutput::Json).unwrap(),
+///     r#"{"arguments":{"output":"out dir","verbose":true},"positionals":["file"]}"#
+/// );
+/// ```
+pub fn run(spec: &str, input: Vec<String>, output: &ScriptOutput) -> Result<String, ScriptError> {
+    let mut args_list = parse_spec(spec)?;
+    args_list.parse_args(input).map_err(ScriptError::Parse)?;
+    let parsed = args_list.parsed();
+    match output {
+        ScriptOutput::Env(prefix) => {
+            let mut exports = parsed.to_env_exports(prefix);
+            exports.push_str("set --");
+            for value in parsed.positionals() {
+                exports.push(' ');
+                exports.push_str(&shell_word(value));
+            }
+            exports.push('\n');
+            Ok(exports)
+        }
+        ScriptOutput::Json => Ok(to_json(parsed)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_spec, run, ScriptError, ScriptOutput};
+    use crate::error::ParseError;
+
+    #[test]
+    fn parse_spec_works() {
+        let args_list = parse_spec("  v|verbose\to|output=  I*\nlevel ").unwrap();
+        let specs: Vec<String> = args_list.iter_specs().map(|s| s.synopsis()).collect();
+        assert_eq!(
+            specs,
+            vec![
+                "-v, --verbose",
+                "-o, --output <VALUE>",
+                "-I <VALUE>",
+                "--level"
+            ]
+        );
+        for spec in ["ab|c", "|x", "x|", "-v", "v|--verbose", "=", "a|b|c"] {
+            assert!(parse_spec(spec).is_err(), "{}", spec);
+        }
+        assert_eq!(
+            parse_spec("x| y").err().map(|error| format!("{}", error)),
+            Some(String::from("Invalid argument definition 'x|'."))
+        );
+    }
+
+    #[test]
+    fn run_works() {
+        let input = |values: &[&str]| values.iter().map(|v| String::from(*v)).collect();
+        assert_eq!(
+            run(
+                "I*",
+                input(&["-I", "a", "it's", "-I", "b"]),
+                &ScriptOutput::Env(String::new())
+            ),
+            Ok(String::from("I='a\nb'\nset -- 'it'\\''s'\n"))
+        );
+        assert_eq!(
+            run("I*", input(&[]), &ScriptOutput::Json),
+            Ok(String::from(r#"{"arguments":{},"positionals":[]}"#))
+        );
+        assert_eq!(
+            run("v", input(&["-x"]), &ScriptOutput::Json),
+            Err(ScriptError::Parse(ParseError::UnknownArgument(
+                String::from("-x")
+            )))
+        );
+    }
+}
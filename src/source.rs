@@ -0,0 +1,361 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    argument::{strip_name_prefix, ArgumentIdentification},
+    parsed::ParsedArgs,
+    ArgumentList,
+};
+
+/// Origin of an argument value. Later sources take precedence over earlier ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Source {
+    Default,
+    ConfigFile,
+    Env,
+    Cli,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::ConfigFile => write!(f, "config file"),
+            Source::Env => write!(f, "environment"),
+            Source::Cli => write!(f, "command line"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Source {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Serializes effective value of every argument together with source it came from.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SourceStack {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeStruct};
+
+        struct Effective<'s>(&'s Source, &'s Vec<String>);
+
+        impl<'s> serde::Serialize for Effective<'s> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut value = serializer.serialize_struct("Effective", 2)?;
+                value.serialize_field("values", self.1)?;
+                value.serialize_field("source", self.0)?;
+                value.end()
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(self.candidates.len()))?;
+        for name in self.candidates.keys() {
+            if let Some((source, values)) = self.winner(name) {
+                map.serialize_entry(name, &Effective(source, values))?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// Argument whose effective value differs from its default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDiff {
+    pub name: String,
+    /// Default values, empty when argument has no default.
+    pub default: Vec<String>,
+    pub value: Vec<String>,
+    pub source: Source,
+}
+
+impl ConfigDiff {
+    /// Formats difference as `name: default -> value (source)`. With `color` enabled default is
+    /// printed in red and effective value in green using ANSI escape codes.
+    pub fn render(&self, color: bool) -> String {
+        let default = if self.default.is_empty() {
+            String::from("<none>")
+        } else {
+            self.default.join(", ")
+        };
+        let value = self.value.join(", ");
+        if color {
+            format!(
+                "{}: \x1b[31m{}\x1b[0m -> \x1b[32m{}\x1b[0m ({})",
+                self.name, default, value, self.source
+            )
+        } else {
+            format!("{}: {} -> {} ({})", self.name, default, value, self.source)
+        }
+    }
+}
+
+/// Name under which argument is stored, the long name when present.
+pub(crate) fn key(identification: &ArgumentIdentification) -> String {
+    match (identification.long(), identification.short()) {
+        (Some(long), _) => String::from(long),
+        (None, Some(short)) => short.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Name of environment variable holding value of argument, uppercased with `-` and `.` replaced by
+/// `_` and prefixed by `prefix`.
+pub(crate) fn env_variable(prefix: &str, name: &str) -> String {
+    format!("{}{}", prefix, name.to_uppercase().replace(['-', '.'], "_"))
+}
+
+///
+/// Merges values of arguments coming from multiple sources. Every source is a layer of candidate
+/// values and the value from the source with highest precedence wins
+/// (defaults < config file < environment < CLI), regardless of order in which layers were merged.
+/// Arguments are identified by long name, or by short name given as a single character string
+/// when argument has no long name.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::{parsable_argument::ParsableValueArgument, ArgumentIdentification},
+///     source::{Source, SourceStack},
+/// };
+///
+/// let mut argument_port =
+///     ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("port")))
+///         .set_default(80);
+/// let mut args_list = ArgumentList::new();
+/// args_list.register_parsable(&mut argument_port);
+/// args_list.parse_args(Vec::new()).unwrap();
+///
+/// let mut stack = SourceStack::new();
+/// stack.merge_defaults(&args_list);
+/// stack.merge_from(Source::ConfigFile, vec![("port", "8080")]);
+/// stack.merge_parsed(args_list.parsed());
+/// assert_eq!(stack.value("port"), Some("8080"));
+/// assert_eq!(stack.value_source("port"), Some(Source::ConfigFile));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceStack {
+    candidates: BTreeMap<String, BTreeMap<Source, Vec<String>>>,
+}
+
+impl SourceStack {
+    pub fn new() -> SourceStack {
+        SourceStack::default()
+    }
+
+    /// Adds candidate values from given source. Values given for the same argument by the same
+    /// source replace ones merged before.
+    pub fn merge_from<I, K, V>(&mut self, source: Source, values: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut layer: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (name, value) in values {
+            layer.entry(name.into()).or_default().push(value.into());
+        }
+        for (name, values) in layer {
+            self.candidates
+                .entry(name)
+                .or_default()
+                .insert(source, values);
+        }
+    }
+
+    /// Adds default values of all registered arguments which have one.
+    pub fn merge_defaults(&mut self, args_list: &ArgumentList<'_>) {
+        let defaults: Vec<(String, String)> = args_list
+            .iter_specs()
+            .filter_map(|spec| {
+                spec.default_value()
+                    .map(|default| (key(spec.identification()), String::from(default)))
+            })
+            .collect();
+        self.merge_from(Source::Default, defaults);
+    }
+
+    /// Adds values of environment variables named after arguments, uppercased with `-` and `.`
+    /// replaced by `_` and prefixed by `prefix` (`port` becomes `APP_PORT` for prefix `APP_`).
+    pub fn merge_env(&mut self, prefix: &str, names: &[&str]) {
+        self.merge_env_with(prefix, names, |variable| std::env::var(variable).ok());
+    }
+
+    /// Adds values of environment variables read with `env_var`, like merge_env.
+    fn merge_env_with<F>(&mut self, prefix: &str, names: &[&str], env_var: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut values = Vec::new();
+        for name in names {
+            if let Some(value) = env_var(&env_variable(prefix, name)) {
+                values.push((String::from(*name), value));
+            }
+        }
+        self.merge_from(Source::Env, values);
+    }
+
+    /// Adds values of arguments given in command line. Arguments given without values are
+    /// recorded with empty value list.
+    pub fn merge_parsed(&mut self, parsed: &ParsedArgs) {
+        for occurrence in parsed.occurrences() {
+            let name = key(occurrence.identification());
+            let values = parsed
+                .values_of(&name)
+                .into_iter()
+                .map(String::from)
+                .collect();
+            self.candidates
+                .entry(name)
+                .or_default()
+                .insert(Source::Cli, values);
+        }
+    }
+
+    fn winner(&self, name: &str) -> Option<(&Source, &Vec<String>)> {
+        self.candidates
+            .get(strip_name_prefix(name))
+            .and_then(|sources| sources.iter().next_back())
+    }
+
+    /// Returns all values of argument from the source with highest precedence.
+    pub fn values(&self, name: &str) -> Option<&Vec<String>> {
+        self.winner(name).map(|(_, values)| values)
+    }
+
+    /// Returns the last value of argument from the source with highest precedence.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values(name)
+            .and_then(|values| values.last())
+            .map(String::as_str)
+    }
+
+    /// Returns source from which effective value of argument comes.
+    pub fn value_source(&self, name: &str) -> Option<Source> {
+        self.winner(name).map(|(source, _)| *source)
+    }
+
+    /// Lists every argument whose effective value differs from its default, together with source
+    /// of that value. Arguments are listed in alphabetical order.
+    ///
+    /// # Examples
+    /// ```
+    /// use trivial_argument_parser::source::{Source, SourceStack};
+    ///
+    /// let mut stack = SourceStack::new();
+    /// stack.merge_from(Source::Default, vec![("port", "80"), ("host", "localhost")]);
+    /// stack.merge_from(Source::Env, vec![("port", "8080"), ("host", "localhost")]);
+    /// let diff = stack.diff_defaults();
+    /// assert_eq!(diff.len(), 1);
+    /// assert_eq!(diff[0].render(false), "port: 80 -> 8080 (environment)");
+    /// ```
+    pub fn diff_defaults(&self) -> Vec<ConfigDiff> {
+        let mut diffs = Vec::new();
+        for (name, sources) in &self.candidates {
+            let default = sources.get(&Source::Default).cloned().unwrap_or_default();
+            if let Some((source, value)) = sources.iter().next_back() {
+                if *source != Source::Default && *value != default {
+                    diffs.push(ConfigDiff {
+                        name: name.clone(),
+                        default,
+                        value: value.clone(),
+                        source: *source,
+                    });
+                }
+            }
+        }
+        diffs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConfigDiff, Source, SourceStack};
+    use crate::{
+        argument::{
+            legacy_argument::*, parsable_argument::ParsableValueArgument, ArgumentIdentification,
+        },
+        ArgumentList,
+    };
+
+    #[test]
+    fn precedence_works() {
+        let mut stack = SourceStack::new();
+        stack.merge_from(Source::Env, vec![("port", "3000"), ("host", "env-host")]);
+        stack.merge_from(Source::Default, vec![("port", "80"), ("user", "root")]);
+        stack.merge_from(Source::ConfigFile, vec![("host", "file-host")]);
+        let mut args_list = ArgumentList::new();
+        args_list.append_arg(Argument::new(None, Some("port"), ArgType::Value).unwrap());
+        args_list
+            .parse_args(vec![String::from("--port"), String::from("8080")])
+            .unwrap();
+        stack.merge_parsed(args_list.parsed());
+        assert_eq!(stack.value("port"), Some("8080"));
+        assert_eq!(stack.value_source("port"), Some(Source::Cli));
+        assert_eq!(stack.value("host"), Some("env-host"));
+        assert_eq!(stack.value_source("host"), Some(Source::Env));
+        assert_eq!(stack.value_source("user"), Some(Source::Default));
+        assert_eq!(stack.value_source("missing"), None);
+    }
+
+    #[test]
+    fn merge_env_works() {
+        let mut stack = SourceStack::new();
+        stack.merge_env_with("SOURCE_TEST_", &["db-host", "db-port"], |variable| {
+            if variable == "SOURCE_TEST_DB_HOST" {
+                Some(String::from("db.local"))
+            } else {
+                None
+            }
+        });
+        assert_eq!(stack.value("db-host"), Some("db.local"));
+        assert_eq!(stack.value_source("db-host"), Some(Source::Env));
+        assert_eq!(stack.value("db-port"), None);
+    }
+
+    #[test]
+    fn parsed_diff_defaults_works() {
+        let mut argument_port =
+            ParsableValueArgument::new_integer(ArgumentIdentification::Long(String::from("port")))
+                .set_default(80);
+        let mut argument_retries = ParsableValueArgument::new_integer(
+            ArgumentIdentification::Long(String::from("retries")),
+        )
+        .set_default(3);
+        let mut args_list = ArgumentList::new();
+        args_list.register_parsable(&mut argument_port);
+        args_list.register_parsable(&mut argument_retries);
+        let input = ["--port", "8080", "--retries", "3"];
+        args_list
+            .parse_args(input.iter().map(|s| String::from(*s)).collect())
+            .unwrap();
+        let diff = args_list.parsed().diff_defaults();
+        assert_eq!(
+            diff,
+            vec![ConfigDiff {
+                name: String::from("port"),
+                default: vec![String::from("80")],
+                value: vec![String::from("8080")],
+                source: Source::Cli,
+            }]
+        );
+        assert_eq!(
+            diff[0].render(true),
+            "port: \x1b[31m80\x1b[0m -> \x1b[32m8080\x1b[0m (command line)"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn source_stack_serializes_provenance() {
+        let mut stack = SourceStack::new();
+        stack.merge_from(Source::Default, vec![("port", "80")]);
+        stack.merge_from(Source::Env, vec![("port", "8080")]);
+        assert_eq!(
+            serde_json::to_string(&stack).unwrap(),
+            r#"{"port":{"values":["8080"],"source":"environment"}}"#
+        );
+    }
+}
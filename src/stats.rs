@@ -0,0 +1,91 @@
+use crate::{error::ParseError, parsed::ParsedItem};
+
+///
+/// Counters describing work done by the last call to parse_args. Collected only when enabled with
+/// ParserOptions::collect_stats, so parsing does not pay for them by default.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*, options::ParserOptions};
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.set_options(ParserOptions::new().collect_stats(true));
+/// args_list.append_arg(Argument::new(Some('p'), None, ArgType::Value).unwrap());
+/// args_list
+///     .parse_args(vec![String::from("-p"), String::from("/file"), String::from("file")])
+///     .unwrap();
+/// let stats = args_list.stats().unwrap();
+/// assert_eq!(stats.tokens, 3);
+/// assert_eq!(stats.lookups, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ParseStats {
+    /// Number of input values processed, including values consumed by arguments.
+    pub tokens: usize,
+    /// Number of argument names looked up. Every option of a bundle counts separately.
+    pub lookups: usize,
+    /// Estimated number of heap allocations made while recording results. Allocations done by
+    /// argument handlers are not included.
+    pub allocations: usize,
+}
+
+///
+/// Anonymized summary of a single parse passed to ParserConfig::on_parse_complete. Contains
+/// names of used arguments and kinds of errors, but no values given by the user.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList, argument::legacy_argument::*, config::ParserConfig, stats::ParseReport,
+/// };
+///
+/// fn record(report: &ParseReport) {
+///     assert_eq!(report.arguments, vec!["-p, --path"]);
+///     assert_eq!(report.positionals, 1);
+///     assert!(report.errors.is_empty());
+/// }
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.set_config(ParserConfig {
+///     on_parse_complete: Some(record),
+///     ..ParserConfig::default()
+/// });
+/// args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::Value).unwrap());
+/// let input = vec!["-p", "/secret/file", "file"];
+/// args_list
+///     .parse_args(input.iter().map(|s| String::from(*s)).collect())
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseReport {
+    /// Arguments given by the user in input order, formatted like `-p, --path`.
+    pub arguments: Vec<String>,
+    /// Number of values not attached to any argument.
+    pub positionals: usize,
+    /// Kinds of errors (see ParseError::kind), empty when parsing succeeded.
+    pub errors: Vec<&'static str>,
+    /// Warnings about accepted values, e.g. saturated numbers (see
+    /// ArgumentList::value_warnings). They do not contain values given by the user.
+    pub warnings: Vec<String>,
+}
+
+impl ParseReport {
+    pub(crate) fn new<'i, I>(items: I, error: Option<&ParseError>) -> ParseReport
+    where
+        I: Iterator<Item = &'i ParsedItem>,
+    {
+        let mut report = ParseReport::default();
+        for item in items {
+            match item {
+                ParsedItem::Argument(occurrence) => report
+                    .arguments
+                    .push(format!("{}", occurrence.identification())),
+                ParsedItem::Positional { .. } | ParsedItem::Stdin { .. } => report.positionals += 1,
+            }
+        }
+        if let Some(error) = error {
+            report.errors = error.errors().iter().map(|e| e.kind()).collect();
+        }
+        report
+    }
+}
@@ -0,0 +1,68 @@
+/// Number of single character insertions, deletions and substitutions needed to turn `a` into `b`.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Finds candidate closest to input. Candidates differing in more than a third of characters
+/// (but at least one) are not considered similar.
+pub(crate) fn closest<'c, I>(input: &str, candidates: I) -> Option<&'c str>
+where
+    I: IntoIterator<Item = &'c str>,
+{
+    let limit = (input.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(input, c), c))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+/// Message describing value not being one of allowed choices, with suggestion when possible.
+pub(crate) fn invalid_choice<'c, I>(value: &str, choices: I) -> String
+where
+    I: IntoIterator<Item = &'c str> + Clone,
+{
+    match closest(value, choices.clone()) {
+        Some(suggestion) => format!("'{}' is not valid; did you mean '{}'?", value, suggestion),
+        None => format!(
+            "'{}' is not valid; possible values: {}",
+            value,
+            choices.into_iter().collect::<Vec<&str>>().join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{closest, edit_distance, invalid_choice};
+
+    #[test]
+    fn edit_distance_works() {
+        assert_eq!(edit_distance("json", "json"), 0);
+        assert_eq!(edit_distance("jsn", "json"), 1);
+        assert_eq!(edit_distance("yaml", "xml"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn closest_works() {
+        let choices = ["text", "json", "yaml"];
+        assert_eq!(closest("jsn", choices.iter().copied()), Some("json"));
+        assert_eq!(closest("xml", choices.iter().copied()), None);
+        assert_eq!(
+            invalid_choice("xml", choices.iter().copied()),
+            "'xml' is not valid; possible values: text, json, yaml"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::options::{ParserOptions, Prefix};
+
+/// Meaning of a single input value, decided by Tokenizer before arguments are looked up.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token<'w> {
+    /// Short option (`-v`).
+    Short(char),
+    /// Long option (`--name`). `prefix` is the text preceding the name and `value` is the text
+    /// given after `=`.
+    Long {
+        prefix: &'w str,
+        name: &'w str,
+        value: Option<&'w str>,
+    },
+    /// Group of short options (`-vdo`).
+    Bundle(Vec<char>),
+    /// Value which does not introduce any argument.
+    Value,
+}
+
+///
+/// Decides how input values are classified. Can be replaced with ArgumentList::set_tokenizer to
+/// support different prefixes or rules without reimplementing parse_args.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{
+///     ArgumentList,
+///     argument::legacy_argument::*,
+///     options::ParserOptions,
+///     token::{DefaultTokenizer, Token, Tokenizer},
+/// };
+///
+/// /// Accepts `+v` as a short option in addition to default syntax.
+/// struct PlusTokenizer;
+///
+/// impl Tokenizer for PlusTokenizer {
+///     fn tokenize<'w>(&self, word: &'w str, options: &ParserOptions) -> Token<'w> {
+///         let mut chars = word.chars();
+///         match (chars.next(), chars.next(), chars.next()) {
+///             (Some('+'), Some(c), None) => Token::Short(c),
+///             _ => DefaultTokenizer.tokenize(word, options),
+///         }
+///     }
+/// }
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+/// args_list.set_tokenizer(PlusTokenizer);
+/// args_list.parse_args(vec![String::from("+v")]).unwrap();
+/// assert!(args_list.search_by_short_name('v').unwrap().get_flag().unwrap());
+/// ```
+pub trait Tokenizer {
+    /// Classifies a single input value.
+    fn tokenize<'w>(&self, word: &'w str, options: &ParserOptions) -> Token<'w>;
+}
+
+/// Tokenizer recognizing `-v`, `--name`, `--name=value` and, when enabled in ParserOptions,
+/// `-name`, `+v` and short option bundles. Long options must start with a letter; `--123` is a
+/// value unless ArgumentList has such long name registered.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultTokenizer;
+
+impl DefaultTokenizer {
+    /// Returns long name from word longer than 2 characters if it is written as a long option.
+    /// `-name` is accepted only with Prefix::SingleDash. `second` and `third` are already decoded
+    /// characters following the leading `-`.
+    fn long_name_of<'w>(
+        word: &'w str,
+        second: char,
+        third: char,
+        options: &ParserOptions,
+    ) -> Option<&'w str> {
+        match (second, third) {
+            ('-', c) if c.is_alphabetic() => Some(&word[2..]),
+            (c, _) if c.is_alphabetic() && options.long_prefix == Prefix::SingleDash => {
+                Some(&word[1..])
+            }
+            _ => None,
+        }
+    }
+
+    /// Classifies word starting with `+`, which can only be a short option or a bundle.
+    fn plus_option_of<'w>(word: &'w str, options: &ParserOptions) -> Token<'w> {
+        let mut chars = word[1..].chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_alphabetic() => Token::Short(c),
+            (Some(_), Some(_)) if options.short_bundling => {
+                DefaultTokenizer::bundle_of(word).map_or(Token::Value, Token::Bundle)
+            }
+            _ => Token::Value,
+        }
+    }
+
+    /// Returns options of a group of short options (e.g. `-vdo`). Characters are decoded only
+    /// once, while checking them.
+    fn bundle_of(word: &str) -> Option<Vec<char>> {
+        let options = word[1..]
+            .chars()
+            .map(|c| if c.is_alphabetic() { Some(c) } else { None })
+            .collect::<Option<Vec<char>>>()?;
+        if options.len() > 1 {
+            Some(options)
+        } else {
+            None
+        }
+    }
+}
+
+impl Tokenizer for DefaultTokenizer {
+    /// Classifies word in a single pass over its leading characters. Values not starting with
+    /// `-` (usually most of large inputs, e.g. file lists read from response files) are classified
+    /// after reading their first byte and `=` of `--name=value` is found with byte search.
+    fn tokenize<'w>(&self, word: &'w str, options: &ParserOptions) -> Token<'w> {
+        match word.as_bytes().first() {
+            Some(b'-') => (),
+            Some(b'+') if options.plus_options => {
+                return DefaultTokenizer::plus_option_of(word, options)
+            }
+            _ => return Token::Value,
+        }
+        // The first character is `-`, which is a single byte.
+        let mut chars = word[1..].chars();
+        let (second, third) = match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_alphabetic() => return Token::Short(c),
+            (Some(second), Some(third)) => (second, third),
+            _ => return Token::Value,
+        };
+        if let Some(long) = DefaultTokenizer::long_name_of(word, second, third, options) {
+            // Everything after the first `=` is the value, even if it contains more `=`.
+            let prefix = &word[..word.len() - long.len()];
+            match long.split_once('=') {
+                Some((name, value)) => Token::Long {
+                    prefix,
+                    name,
+                    value: Some(value),
+                },
+                None => Token::Long {
+                    prefix,
+                    name: long,
+                    value: None,
+                },
+            }
+        } else if options.short_bundling {
+            DefaultTokenizer::bundle_of(word).map_or(Token::Value, Token::Bundle)
+        } else {
+            Token::Value
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DefaultTokenizer, Token, Tokenizer};
+    use crate::options::{ParserOptions, Prefix};
+
+    #[test]
+    fn default_tokenizer_works() {
+        let options = ParserOptions::new();
+        assert_eq!(DefaultTokenizer.tokenize("-v", &options), Token::Short('v'));
+        assert_eq!(DefaultTokenizer.tokenize("-1", &options), Token::Value);
+        assert_eq!(DefaultTokenizer.tokenize("--", &options), Token::Value);
+        assert_eq!(
+            DefaultTokenizer.tokenize("--name=a=b", &options),
+            Token::Long {
+                prefix: "--",
+                name: "name",
+                value: Some("a=b"),
+            }
+        );
+        assert_eq!(DefaultTokenizer.tokenize("-vd", &options), Token::Value);
+        let options = ParserOptions::new()
+            .short_bundling(true)
+            .long_prefix(Prefix::SingleDash);
+        assert_eq!(
+            DefaultTokenizer.tokenize("-name", &options),
+            Token::Long {
+                prefix: "-",
+                name: "name",
+                value: None,
+            }
+        );
+        let options = ParserOptions::new().short_bundling(true);
+        assert_eq!(
+            DefaultTokenizer.tokenize("-vd", &options),
+            Token::Bundle(vec!['v', 'd'])
+        );
+        assert_eq!(
+            DefaultTokenizer.tokenize("-éa", &options),
+            Token::Bundle(vec!['é', 'a'])
+        );
+        assert_eq!(DefaultTokenizer.tokenize("-é", &options), Token::Short('é'));
+        assert_eq!(DefaultTokenizer.tokenize("-v1", &options), Token::Value);
+        assert_eq!(DefaultTokenizer.tokenize("file", &options), Token::Value);
+        assert_eq!(DefaultTokenizer.tokenize("", &options), Token::Value);
+    }
+
+    #[test]
+    fn default_tokenizer_plus_options_work() {
+        let options = ParserOptions::new();
+        assert_eq!(DefaultTokenizer.tokenize("+x", &options), Token::Value);
+        let options = ParserOptions::new().plus_options(true);
+        assert_eq!(DefaultTokenizer.tokenize("+x", &options), Token::Short('x'));
+        assert_eq!(DefaultTokenizer.tokenize("+xe", &options), Token::Value);
+        assert_eq!(DefaultTokenizer.tokenize("+1", &options), Token::Value);
+        assert_eq!(DefaultTokenizer.tokenize("+", &options), Token::Value);
+        let options = options.short_bundling(true);
+        assert_eq!(
+            DefaultTokenizer.tokenize("+xe", &options),
+            Token::Bundle(vec!['x', 'e'])
+        );
+    }
+
+    /// Classification done by parse_args before tokens were introduced, with `chars().nth()`
+    /// called separately for every check.
+    fn classify_by_nth<'w>(word: &'w str, options: &ParserOptions) -> Token<'w> {
+        let nth = |n| word.chars().nth(n);
+        let long = if nth(0) == Some('-') && nth(1) == Some('-') && nth(2).is_some() {
+            nth(2).filter(|c| c.is_alphabetic()).map(|_| &word[2..])
+        } else {
+            None
+        };
+        let single_dash_long = if options.long_prefix == Prefix::SingleDash
+            && nth(0) == Some('-')
+            && nth(1).is_some_and(char::is_alphabetic)
+            && nth(2).is_some()
+        {
+            Some(&word[1..])
+        } else {
+            None
+        };
+        if word.chars().count() == 2 && nth(0) == Some('-') && nth(1).unwrap().is_alphabetic() {
+            Token::Short(nth(1).unwrap())
+        } else if let Some(long) = long.or(single_dash_long) {
+            let prefix = &word[..word.len() - long.len()];
+            match long.split_once('=') {
+                Some((name, value)) => Token::Long {
+                    prefix,
+                    name,
+                    value: Some(value),
+                },
+                None => Token::Long {
+                    prefix,
+                    name: long,
+                    value: None,
+                },
+            }
+        } else if options.short_bundling
+            && word.chars().count() > 2
+            && nth(0) == Some('-')
+            && word.chars().skip(1).all(char::is_alphabetic)
+        {
+            Token::Bundle(word.chars().skip(1).collect())
+        } else {
+            Token::Value
+        }
+    }
+
+    #[test]
+    fn tokenize_matches_nth_classification() {
+        let words = [
+            "",
+            "-",
+            "--",
+            "---",
+            "-v",
+            "-1",
+            "-é",
+            "-vd",
+            "-v1",
+            "-éa",
+            "--a",
+            "--1a",
+            "--name",
+            "--name=",
+            "--name=a=b",
+            "-name",
+            "-name=value",
+            "-n=1",
+            "--=a",
+            "file",
+            "v-",
+            "ab",
+            "-\u{00e9}x",
+            "--ü",
+            "-ü-",
+        ];
+        let options = [
+            ParserOptions::new(),
+            ParserOptions::new().short_bundling(true),
+            ParserOptions::new().long_prefix(Prefix::SingleDash),
+            ParserOptions::new()
+                .short_bundling(true)
+                .long_prefix(Prefix::SingleDash),
+        ];
+        for options in options.iter() {
+            for word in words.iter() {
+                assert_eq!(
+                    DefaultTokenizer.tokenize(word, options),
+                    classify_by_nth(word, options),
+                    "{}",
+                    word
+                );
+            }
+        }
+    }
+}
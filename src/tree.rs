@@ -0,0 +1,214 @@
+use crate::{
+    argument::ArgumentIdentification, error::ParseError, parsed::ParsedItem, token::Token,
+    ArgumentList,
+};
+
+/// Role of input value in parsed command line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    /// Value naming argument (`-p`, `--path`, `--path=/file` or bundle `-vp`).
+    Argument,
+    /// Value consumed by preceding argument.
+    Value,
+    /// Value not attached to any argument.
+    Positional,
+}
+
+/// Single input value together with its classification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenNode {
+    text: String,
+    index: usize,
+    kind: TokenKind,
+    arguments: Vec<ArgumentIdentification>,
+}
+
+impl TokenNode {
+    /// Input value exactly as given by the user.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Position of the value in input.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// Arguments named by or consuming this value. Bundles name more than one argument, values
+    /// not attached to any argument have none.
+    pub fn arguments(&self) -> &Vec<ArgumentIdentification> {
+        &self.arguments
+    }
+}
+
+///
+/// Lossless view of parsed input. Contains every input value in original order with links to
+/// arguments which consumed it, so tools can rewrite command lines without changing anything
+/// they do not understand.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::{ArgumentList, argument::legacy_argument::*};
+///
+/// let mut args_list = ArgumentList::new();
+/// args_list.append_arg(Argument::new(None, Some("colour"), ArgType::Value).unwrap());
+/// let input = vec!["build", "--colour", "red"];
+/// let tree = args_list
+///     .parse_tree(input.iter().map(|s| String::from(*s)).collect())
+///     .unwrap();
+/// // Replace deprecated spelling of the option.
+/// let rewritten = tree.rewrite(|token| match token.text() {
+///     "--colour" => Some(String::from("--color")),
+///     _ => None,
+/// });
+/// assert_eq!(rewritten, vec!["build", "--color", "red"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseTree {
+    tokens: Vec<TokenNode>,
+}
+
+impl ParseTree {
+    pub fn tokens(&self) -> &Vec<TokenNode> {
+        &self.tokens
+    }
+
+    /// Returns input with values replaced by `replace`. Values for which it returns None are kept
+    /// unchanged.
+    pub fn rewrite<F>(&self, replace: F) -> Vec<String>
+    where
+        F: Fn(&TokenNode) -> Option<String>,
+    {
+        self.tokens
+            .iter()
+            .map(|token| replace(token).unwrap_or_else(|| token.text.clone()))
+            .collect()
+    }
+}
+
+impl<'a> ArgumentList<'a> {
+    /// Parses input like parse_args and returns ParseTree describing every input value. Indexes
+    /// refer to input after preprocessing.
+    pub fn parse_tree(&mut self, input: Vec<String>) -> Result<ParseTree, ParseError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("parse_tree", values = input.len()).entered();
+        let first_item = self.parsed.iter_in_order().len();
+        let input = match self.prepare_input(input) {
+            Ok(input) => input,
+            Err(error) => {
+                self.report_parse(first_item, Some(&error));
+                return Err(error);
+            }
+        };
+        let result = self.parse_prepared(&input);
+        self.report_parse(first_item, result.as_ref().err());
+        result?;
+        let mut tokens: Vec<TokenNode> = input
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| TokenNode {
+                text,
+                index,
+                kind: TokenKind::Positional,
+                arguments: Vec::new(),
+            })
+            .collect();
+        for item in self.parsed.iter_in_order().skip(first_item) {
+            if let ParsedItem::Argument(occurrence) = item {
+                let index = occurrence.index();
+                tokens[index].kind = TokenKind::Argument;
+                tokens[index]
+                    .arguments
+                    .push(occurrence.identification().clone());
+                // Name of preset given as a separate value belongs to `--preset`.
+                if occurrence.preset().is_some() {
+                    if let Token::Long { value: None, .. } = self.tokenize(&tokens[index].text) {
+                        tokens[index + 1].kind = TokenKind::Value;
+                    }
+                }
+                // Values given after `=` or by a preset are a part of the argument token.
+                for token in &mut tokens[occurrence.value_range()] {
+                    token.kind = TokenKind::Value;
+                    token.arguments.push(occurrence.identification().clone());
+                }
+            }
+        }
+        Result::Ok(ParseTree { tokens })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TokenKind;
+    use crate::{
+        argument::{
+            legacy_argument::{ArgType, Argument},
+            ArgumentIdentification,
+        },
+        config::ParserConfig,
+        options::ParserOptions,
+        ArgumentList,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn parse_tree_works() {
+        let mut args_list = ArgumentList::new();
+        args_list.set_options(ParserOptions::new().short_bundling(true));
+        args_list.append_arg(Argument::new_short('v', ArgType::Flag));
+        args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::ValueList).unwrap());
+        let input = vec!["-vp", "a", "file", "--path=b"];
+        let tree = args_list
+            .parse_tree(input.iter().map(|s| String::from(*s)).collect())
+            .unwrap();
+        let kinds: Vec<TokenKind> = tree.tokens().iter().map(|t| t.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Argument,
+                TokenKind::Value,
+                TokenKind::Positional,
+                TokenKind::Argument
+            ]
+        );
+        assert_eq!(
+            tree.tokens()[0].arguments(),
+            &vec![
+                ArgumentIdentification::Short('v'),
+                ArgumentIdentification::Both('p', String::from("path"))
+            ]
+        );
+        assert!(tree.tokens()[2].arguments().is_empty());
+        assert_eq!(tree.rewrite(|_| None), input);
+    }
+
+    #[test]
+    fn parse_tree_handles_presets() {
+        static REPORTED: AtomicUsize = AtomicUsize::new(0);
+        let mut args_list = ArgumentList::new();
+        args_list.set_config(ParserConfig {
+            on_parse_complete: Some(|report| {
+                REPORTED.fetch_add(report.arguments.len(), Ordering::SeqCst);
+            }),
+            ..ParserConfig::default()
+        });
+        args_list.append_arg(Argument::new(Some('p'), Some("path"), ArgType::ValueList).unwrap());
+        args_list.define_preset("home", &["-p", "a", "-p", "b"]);
+        let input = ["--preset", "home", "file"];
+        let tree = args_list
+            .parse_tree(input.iter().map(|s| String::from(*s)).collect())
+            .unwrap();
+        let kinds: Vec<TokenKind> = tree.tokens().iter().map(|t| t.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![TokenKind::Argument, TokenKind::Value, TokenKind::Positional]
+        );
+        assert_eq!(tree.tokens()[0].arguments().len(), 2);
+        assert!(tree.tokens()[1].arguments().is_empty());
+        assert_eq!(REPORTED.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,141 @@
+/// Check if value has to be surrounded by quotes.
+fn needs_quotes(value: &str) -> bool {
+    value.is_empty()
+        || value
+            .chars()
+            .any(|c| matches!(c, ' ' | '\t' | '\n' | '\u{b}' | '"'))
+}
+
+///
+/// Joins values into a single Windows command line following quoting rules of the Microsoft C
+/// runtime, so processes spawned on Windows get exactly the same values. Values containing
+/// whitespace or quotes are quoted and backslashes preceding quotes are escaped. Program name
+/// should not contain quotes, as they are not allowed in Windows paths.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::windows::to_windows_command_line;
+///
+/// let values = vec![
+///     String::from("tool.exe"),
+///     String::from("C:\\Program Files\\"),
+///     String::from("say \"hi\""),
+///     String::new(),
+/// ];
+/// assert_eq!(
+///     to_windows_command_line(&values),
+///     r#"tool.exe "C:\Program Files\\" "say \"hi\"" """#
+/// );
+/// ```
+pub fn to_windows_command_line(values: &[String]) -> String {
+    let mut line = String::new();
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            line.push(' ');
+        }
+        if !needs_quotes(value) {
+            line.push_str(value);
+            continue;
+        }
+        line.push('"');
+        let mut backslashes = 0;
+        for c in value.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                // Backslashes before a quote and the quote itself are escaped.
+                '"' => {
+                    line.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                    line.push('"');
+                    backslashes = 0;
+                }
+                _ => {
+                    line.extend(std::iter::repeat_n('\\', backslashes));
+                    line.push(c);
+                    backslashes = 0;
+                }
+            }
+        }
+        // Backslashes before the closing quote are escaped.
+        line.extend(std::iter::repeat_n('\\', backslashes * 2));
+        line.push('"');
+    }
+    line
+}
+
+///
+/// Splits command line into values, inverse of to_windows_command_line. Values are separated by
+/// spaces and tabs outside of quotes. `2n` backslashes followed by a quote become `n` backslashes
+/// and the quote starts or ends quoted text, `2n + 1` backslashes followed by a quote become `n`
+/// backslashes and a literal quote. Inside quoted text `""` is a literal quote. Other backslashes
+/// are kept as they are. Program name is split using the same rules.
+///
+/// # Examples
+/// ```
+/// use trivial_argument_parser::windows::from_windows_command_line;
+///
+/// assert_eq!(
+///     from_windows_command_line(r#"tool.exe "a b" c\d "e\"f" g""h """#),
+///     vec!["tool.exe", "a b", "c\\d", "e\"f", "gh", ""]
+/// );
+/// ```
+pub fn from_windows_command_line(line: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+        if chars.peek().is_none() {
+            return values;
+        }
+        let mut value = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    let mut backslashes = 1;
+                    while chars.next_if_eq(&'\\').is_some() {
+                        backslashes += 1;
+                    }
+                    if chars.peek() == Some(&'"') {
+                        value.extend(std::iter::repeat_n('\\', backslashes / 2));
+                        if backslashes % 2 == 1 {
+                            value.push('"');
+                            chars.next();
+                        }
+                    } else {
+                        value.extend(std::iter::repeat_n('\\', backslashes));
+                    }
+                }
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    value.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ' ' | '\t' if !quoted => break,
+                _ => value.push(c),
+            }
+        }
+        values.push(value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{from_windows_command_line, to_windows_command_line};
+
+    #[test]
+    fn command_line_round_trip_works() {
+        let cases = [
+            vec!["tool.exe"],
+            vec!["tool.exe", "", "a\tb", "new\nline"],
+            vec!["tool.exe", "\\\\server\\share\\", "\\\"", "a\\\\\"b\\"],
+            vec!["tool.exe", "\"", "\"\"", "ends with space "],
+        ];
+        for case in cases.iter() {
+            let values: Vec<String> = case.iter().map(|s| String::from(*s)).collect();
+            let line = to_windows_command_line(&values);
+            assert_eq!(from_windows_command_line(&line), values, "{}", line);
+        }
+        assert_eq!(to_windows_command_line(&[]), "");
+        assert!(from_windows_command_line(" \t ").is_empty());
+    }
+}